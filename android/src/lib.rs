@@ -1,5 +1,11 @@
 use bevy::{input::touch::Touch, prelude::*};
-use mazonic::{self, camera::CameraTarget, controller_screen_position::ControllerScreenPosition};
+use mazonic::{
+    self, camera::CameraTarget, controller_screen_position::ControllerScreenPosition,
+    soundfont::SoundfontSource,
+};
+
+// Below this separation in pixels the angle between the fingers is mostly noise.
+const MIN_TWIST_WIDTH: f32 = 40.0;
 
 #[bevy_main]
 fn main() {
@@ -14,6 +20,7 @@ fn main() {
     let save_location = mazonic::game_save::SaveLocation(internal_storage_path.clone());
 
     app.insert_resource(save_location);
+    app.insert_resource(SoundfontSource::File(internal_storage_path.join("soundfont.sf2")));
 
     mazonic::add_common_plugins(&mut app);
 
@@ -26,6 +33,7 @@ fn update_controller_position(
     touches: Res<Touches>,
     mut camera_target_query: Query<&mut CameraTarget>,
     mut local_start_camera_norm: Local<Option<f32>>,
+    mut local_start_camera_up: Local<Option<Vec3>>,
     mut controller_screen_position_query: Query<&mut ControllerScreenPosition>,
 ) {
     let Ok(mut controller_screen_position) = controller_screen_position_query.get_single_mut()
@@ -57,8 +65,17 @@ fn update_controller_position(
         let zoom_coefficient = compute_target_zoom_level(touch_1, touch_2);
 
         camera_target.set_zoom(start_camera_norm * zoom_coefficient);
+
+        let start_camera_up = *local_start_camera_up.get_or_insert(camera_target.up);
+
+        if let Some(twist_angle) = compute_twist_angle(touch_1, touch_2) {
+            // Rolling about the view axis keeps the solid turning with the fingers.
+            let roll = Quat::from_axis_angle(camera_target.translation_dir, twist_angle);
+            camera_target.up = roll * start_camera_up;
+        }
     } else {
         *local_start_camera_norm = None;
+        *local_start_camera_up = None;
     }
 }
 
@@ -68,3 +85,14 @@ fn compute_target_zoom_level(touch_1: &Touch, touch_2: &Touch) -> f32 {
 
     starting_width / f32::max(current_width, 1.0)
 }
+
+fn compute_twist_angle(touch_1: &Touch, touch_2: &Touch) -> Option<f32> {
+    let current_offset = touch_2.position() - touch_1.position();
+    let starting_offset = touch_2.start_position() - touch_1.start_position();
+
+    if current_offset.length() < MIN_TWIST_WIDTH || starting_offset.length() < MIN_TWIST_WIDTH {
+        return None;
+    }
+
+    Some(starting_offset.angle_to(current_offset))
+}
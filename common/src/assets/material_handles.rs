@@ -1,12 +1,15 @@
-use crate::{game_settings::GameSettings, levels::LEVELS, shape::{cube, dodecahedron, icosahedron, octahedron, tetrahedron}};
+use crate::{game_settings::{GameColorPalette, GameSettings}, levels::LEVELS, shape::{cube, dodecahedron, icosahedron, octahedron, tetrahedron}};
 use bevy::{
+    ecs::system::SystemParam,
     pbr::{ExtendedMaterial, MaterialExtension},
     prelude::*,
 };
 
-use super::shaders::{
-    DashedArrowShader, GlobalShader, MenuSelectionHoverShader, PlayerHaloShader,
-    PulsingDashedArrowShader, PulsingShader,
+use super::{
+    shaders::{
+        DashedArrowShader, GlobalShader, MenuSelectionHoverShader, PlayerHaloShader, PulsingShader,
+    },
+    symbol_sheet::SymbolSheet,
 };
 
 pub struct FaceMaterialHandles {
@@ -25,6 +28,7 @@ impl FaceMaterialHandles {
     pub fn tetrahedron(&self, coloring: &tetrahedron::Coloring) -> [Handle<ExtendedMaterial<StandardMaterial, GlobalShader>>; 4] {
         match coloring {
             tetrahedron::Coloring::Full(permutation) => self.get_materials([0, 1, 2, 3], permutation),
+            tetrahedron::Coloring::Stripes(permutation) => self.get_materials([0, 1, 1, 2], permutation),
             tetrahedron::Coloring::Dual(permutation) => self.get_materials([0, 0, 1, 1], permutation),
             tetrahedron::Coloring::Mono(color_id) => self.get_materials([0; 4], &[*color_id])
         }
@@ -65,206 +69,455 @@ impl FaceMaterialHandles {
     }
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MelodyEmphasis {
+    Subtle,
+    #[default]
+    Standard,
+    Bold,
+}
+
+impl MelodyEmphasis {
+    fn emissive_scale(&self) -> f32 {
+        match self {
+            MelodyEmphasis::Subtle => 0.25,
+            MelodyEmphasis::Standard => 1.0,
+            MelodyEmphasis::Bold => 2.5,
+        }
+    }
+}
+
+pub struct MelodyEmphasisHandles<M: Asset> {
+    pub subtle: Handle<M>,
+    pub standard: Handle<M>,
+    pub bold: Handle<M>,
+}
+
+impl<M: Asset> MelodyEmphasisHandles<M> {
+    fn reserve(assets: &Assets<M>) -> Self {
+        Self {
+            subtle: assets.reserve_handle(),
+            standard: assets.reserve_handle(),
+            bold: assets.reserve_handle(),
+        }
+    }
+
+    pub fn get(&self, emphasis: MelodyEmphasis) -> &Handle<M> {
+        match emphasis {
+            MelodyEmphasis::Subtle => &self.subtle,
+            MelodyEmphasis::Standard => &self.standard,
+            MelodyEmphasis::Bold => &self.bold,
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (MelodyEmphasis, &Handle<M>)> {
+        [
+            (MelodyEmphasis::Subtle, &self.subtle),
+            (MelodyEmphasis::Standard, &self.standard),
+            (MelodyEmphasis::Bold, &self.bold),
+        ]
+        .into_iter()
+    }
+}
+
 pub struct SelectorHandles {
     pub unavailable: Handle<StandardMaterial>,
     pub completed: Handle<StandardMaterial>,
+    pub perfect: Handle<StandardMaterial>,
     pub melody_found: Handle<StandardMaterial>,
     pub selection_pressed: Handle<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>,
     pub selection_hover: Handle<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>,
     pub level_symbols: Handle<StandardMaterial>,
     pub unavailable_level_symbols: Handle<StandardMaterial>,
-    pub melody_found_selector_face: Handle<ExtendedMaterial<StandardMaterial, PulsingShader>>,
+    pub melody_found_symbols: MelodyEmphasisHandles<ExtendedMaterial<StandardMaterial, PulsingShader>>,
     pub incomplete_face_colors: [Handle<StandardMaterial>; LEVELS.len()],
 }
 
+impl SelectorHandles {
+    pub fn reserve(
+        materials: &Assets<StandardMaterial>,
+        menu_selection_hover_materials: &Assets<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>,
+        pulsing_materials: &Assets<ExtendedMaterial<StandardMaterial, PulsingShader>>,
+    ) -> Self {
+        SelectorHandles {
+            unavailable: materials.reserve_handle(),
+            completed: materials.reserve_handle(),
+            perfect: materials.reserve_handle(),
+            melody_found: materials.reserve_handle(),
+            selection_pressed: menu_selection_hover_materials.reserve_handle(),
+            selection_hover: menu_selection_hover_materials.reserve_handle(),
+            level_symbols: materials.reserve_handle(),
+            unavailable_level_symbols: materials.reserve_handle(),
+            melody_found_symbols: MelodyEmphasisHandles::reserve(pulsing_materials),
+            incomplete_face_colors: core::array::from_fn(|_| materials.reserve_handle()),
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct MaterialHandles {
     pub player_halo_handle: Handle<ExtendedMaterial<StandardMaterial, PlayerHaloShader>>,
     pub player_handle: Handle<StandardMaterial>,
     pub line_handle: Handle<StandardMaterial>,
-    pub bright_line_handle: Handle<StandardMaterial>,
     pub dashed_arrow_handle: Handle<ExtendedMaterial<StandardMaterial, DashedArrowShader>>,
     pub bright_dashed_arrow_handle: Handle<ExtendedMaterial<StandardMaterial, DashedArrowShader>>,
+    pub melody_line_handles: MelodyEmphasisHandles<StandardMaterial>,
+    pub melody_dashed_arrow_handles: MelodyEmphasisHandles<ExtendedMaterial<StandardMaterial, DashedArrowShader>>,
+    pub path_trail_handle: Handle<ExtendedMaterial<StandardMaterial, DashedArrowShader>>,
     pub face_handles: FaceMaterialHandles,
     pub selector: SelectorHandles,
     pub goal_handle: Handle<ExtendedMaterial<StandardMaterial, PulsingShader>>,
+    pub high_visibility_ring_handle: Handle<ExtendedMaterial<StandardMaterial, PulsingShader>>,
 }
 
 pub const ALPHA_MODE: AlphaMode = AlphaMode::AlphaToCoverage;
 
+#[derive(SystemParam)]
+pub struct MaterialAssets<'w> {
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    dashed_arrow_materials: ResMut<'w, Assets<ExtendedMaterial<StandardMaterial, DashedArrowShader>>>,
+    player_halo_materials: ResMut<'w, Assets<ExtendedMaterial<StandardMaterial, PlayerHaloShader>>>,
+    pulsing_materials: ResMut<'w, Assets<ExtendedMaterial<StandardMaterial, PulsingShader>>>,
+    menu_selection_hover_materials:
+        ResMut<'w, Assets<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>>,
+    shape_face_materials: ResMut<'w, Assets<ExtendedMaterial<StandardMaterial, GlobalShader>>>,
+}
+
 pub fn setup_materials(
     mut commands: Commands,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    mut dashed_arrow_materials: ResMut<
-        Assets<ExtendedMaterial<StandardMaterial, DashedArrowShader>>,
-    >,
-    mut player_halo_materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, PlayerHaloShader>>>,
-    mut pulsing_materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, PulsingShader>>>,
-    mut pulsing_dashed_materials: ResMut<
-        Assets<ExtendedMaterial<StandardMaterial, PulsingDashedArrowShader>>,
-    >,
-    mut menu_selection_hover_materials: ResMut<
-        Assets<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>,
-    >,
-    mut shape_face_materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, GlobalShader>>>,
+    mut material_assets: MaterialAssets,
     asset_server: Res<AssetServer>,
     game_settings: Res<GameSettings>,
+    symbol_sheet: Res<SymbolSheet>,
 ) {
-    let goal_handle = pulsing_materials.add(ExtendedMaterial {
-        base: StandardMaterial {
-            base_color: game_settings.palette.player_color,
-            alpha_mode: ALPHA_MODE,
-            ..Default::default()
-        },
-        extension: PulsingShader {},
-    });
-
-    let player_color = &game_settings.palette.player_color.to_linear();
-    let player_halo_handle = player_halo_materials.add(ExtendedMaterial {
-        base: StandardMaterial {
-            base_color: Color::LinearRgba(*player_color),
-            emissive: LinearRgba::from_vec3(player_color.to_vec3() * 2.0),
-            alpha_mode: ALPHA_MODE,
-            diffuse_transmission: 1.0,
-            thickness: 0.17,
-            metallic: 0.2,
-            fog_enabled: true,
-            double_sided: true,
-            ..Default::default()
-        },
-        extension: PlayerHaloShader {},
-    });
-    let player_handle = materials.add(StandardMaterial {
-        base_color: Color::LinearRgba(*player_color),
-        emissive: LinearRgba::from_vec3(player_color.to_vec3() * 1.5),
-        alpha_mode: ALPHA_MODE,
-        reflectance: 0.1,
-        ..Default::default()
-    });
+    let material_handles = MaterialHandles::reserve(&material_assets);
+    let level_symbol_sprite_sheet = asset_server.load(&symbol_sheet.path);
 
-    let line_color = &game_settings.palette.line_color;
-    let line_color_vec = line_color.to_linear().to_vec3();
+    material_handles.write(
+        &game_settings.palette,
+        level_symbol_sprite_sheet,
+        &mut material_assets,
+    );
 
-    let line_material = StandardMaterial {
-        base_color: *line_color,
-        alpha_mode: ALPHA_MODE,
-        ..Default::default()
-    };
+    commands.insert_resource(material_handles);
+}
 
-    let bright_line = StandardMaterial {
-        base_color: *line_color,
-        alpha_mode: ALPHA_MODE,
-        emissive: LinearRgba::from_vec3(line_color_vec * 20.0),
-        ..Default::default()
-    };
+pub fn update_palette(
+    game_settings: Res<GameSettings>,
+    material_handles: Res<MaterialHandles>,
+    mut material_assets: MaterialAssets,
+    asset_server: Res<AssetServer>,
+    symbol_sheet: Res<SymbolSheet>,
+    mut previous_palette: Local<Option<GameColorPalette>>,
+) {
+    if !game_settings.is_changed() {
+        return;
+    }
+
+    let palette = &game_settings.palette;
+
+    if previous_palette
+        .replace(palette.clone())
+        .map_or(true, |previous| previous == *palette)
+    {
+        return;
+    }
+
+    let level_symbol_sprite_sheet = asset_server.load(&symbol_sheet.path);
+    material_handles.write(palette, level_symbol_sprite_sheet, &mut material_assets);
+}
+
+impl MaterialHandles {
+    fn reserve(material_assets: &MaterialAssets) -> Self {
+        let materials = &material_assets.materials;
+        let menu_selection_hover_materials = &material_assets.menu_selection_hover_materials;
+        let shape_face_materials = &material_assets.shape_face_materials;
+
+        let selector = SelectorHandles::reserve(
+            materials,
+            menu_selection_hover_materials,
+            &material_assets.pulsing_materials,
+        );
 
-    let bright_line_handle = materials.add(bright_line.clone());
+        MaterialHandles {
+            player_halo_handle: material_assets.player_halo_materials.reserve_handle(),
+            player_handle: materials.reserve_handle(),
+            line_handle: materials.reserve_handle(),
+            dashed_arrow_handle: material_assets.dashed_arrow_materials.reserve_handle(),
+            bright_dashed_arrow_handle: material_assets.dashed_arrow_materials.reserve_handle(),
+            melody_line_handles: MelodyEmphasisHandles::reserve(materials),
+            melody_dashed_arrow_handles: MelodyEmphasisHandles::reserve(&material_assets.dashed_arrow_materials),
+            path_trail_handle: material_assets.dashed_arrow_materials.reserve_handle(),
+            face_handles: FaceMaterialHandles {
+                face_handles: core::array::from_fn(|_| shape_face_materials.reserve_handle()),
+            },
+            selector,
+            goal_handle: material_assets.pulsing_materials.reserve_handle(),
+            high_visibility_ring_handle: material_assets.pulsing_materials.reserve_handle(),
+        }
+    }
+
+    // Overwrites the assets behind the existing handles so spawned entities pick up the new palette.
+    fn write(
+        &self,
+        palette: &GameColorPalette,
+        level_symbol_sprite_sheet: Handle<Image>,
+        material_assets: &mut MaterialAssets,
+    ) {
+        let MaterialAssets {
+            materials,
+            dashed_arrow_materials,
+            player_halo_materials,
+            pulsing_materials,
+            menu_selection_hover_materials,
+            shape_face_materials,
+        } = material_assets;
 
-    let dashed_arrow_handle = dashed_arrow_materials.add(ExtendedMaterial {
-        base: line_material.clone(),
-        extension: DashedArrowShader {},
-    });
+        pulsing_materials.insert(
+            &self.goal_handle,
+            ExtendedMaterial {
+                base: StandardMaterial {
+                    base_color: palette.player_color,
+                    alpha_mode: ALPHA_MODE,
+                    ..Default::default()
+                },
+                extension: PulsingShader {},
+            },
+        );
 
-    let bright_dashed_arrow_handle = dashed_arrow_materials.add(ExtendedMaterial {
-        base: bright_line.clone(),
-        extension: DashedArrowShader {},
-    });
+        // Contrasts with both the player and the face colors, so the marker stands out anywhere on the maze.
+        let ring_color = palette.line_color.to_linear();
+        pulsing_materials.insert(
+            &self.high_visibility_ring_handle,
+            ExtendedMaterial {
+                base: StandardMaterial {
+                    base_color: palette.line_color.with_alpha(0.7),
+                    emissive: LinearRgba::from_vec3(ring_color.to_vec3() * 4.0),
+                    alpha_mode: ALPHA_MODE,
+                    double_sided: true,
+                    ..Default::default()
+                },
+                extension: PulsingShader {},
+            },
+        );
 
-    let face_handles = game_settings.palette.face_colors.colors.map(|color| {
-        shape_face_materials.add(ExtendedMaterial {
-            base: StandardMaterial {
-                base_color: color,
-                reflectance: 0.0,
+        let player_color = &palette.player_color.to_linear();
+        player_halo_materials.insert(
+            &self.player_halo_handle,
+            ExtendedMaterial {
+                base: StandardMaterial {
+                    base_color: Color::LinearRgba(*player_color),
+                    emissive: LinearRgba::from_vec3(player_color.to_vec3() * 2.0),
+                    alpha_mode: ALPHA_MODE,
+                    diffuse_transmission: 1.0,
+                    thickness: 0.17,
+                    metallic: 0.2,
+                    fog_enabled: true,
+                    double_sided: true,
+                    ..Default::default()
+                },
+                extension: PlayerHaloShader {},
+            },
+        );
+        materials.insert(
+            &self.player_handle,
+            StandardMaterial {
+                base_color: Color::LinearRgba(*player_color),
+                emissive: LinearRgba::from_vec3(player_color.to_vec3() * 1.5),
                 alpha_mode: ALPHA_MODE,
-                perceptual_roughness: 1.0,
+                reflectance: 0.1,
                 ..Default::default()
             },
-            extension: GlobalShader {},
-        })
-    });
-
-    let selection_hover = menu_selection_hover_materials.add(ExtendedMaterial {
-        base: StandardMaterial {
-            base_color: line_color.with_alpha(0.55),
-            emissive: LinearRgba::from_vec3(line_color_vec * 2.0),
-            alpha_mode: ALPHA_MODE,
-            ..Default::default()
-        },
-        extension: MenuSelectionHoverShader {},
-    });
-    let selection_pressed = menu_selection_hover_materials.add(ExtendedMaterial {
-        base: StandardMaterial {
-            base_color: line_color.with_alpha(0.99),
-            emissive: LinearRgba::from_vec3(line_color_vec * 5.0),
+        );
+
+        let line_color = &palette.line_color;
+        let line_color_vec = line_color.to_linear().to_vec3();
+
+        let line_material = StandardMaterial {
+            base_color: *line_color,
             alpha_mode: ALPHA_MODE,
             ..Default::default()
-        },
-        extension: MenuSelectionHoverShader {},
-    });
-    let face_colors = &game_settings.palette.face_colors.colors;
-
-    let level_symbol_sprite_sheet = asset_server.load("sprites/symbols_sprite_sheet.png");
-    let level_symbols = materials.add(StandardMaterial {
-        base_color_texture: Some(level_symbol_sprite_sheet.clone()),
-        base_color: game_settings.palette.line_color,
-        alpha_mode: ALPHA_MODE,
-        emissive: LinearRgba::from_vec3(line_color_vec * 10.0),
-        ..Default::default()
-    });
-    let unavailable_level_symbols = materials.add(StandardMaterial {
-        base_color_texture: Some(level_symbol_sprite_sheet.clone()),
-        base_color: face_colors[4],
-        reflectance: 0.1,
-        metallic: 0.1,
-        alpha_mode: ALPHA_MODE,
-        ..Default::default()
-    });
+        };
 
-    let melody_found_selector_face = pulsing_materials.add(ExtendedMaterial {
-        base: StandardMaterial {
-            base_color: game_settings.palette.player_color,
-            base_color_texture: Some(level_symbol_sprite_sheet.clone()),
-            emissive: LinearRgba::from_vec3(player_color.to_vec3() * 2.0),
+        let bright_line = StandardMaterial {
+            base_color: *line_color,
             alpha_mode: ALPHA_MODE,
+            emissive: LinearRgba::from_vec3(line_color_vec * 20.0),
             ..Default::default()
-        },
-        extension: PulsingShader {},
-    });
-
-    let ready_easy_color = &game_settings.palette.face_colors.colors[0];
-    let ready_hard_color = &game_settings.palette.face_colors.colors[3];
-
-    let incomplete_face_colors = core::array::from_fn(|level_index| {
-        let material =
-            get_ready_selector_face_colors(level_index, ready_easy_color, ready_hard_color);
-        materials.add(material)
-    });
-
-    let selector_handles = SelectorHandles {
-        unavailable: materials.add(get_face_material_from_color(face_colors[4])),
-        completed: materials.add(get_face_material_from_color(face_colors[2])),
-        melody_found: materials.add(get_face_material_from_color(
-            game_settings.palette.player_color,
-        )),
-        selection_pressed,
-        selection_hover,
-        level_symbols,
-        unavailable_level_symbols,
-        melody_found_selector_face,
-        incomplete_face_colors,
-    };
-
-    commands.insert_resource(MaterialHandles {
-        player_halo_handle,
-        player_handle,
-        line_handle: materials.add(line_material),
-        bright_line_handle,
-        dashed_arrow_handle,
-        bright_dashed_arrow_handle,
-        face_handles: FaceMaterialHandles { face_handles },
-        selector: selector_handles,
-        goal_handle,
-    })
+        };
+
+        materials.insert(&self.line_handle, line_material.clone());
+
+        for (emphasis, melody_line_handle) in self.melody_line_handles.iter() {
+            materials.insert(melody_line_handle, melody_line_material(&bright_line, emphasis));
+        }
+
+        for (emphasis, melody_dashed_arrow_handle) in self.melody_dashed_arrow_handles.iter() {
+            dashed_arrow_materials.insert(
+                melody_dashed_arrow_handle,
+                ExtendedMaterial {
+                    base: melody_line_material(&bright_line, emphasis),
+                    extension: DashedArrowShader::default(),
+                },
+            );
+        }
+
+        dashed_arrow_materials.insert(
+            &self.dashed_arrow_handle,
+            ExtendedMaterial {
+                base: line_material,
+                extension: DashedArrowShader::default(),
+            },
+        );
+
+        dashed_arrow_materials.insert(
+            &self.bright_dashed_arrow_handle,
+            ExtendedMaterial {
+                base: bright_line,
+                extension: DashedArrowShader::default(),
+            },
+        );
+
+        dashed_arrow_materials.insert(
+            &self.path_trail_handle,
+            ExtendedMaterial {
+                base: StandardMaterial {
+                    base_color: Color::LinearRgba(*player_color),
+                    emissive: LinearRgba::from_vec3(player_color.to_vec3() * 4.0),
+                    alpha_mode: ALPHA_MODE,
+                    ..Default::default()
+                },
+                extension: DashedArrowShader::default(),
+            },
+        );
+
+        for (face_handle, color) in self
+            .face_handles
+            .face_handles
+            .iter()
+            .zip(palette.face_colors.colors)
+        {
+            shape_face_materials.insert(
+                face_handle,
+                ExtendedMaterial {
+                    base: StandardMaterial {
+                        base_color: color,
+                        reflectance: 0.0,
+                        alpha_mode: ALPHA_MODE,
+                        perceptual_roughness: 1.0,
+                        ..Default::default()
+                    },
+                    extension: GlobalShader {},
+                },
+            );
+        }
+
+        let selector_handles = &self.selector;
+
+        menu_selection_hover_materials.insert(
+            &selector_handles.selection_hover,
+            ExtendedMaterial {
+                base: StandardMaterial {
+                    base_color: line_color.with_alpha(0.55),
+                    emissive: LinearRgba::from_vec3(line_color_vec * 2.0),
+                    alpha_mode: ALPHA_MODE,
+                    ..Default::default()
+                },
+                extension: MenuSelectionHoverShader {},
+            },
+        );
+        menu_selection_hover_materials.insert(
+            &selector_handles.selection_pressed,
+            ExtendedMaterial {
+                base: StandardMaterial {
+                    base_color: line_color.with_alpha(0.99),
+                    emissive: LinearRgba::from_vec3(line_color_vec * 5.0),
+                    alpha_mode: ALPHA_MODE,
+                    ..Default::default()
+                },
+                extension: MenuSelectionHoverShader {},
+            },
+        );
+        let face_colors = &palette.face_colors.colors;
+
+        materials.insert(
+            &selector_handles.level_symbols,
+            StandardMaterial {
+                base_color_texture: Some(level_symbol_sprite_sheet.clone()),
+                base_color: palette.line_color,
+                alpha_mode: ALPHA_MODE,
+                emissive: LinearRgba::from_vec3(line_color_vec * 10.0),
+                ..Default::default()
+            },
+        );
+        materials.insert(
+            &selector_handles.unavailable_level_symbols,
+            StandardMaterial {
+                base_color_texture: Some(level_symbol_sprite_sheet.clone()),
+                base_color: face_colors[4],
+                reflectance: 0.1,
+                metallic: 0.1,
+                alpha_mode: ALPHA_MODE,
+                ..Default::default()
+            },
+        );
+
+        for (emphasis, melody_found_symbols_handle) in selector_handles.melody_found_symbols.iter() {
+            pulsing_materials.insert(
+                melody_found_symbols_handle,
+                ExtendedMaterial {
+                    base: StandardMaterial {
+                        base_color: palette.player_color,
+                        base_color_texture: Some(level_symbol_sprite_sheet.clone()),
+                        emissive: LinearRgba::from_vec3(
+                            player_color.to_vec3() * 2.0 * emphasis.emissive_scale(),
+                        ),
+                        alpha_mode: ALPHA_MODE,
+                        ..Default::default()
+                    },
+                    extension: PulsingShader {},
+                },
+            );
+        }
+
+        let ready_easy_color = &face_colors[0];
+        let ready_hard_color = &face_colors[3];
+
+        for (level_index, incomplete_face_handle) in
+            selector_handles.incomplete_face_colors.iter().enumerate()
+        {
+            let material =
+                get_ready_selector_face_colors(level_index, ready_easy_color, ready_hard_color);
+            materials.insert(incomplete_face_handle, material);
+        }
+
+        materials.insert(
+            &selector_handles.unavailable,
+            get_face_material_from_color(face_colors[4]),
+        );
+        materials.insert(
+            &selector_handles.completed,
+            get_face_material_from_color(face_colors[2]),
+        );
+        materials.insert(
+            &selector_handles.perfect,
+            StandardMaterial {
+                emissive: LinearRgba::from_vec3(face_colors[2].to_linear().to_vec3() * 0.6),
+                ..get_face_material_from_color(face_colors[2])
+            },
+        );
+        materials.insert(
+            &selector_handles.melody_found,
+            get_face_material_from_color(palette.player_color),
+        );
+    }
+}
+
+fn melody_line_material(bright_line: &StandardMaterial, emphasis: MelodyEmphasis) -> StandardMaterial {
+    StandardMaterial {
+        emissive: bright_line.emissive * emphasis.emissive_scale(),
+        ..bright_line.clone()
+    }
 }
 
 fn get_face_material_from_color(color: Color) -> StandardMaterial {
@@ -290,3 +543,95 @@ fn get_ready_selector_face_colors(
         ..Default::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{asset::AssetPlugin, ecs::system::RunSystemOnce};
+
+    use crate::game_settings::PalettePreset;
+
+    use super::*;
+
+    fn material_counts(world: &World) -> [usize; 6] {
+        [
+            world.resource::<Assets<StandardMaterial>>().len(),
+            world.resource::<Assets<ExtendedMaterial<StandardMaterial, DashedArrowShader>>>().len(),
+            world.resource::<Assets<ExtendedMaterial<StandardMaterial, PlayerHaloShader>>>().len(),
+            world.resource::<Assets<ExtendedMaterial<StandardMaterial, PulsingShader>>>().len(),
+            world.resource::<Assets<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>>().len(),
+            world.resource::<Assets<ExtendedMaterial<StandardMaterial, GlobalShader>>>().len(),
+        ]
+    }
+
+    fn material_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<StandardMaterial>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, DashedArrowShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, PlayerHaloShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, PulsingShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, GlobalShader>>()
+            .insert_resource(GameSettings::default())
+            .insert_resource(SymbolSheet::default());
+
+        app.world_mut().run_system_once(setup_materials).unwrap();
+        app
+    }
+
+    #[test]
+    fn palette_rewrite_reuses_every_material_handle() {
+        let mut app = material_app();
+        app.add_systems(Update, update_palette);
+        // The first update only records the starting palette.
+        app.update();
+
+        let counts = material_counts(app.world());
+        let player_handle = app.world().resource::<MaterialHandles>().player_handle.clone();
+
+        for preset in [PalettePreset::HighContrast, PalettePreset::ColorblindSafe] {
+            app.world_mut().resource_mut::<GameSettings>().palette = preset.palette();
+            app.update();
+        }
+
+        let world = app.world();
+        assert_eq!(material_counts(world), counts);
+        assert_eq!(world.resource::<MaterialHandles>().player_handle, player_handle);
+
+        let player_material = world.resource::<Assets<StandardMaterial>>().get(&player_handle).unwrap();
+        assert_eq!(
+            player_material.base_color.to_linear(),
+            PalettePreset::ColorblindSafe.palette().player_color.to_linear()
+        );
+    }
+
+    #[test]
+    fn each_melody_emphasis_maps_to_its_own_material_in_order_of_strength() {
+        let app = material_app();
+        let world = app.world();
+        let material_handles = world.resource::<MaterialHandles>();
+        let materials = world.resource::<Assets<StandardMaterial>>();
+        let dashed_arrow_materials = world.resource::<Assets<ExtendedMaterial<StandardMaterial, DashedArrowShader>>>();
+        let pulsing_materials = world.resource::<Assets<ExtendedMaterial<StandardMaterial, PulsingShader>>>();
+
+        let emphases = [MelodyEmphasis::Subtle, MelodyEmphasis::Standard, MelodyEmphasis::Bold];
+        let line_emissive = emphases.map(|emphasis| {
+            materials.get(material_handles.melody_line_handles.get(emphasis)).unwrap().emissive.to_vec3().length()
+        });
+        let arrow_emissive = emphases.map(|emphasis| {
+            dashed_arrow_materials.get(material_handles.melody_dashed_arrow_handles.get(emphasis)).unwrap().base.emissive.to_vec3().length()
+        });
+        let symbol_emissive = emphases.map(|emphasis| {
+            pulsing_materials.get(material_handles.selector.melody_found_symbols.get(emphasis)).unwrap().base.emissive.to_vec3().length()
+        });
+
+        for emissive in [line_emissive, arrow_emissive, symbol_emissive] {
+            assert!(emissive[0] < emissive[1] && emissive[1] < emissive[2], "{emissive:?}");
+        }
+
+        // The standard emphasis keeps the bright line's original glow.
+        let line_color = GameSettings::default().palette.line_color.to_linear().to_vec3();
+        assert!((line_emissive[1] - line_color.length() * 20.0).abs() < 1e-3);
+    }
+}
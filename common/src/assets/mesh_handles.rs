@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
+    game_settings::GameSettings,
     maze::maze_mesh_builder::MazeMeshBuilder,
     shape::{cube, dodecahedron, icosahedron, octahedron, tetrahedron},
 };
@@ -25,6 +26,30 @@ pub struct ShapeMazeEdgeMeshHandles {
     pub icosahedron: MazeEdgeMeshHandles,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoomMarkerStyle {
+    #[default]
+    Circle,
+    Square,
+    Hexagon,
+}
+
+pub struct RoomMarkerMeshHandles {
+    pub circle: Handle<Mesh>,
+    pub square: Handle<Mesh>,
+    pub hexagon: Handle<Mesh>,
+}
+
+impl RoomMarkerMeshHandles {
+    pub fn get(&self, style: RoomMarkerStyle) -> &Handle<Mesh> {
+        match style {
+            RoomMarkerStyle::Circle => &self.circle,
+            RoomMarkerStyle::Square => &self.square,
+            RoomMarkerStyle::Hexagon => &self.hexagon,
+        }
+    }
+}
+
 pub struct ShapeMeshHandles {
     pub tetrahedron: [Handle<Mesh>; 4],
     pub cube: [Handle<Mesh>; 6],
@@ -38,20 +63,20 @@ pub struct MeshHandles {
     pub player: Handle<Mesh>,
     pub player_halo: Handle<Mesh>,
     pub goal_room: Handle<Mesh>,
-    pub junction_room: Handle<Mesh>,
+    pub junction_room: RoomMarkerMeshHandles,
     pub node_arrival_effect: Handle<Mesh>,
     pub shape_mesh_handles: ShapeMeshHandles,
     pub shape_maze_edge_mesh_handles: ShapeMazeEdgeMeshHandles,
 }
 
-pub fn setup_mesh_handles(mut meshes: ResMut<Assets<Mesh>>, mut commands: Commands) {
+pub fn setup_mesh_handles(mut meshes: ResMut<Assets<Mesh>>, mut commands: Commands, game_settings: Res<GameSettings>) {
     let player = meshes.add(Sphere::new(1.0));
     let player_halo = meshes.add(Sphere::new(1.08));
     let goal_room = meshes.add(Circle::new(1.0 / 5.5));
-    let junction_room = meshes.add(Circle::new(1.0 / 6.0));
+    let junction_room = get_room_marker_mesh_handles(&mut meshes, 1.0 / 6.0);
     let node_arrival_effect = meshes.add(Circle::new(0.1));
     let shape_mesh_handles = get_shape_mesh_handles(&mut meshes);
-    let shape_maze_edge_mesh_handles = get_shape_maze_edge_mesh_handles(&mut meshes);
+    let shape_maze_edge_mesh_handles = get_shape_maze_edge_mesh_handles(&mut meshes, game_settings.fold_curvature);
 
     commands.insert_resource(MeshHandles {
         player,
@@ -64,12 +89,23 @@ pub fn setup_mesh_handles(mut meshes: ResMut<Assets<Mesh>>, mut commands: Comman
     })
 }
 
-fn get_shape_maze_edge_mesh_handles(mut meshes: &mut Assets<Mesh>) -> ShapeMazeEdgeMeshHandles {
-    let tetrahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::tetrahedron());
-    let cube = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::cube());
-    let octahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::octahedron());
-    let dodecahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::dodecahedron());
-    let icosahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::icosahedron());
+fn get_room_marker_mesh_handles(meshes: &mut Assets<Mesh>, radius: f32) -> RoomMarkerMeshHandles {
+    // The square is shrunk so its area roughly matches the circle marker.
+    let square_side = radius * std::f32::consts::PI.sqrt();
+
+    RoomMarkerMeshHandles {
+        circle: meshes.add(Circle::new(radius)),
+        square: meshes.add(Rectangle::new(square_side, square_side)),
+        hexagon: meshes.add(RegularPolygon::new(radius, 6)),
+    }
+}
+
+fn get_shape_maze_edge_mesh_handles(mut meshes: &mut Assets<Mesh>, fold_curvature: f32) -> ShapeMazeEdgeMeshHandles {
+    let tetrahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::tetrahedron().with_fold_curvature(fold_curvature));
+    let cube = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::cube().with_fold_curvature(fold_curvature));
+    let octahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::octahedron().with_fold_curvature(fold_curvature));
+    let dodecahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::dodecahedron().with_fold_curvature(fold_curvature));
+    let icosahedron = get_maze_edge_mesh_handles(&mut meshes, MazeMeshBuilder::icosahedron().with_fold_curvature(fold_curvature));
 
     ShapeMazeEdgeMeshHandles {
         tetrahedron,
@@ -113,3 +149,51 @@ fn get_shape_mesh_handles(mut meshes: &mut Assets<Mesh>) -> ShapeMeshHandles {
         icosahedron,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::MeshAabb;
+
+    use super::*;
+
+    const RADIUS: f32 = 1.0 / 6.0;
+
+    #[test]
+    fn each_room_marker_style_has_its_own_mesh() {
+        let mut meshes = Assets::<Mesh>::default();
+        let handles = get_room_marker_mesh_handles(&mut meshes, RADIUS);
+
+        let styles = [RoomMarkerStyle::Circle, RoomMarkerStyle::Square, RoomMarkerStyle::Hexagon];
+        for (index, style) in styles.iter().enumerate() {
+            assert!(meshes.contains(handles.get(*style)));
+            for other in &styles[index + 1..] {
+                assert_ne!(handles.get(*style), handles.get(*other));
+            }
+        }
+        assert_eq!(handles.get(RoomMarkerStyle::default()), &handles.circle);
+    }
+
+    #[test]
+    fn square_marker_covers_the_same_area_as_the_circle() {
+        let mut meshes = Assets::<Mesh>::default();
+        let handles = get_room_marker_mesh_handles(&mut meshes, RADIUS);
+
+        let square = meshes.get(handles.get(RoomMarkerStyle::Square)).unwrap();
+        let size = square.compute_aabb().unwrap().half_extents * 2.0;
+
+        let circle_area = std::f32::consts::PI * RADIUS * RADIUS;
+        assert!((size.x * size.y - circle_area).abs() < 1e-5);
+    }
+
+    #[test]
+    fn hexagon_marker_fits_within_the_circle() {
+        let mut meshes = Assets::<Mesh>::default();
+        let handles = get_room_marker_mesh_handles(&mut meshes, RADIUS);
+
+        let hexagon = meshes.get(handles.get(RoomMarkerStyle::Hexagon)).unwrap();
+        let positions = hexagon.attribute(Mesh::ATTRIBUTE_POSITION).unwrap().as_float3().unwrap();
+
+        assert_eq!(positions.len(), 6);
+        assert!(positions.iter().all(|position| (Vec3::from(*position).length() - RADIUS).abs() < 1e-5));
+    }
+}
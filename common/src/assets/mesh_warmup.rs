@@ -0,0 +1,191 @@
+use std::{cmp::Reverse, collections::VecDeque, mem::discriminant};
+
+use bevy::prelude::*;
+use itertools::Itertools;
+
+use crate::{
+    game_state::PuzzleState,
+    levels::{Shape, LEVELS},
+};
+
+use super::{
+    material_handles::MaterialHandles,
+    mesh_handles::{MazeEdgeMeshHandles, MeshHandles},
+};
+
+// Shapes are warmed one per frame so the loading screen and selector never stall on a long
+// batch of uploads, which matters most on mobile.
+const SHAPES_PER_FRAME: usize = 1;
+// Long enough for the meshes to be extracted and drawn at least once.
+const WARMUP_FRAMES: u32 = 2;
+// Small enough to stay hidden inside whatever solid is at the origin.
+const WARMUP_SCALE: f32 = 0.0001;
+
+#[derive(Resource, Default)]
+pub struct MeshWarmup {
+    queue: VecDeque<Shape>,
+}
+
+#[derive(Component)]
+pub struct WarmupEntity {
+    frames_left: u32,
+}
+
+// The mesh handles are built at startup, but their GPU upload and pipeline compilation only
+// happen the first time they are drawn. Drawing each shape once up front moves that cost off the
+// first frame of a level.
+pub fn setup(mut commands: Commands) {
+    // The shapes the campaign uses most are warmed first; the rest follow lazily. Meshes depend
+    // only on the kind of shape, not its size or coloring, so a daily of any size is covered as long
+    // as its kind appears somewhere in LEVELS.
+    let queue = LEVELS
+        .iter()
+        .enumerate()
+        .into_group_map_by(|(_, level)| discriminant(&level.shape))
+        .into_values()
+        .sorted_by_key(|levels| (Reverse(levels.len()), levels[0].0))
+        .map(|levels| levels[0].1.shape.clone())
+        .collect();
+
+    commands.insert_resource(MeshWarmup { queue });
+}
+
+pub fn warmup_pending(mesh_warmup: Res<MeshWarmup>, puzzle_state: Option<Res<State<PuzzleState>>>) -> bool {
+    let playing = puzzle_state.is_some_and(|state| *state.get() == PuzzleState::Playing);
+    !mesh_warmup.queue.is_empty() && !playing
+}
+
+pub fn warm_next_shapes(
+    mut commands: Commands,
+    mut mesh_warmup: ResMut<MeshWarmup>,
+    mesh_handles: Res<MeshHandles>,
+    material_handles: Res<MaterialHandles>,
+) {
+    for _ in 0..SHAPES_PER_FRAME {
+        let Some(shape) = mesh_warmup.queue.pop_front() else {
+            return;
+        };
+
+        debug!(target: "mazonic::warmup", "Warming meshes for {:?}", shape);
+
+        let (face_meshes, edge_meshes) = shape_meshes(&mesh_handles, &shape);
+        let face_material = &material_handles.face_handles.face_handles[0];
+
+        for face_mesh in face_meshes {
+            commands.spawn((
+                Mesh3d(face_mesh.clone()),
+                MeshMaterial3d(face_material.clone()),
+                Transform::from_scale(Vec3::splat(WARMUP_SCALE)),
+                WarmupEntity { frames_left: WARMUP_FRAMES },
+            ));
+        }
+
+        for edge_mesh in [&edge_meshes.same_face_edge, &edge_meshes.cross_face_edge] {
+            commands.spawn((
+                Mesh3d(edge_mesh.clone()),
+                MeshMaterial3d(material_handles.line_handle.clone()),
+                Transform::from_scale(Vec3::splat(WARMUP_SCALE)),
+                WarmupEntity { frames_left: WARMUP_FRAMES },
+            ));
+        }
+
+        for edge_mesh in [&edge_meshes.one_way_same_face_edge, &edge_meshes.one_way_cross_face_edge] {
+            commands.spawn((
+                Mesh3d(edge_mesh.clone()),
+                MeshMaterial3d(material_handles.dashed_arrow_handle.clone()),
+                Transform::from_scale(Vec3::splat(WARMUP_SCALE)),
+                WarmupEntity { frames_left: WARMUP_FRAMES },
+            ));
+        }
+    }
+}
+
+pub fn despawn_warmed(mut commands: Commands, mut warmup_query: Query<(Entity, &mut WarmupEntity)>) {
+    for (entity, mut warmup_entity) in warmup_query.iter_mut() {
+        if warmup_entity.frames_left == 0 {
+            commands.entity(entity).despawn();
+        } else {
+            warmup_entity.frames_left -= 1;
+        }
+    }
+}
+
+fn shape_meshes<'a>(mesh_handles: &'a MeshHandles, shape: &Shape) -> (&'a [Handle<Mesh>], &'a MazeEdgeMeshHandles) {
+    let shape_mesh_handles = &mesh_handles.shape_mesh_handles;
+    let edge_mesh_handles = &mesh_handles.shape_maze_edge_mesh_handles;
+
+    match shape {
+        Shape::Tetrahedron(_) => (shape_mesh_handles.tetrahedron.as_slice(), &edge_mesh_handles.tetrahedron),
+        Shape::Cube(_) => (shape_mesh_handles.cube.as_slice(), &edge_mesh_handles.cube),
+        Shape::Octahedron(_) => (shape_mesh_handles.octahedron.as_slice(), &edge_mesh_handles.octahedron),
+        Shape::Dodecahedron(_) => (shape_mesh_handles.dodecahedron.as_slice(), &edge_mesh_handles.dodecahedron),
+        Shape::Icosahedron(_) => (shape_mesh_handles.icosahedron.as_slice(), &edge_mesh_handles.icosahedron),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{asset::AssetPlugin, ecs::system::RunSystemOnce, pbr::ExtendedMaterial};
+
+    use crate::{
+        assets::{
+            material_handles::setup_materials,
+            mesh_handles::setup_mesh_handles,
+            shaders::{DashedArrowShader, GlobalShader, MenuSelectionHoverShader, PlayerHaloShader, PulsingShader},
+            symbol_sheet::SymbolSheet,
+        },
+        game_settings::GameSettings,
+        shape,
+    };
+
+    use super::*;
+
+    fn warmup_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .init_asset::<Mesh>()
+            .init_asset::<StandardMaterial>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, DashedArrowShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, PlayerHaloShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, PulsingShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, MenuSelectionHoverShader>>()
+            .init_asset::<ExtendedMaterial<StandardMaterial, GlobalShader>>()
+            .insert_resource(GameSettings::default())
+            .insert_resource(SymbolSheet::default());
+
+        let world = app.world_mut();
+        world.run_system_once(setup_materials).unwrap();
+        world.run_system_once(setup_mesh_handles).unwrap();
+        app
+    }
+
+    #[test]
+    fn a_warmed_shape_spawns_its_level_without_new_meshes() {
+        let mut app = warmup_app();
+        let level = LEVELS[1].clone();
+        let world = app.world_mut();
+        world.insert_resource(MeshWarmup { queue: VecDeque::from([level.shape.clone()]) });
+
+        world.run_system_once(warm_next_shapes).unwrap();
+        assert!(world.resource::<MeshWarmup>().queue.is_empty());
+        let warmed_meshes = world
+            .query::<&Mesh3d>()
+            .iter(world)
+            .map(|Mesh3d(handle)| handle.id())
+            .collect::<Vec<_>>();
+        let mesh_count = world.resource::<Assets<Mesh>>().len();
+
+        world.spawn(level);
+        world.run_system_once(shape::spawn).unwrap();
+
+        assert_eq!(world.resource::<Assets<Mesh>>().len(), mesh_count);
+        let level_meshes = world
+            .query_filtered::<&Mesh3d, Without<WarmupEntity>>()
+            .iter(world)
+            .map(|Mesh3d(handle)| handle.id())
+            .collect::<Vec<_>>();
+        assert!(!level_meshes.is_empty());
+        assert!(level_meshes.iter().all(|mesh| warmed_meshes.contains(mesh)));
+    }
+}
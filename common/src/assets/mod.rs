@@ -1,4 +1,6 @@
 pub mod material_handles;
 pub mod mesh_generators;
 pub mod mesh_handles;
+pub mod mesh_warmup;
 pub mod shaders;
+pub mod symbol_sheet;
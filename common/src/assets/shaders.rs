@@ -23,8 +23,27 @@ impl Plugin for ShadersPlugin {
     }
 }
 
+// Multiplies the time term of the dash pattern; 1.0 is the original scroll rate and higher values
+// make one-way edges flow faster.
+pub const DASH_SCROLL_SPEED: f32 = 1.0;
+
 #[derive(Asset, TypePath, AsBindGroup, Debug, Clone)]
-pub struct DashedArrowShader {}
+pub struct DashedArrowShader {
+    #[uniform(100)]
+    pub scroll_speed: f32,
+    // WebGL2 rejects uniform buffers smaller than 16 bytes.
+    #[uniform(100)]
+    _webgl2_padding: Vec3,
+}
+
+impl Default for DashedArrowShader {
+    fn default() -> Self {
+        Self {
+            scroll_speed: DASH_SCROLL_SPEED,
+            _webgl2_padding: Vec3::ZERO,
+        }
+    }
+}
 
 impl MaterialExtension for DashedArrowShader {
     fn fragment_shader() -> ShaderRef {
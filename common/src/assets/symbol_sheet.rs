@@ -0,0 +1,220 @@
+use bevy::{asset::LoadState, prelude::*, utils::HashMap};
+use serde::Deserialize;
+
+const SYMBOL_SHEET_CONFIG_PATH: &str = "sprites/symbols_sprite_sheet.json";
+
+// Compiled in so builds that can't read the asset folder, like the web one, still know the grid.
+const EMBEDDED_SYMBOL_SHEET_JSON: &str =
+    include_str!("../../../desktop/assets/sprites/symbols_sprite_sheet.json");
+
+#[derive(Resource, Deserialize, Debug, Clone)]
+pub struct SymbolSheet {
+    pub path: String,
+    pub cell_size: u32,
+    pub columns: u32,
+    pub rows: u32,
+    pub symbols: HashMap<String, UVec2>,
+}
+
+impl Default for SymbolSheet {
+    fn default() -> Self {
+        serde_json::from_str(EMBEDDED_SYMBOL_SHEET_JSON)
+            .expect("embedded symbol sheet config should be valid json")
+    }
+}
+
+impl SymbolSheet {
+    // Reading the shipped config lets the sheet be re-laid out without a rebuild; the embedded copy
+    // stands in when the file is missing or can't be parsed.
+    pub fn load() -> Self {
+        read_symbol_sheet_config()
+            .and_then(|json| serde_json::from_str(&json).map_err(|err| err.to_string()))
+            .unwrap_or_else(|err| {
+                warn!(target: "mazonic::symbols", "Using the embedded symbol sheet config: {}", err);
+                SymbolSheet::default()
+            })
+    }
+
+    pub fn cell(&self, symbol: &str) -> UVec2 {
+        self.symbols.get(symbol).copied().unwrap_or_else(|| {
+            warn!(target: "mazonic::symbols", "Symbol {:?} missing from the symbol sheet", symbol);
+            UVec2::ZERO
+        })
+    }
+
+    pub fn uv_rect(&self, symbol: &str) -> Rect {
+        let grid_dimensions = UVec2::new(self.columns, self.rows).as_vec2();
+        let cell = self.cell(symbol).as_vec2();
+
+        Rect::from_corners(cell / grid_dimensions, (cell + Vec2::ONE) / grid_dimensions)
+    }
+
+    pub fn pixel_rect(&self, symbol: &str) -> Rect {
+        let cell_size = self.cell_size as f32;
+        let min = self.cell(symbol).as_vec2() * cell_size;
+
+        Rect::from_corners(min, min + Vec2::splat(cell_size))
+    }
+
+    pub fn symbol_mesh(&self, symbol: &str) -> Mesh {
+        let Rect { min: min_uv, max: max_uv } = self.uv_rect(symbol);
+
+        let uvs = vec![
+            min_uv.to_array(),
+            [min_uv.x, max_uv.y],
+            max_uv.to_array(),
+            [max_uv.x, min_uv.y],
+        ];
+
+        let symbol_mesh = Mesh::from(Rectangle::new(1.0, 1.0));
+        symbol_mesh.with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    }
+
+    pub fn validate(&self, image_size: UVec2) -> Result<(), String> {
+        let expected_size = UVec2::new(self.columns, self.rows) * self.cell_size;
+        if image_size != expected_size {
+            return Err(format!(
+                "sprite sheet is {}x{} but the grid declares {}x{}",
+                image_size.x, image_size.y, expected_size.x, expected_size.y
+            ));
+        }
+
+        match self
+            .symbols
+            .iter()
+            .find(|(_, cell)| cell.x >= self.columns || cell.y >= self.rows)
+        {
+            Some((symbol, cell)) => Err(format!("symbol {:?} at {} lies outside the grid", symbol, cell)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+fn read_symbol_sheet_config() -> Result<String, String> {
+    let path = bevy::asset::io::file::FileAssetReader::get_base_path()
+        .join("assets")
+        .join(SYMBOL_SHEET_CONFIG_PATH);
+
+    std::fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+#[cfg(any(target_arch = "wasm32", target_os = "android"))]
+fn read_symbol_sheet_config() -> Result<String, String> {
+    Err(format!("{} is not readable on this platform", SYMBOL_SHEET_CONFIG_PATH))
+}
+
+// Without an image behind the handle the symbol materials never render, so a missing sheet is
+// replaced by a plain placeholder and the symbols show as flat tiles instead.
+pub fn fallback_missing_sprite_sheet(
+    symbol_sheet: Res<SymbolSheet>,
+    mut images: ResMut<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    mut replaced: Local<bool>,
+) {
+    if *replaced {
+        return;
+    }
+
+    let Some(handle) = asset_server.get_handle::<Image>(&symbol_sheet.path) else {
+        return;
+    };
+
+    if !matches!(asset_server.load_state(handle.id()), LoadState::Failed(_)) {
+        return;
+    }
+
+    warn!(target: "mazonic::symbols", "Symbol sheet {:?} failed to load; using a placeholder", symbol_sheet.path);
+    images.insert(&handle, Image::default());
+    *replaced = true;
+}
+
+pub fn validate_sprite_sheet(
+    symbol_sheet: Res<SymbolSheet>,
+    images: Res<Assets<Image>>,
+    asset_server: Res<AssetServer>,
+    mut validated: Local<bool>,
+) {
+    if *validated {
+        return;
+    }
+
+    let Some(image) = asset_server
+        .get_handle::<Image>(&symbol_sheet.path)
+        .and_then(|handle| images.get(&handle))
+    else {
+        return;
+    };
+
+    if let Err(err) = symbol_sheet.validate(image.size()) {
+        warn!(target: "mazonic::symbols", "Symbol sheet {:?} does not match its config: {}", symbol_sheet.path, err);
+    }
+
+    *validated = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::asset::AssetPlugin;
+
+    use super::*;
+
+    #[test]
+    fn a_sheet_that_fails_to_load_is_replaced_by_a_placeholder() {
+        let path = "sprites/missing_symbols.png";
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<Image>()
+            .insert_resource(SymbolSheet { path: path.to_string(), ..default() })
+            .add_systems(Update, fallback_missing_sprite_sheet);
+
+        let handle = app.world().resource::<AssetServer>().load::<Image>(path);
+
+        for _ in 0..200 {
+            app.update();
+            if app.world().resource::<Assets<Image>>().contains(&handle) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(app.world().resource::<Assets<Image>>().contains(&handle));
+    }
+
+    #[test]
+    fn uv_rects_cover_one_cell_of_the_five_by_five_grid() {
+        let symbol_sheet = SymbolSheet::default();
+
+        let cube = symbol_sheet.uv_rect("cube");
+        assert_eq!(cube.min, Vec2::new(3.0, 1.0) / 5.0);
+        assert_eq!(cube.max, Vec2::new(4.0, 2.0) / 5.0);
+
+        let nine = symbol_sheet.uv_rect("9");
+        assert_eq!(nine.min, Vec2::new(3.0, 3.0) / 5.0);
+        assert_eq!(nine.max, Vec2::new(4.0, 4.0) / 5.0);
+    }
+
+    #[test]
+    fn the_embedded_config_matches_its_sheet() {
+        let symbol_sheet = SymbolSheet::default();
+
+        assert_eq!(symbol_sheet.validate(UVec2::splat(5 * 512)), Ok(()));
+    }
+
+    #[test]
+    fn an_image_of_the_wrong_size_fails_validation() {
+        let symbol_sheet = SymbolSheet::default();
+
+        assert!(symbol_sheet.validate(UVec2::new(100, 100)).is_err());
+    }
+
+    #[test]
+    fn a_symbol_outside_the_grid_fails_validation() {
+        let mut symbol_sheet = SymbolSheet::default();
+        symbol_sheet.symbols.insert("stray".to_string(), UVec2::new(5, 0));
+
+        assert!(symbol_sheet.validate(UVec2::splat(5 * 512)).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use bevy::{ecs::system::RunSystemOnce, prelude::*, state::app::StatesPlugin, utils::HashMap};
+use bevy_rustysynth::MidiAudio;
+
+use crate::{
+    custom_maze,
+    error::MazonicError,
+    game_save::{CurrentPuzzle, PuzzleIdentifier},
+    game_settings::GameSettings,
+    game_state::{victory_transition, GameState, PuzzleState},
+    play_statistics::{self, PlayStatistics},
+    player::PlayerMazeState,
+    player_path::{self, PlayerPath},
+    room::Room,
+    shape::loader::{MazeLevelData, SolutionComponent},
+    snap_assist::SnapAssist,
+};
+
+const STEP_SECONDS: f32 = 0.1;
+// How long the goal may be occupied without the victory transition firing before it is reported.
+const VICTORY_TIMEOUT_SECONDS: f32 = 1.0;
+
+#[derive(Resource, Default)]
+pub struct Autoplay {
+    since_last_step: f32,
+    on_goal_for: Option<f32>,
+}
+
+// Walks the player node by node along the stored solution, skipping raycasting entirely, and
+// reports whether the victory transition and statistics update follow.
+#[derive(Default)]
+pub struct AutoplayPlugin;
+
+impl Plugin for AutoplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Autoplay>()
+            .add_systems(OnEnter(PuzzleState::Playing), reset)
+            .add_systems(Update, step_along_solution.run_if(in_state(PuzzleState::Playing)))
+            .add_systems(
+                OnEnter(PuzzleState::Victory),
+                check_victory.after(play_statistics::on_victory),
+            );
+    }
+}
+
+pub fn reset(mut autoplay: ResMut<Autoplay>) {
+    *autoplay = Autoplay::default();
+}
+
+pub fn step_along_solution(
+    mut autoplay: ResMut<Autoplay>,
+    mut player_query: Query<&mut PlayerMazeState>,
+    solution_query: Query<&SolutionComponent>,
+    time: Res<Time>,
+) {
+    let Ok(SolutionComponent(solution)) = solution_query.get_single() else {
+        return;
+    };
+
+    let Ok(mut player_maze_state) = player_query.get_single_mut() else {
+        return;
+    };
+
+    if let Some(on_goal_for) = autoplay.on_goal_for.as_mut() {
+        *on_goal_for += time.delta_secs();
+        if *on_goal_for > VICTORY_TIMEOUT_SECONDS {
+            error!(target: "mazonic::autoplay", "Reached the goal but the victory transition did not fire");
+            autoplay.on_goal_for = None;
+        }
+        return;
+    }
+
+    autoplay.since_last_step += time.delta_secs();
+    if autoplay.since_last_step < STEP_SECONDS {
+        return;
+    }
+    autoplay.since_last_step = 0.0;
+
+    let Some(next_room) = next_solution_room(&player_maze_state, solution) else {
+        error!(target: "mazonic::autoplay", "Player is off the solution; cannot continue");
+        return;
+    };
+
+    *player_maze_state = PlayerMazeState::Node(*next_room);
+
+    if Some(next_room) == solution.last() {
+        autoplay.on_goal_for = Some(0.0);
+    }
+}
+
+pub fn next_solution_room<'a>(player_maze_state: &PlayerMazeState, solution: &'a [Room]) -> Option<&'a Room> {
+    match player_maze_state {
+        PlayerMazeState::Node(room) => solution
+            .iter()
+            .position(|solution_room| solution_room == room)
+            .and_then(|index| solution.get(index + 1)),
+        // Autoplay only moves between nodes, so an edge state means the player was moved by hand.
+        PlayerMazeState::Edge(..) => solution.first(),
+    }
+}
+
+pub fn check_victory(
+    mut autoplay: ResMut<Autoplay>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    play_statistics: Res<PlayStatistics>,
+) {
+    autoplay.on_goal_for = None;
+
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let completed = play_statistics
+        .0
+        .get(puzzle_identifier)
+        .map_or(false, |puzzle_statistics| puzzle_statistics.completed);
+
+    if completed {
+        info!(target: "mazonic::autoplay", "Solved {:?}", puzzle_identifier);
+    } else {
+        error!(target: "mazonic::autoplay", "Victory fired but {:?} was not marked completed", puzzle_identifier);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Playthrough {
+    pub solution_length: usize,
+    // The number of moves after which the puzzle entered `PuzzleState::Victory`, if it ever did.
+    pub victory_after_moves: Option<usize>,
+    pub completed_before_victory: bool,
+    pub completed: bool,
+}
+
+// Plays a level through its solution headlessly: only the puzzle states, the victory transition
+// and the statistics systems run, so tests can solve every shape without a window or renderer.
+pub fn play_through(level_data: MazeLevelData, puzzle_identifier: PuzzleIdentifier) -> Result<Playthrough, MazonicError> {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), StatesPlugin))
+        .init_asset::<MidiAudio>()
+        .init_state::<GameState>()
+        .add_sub_state::<PuzzleState>()
+        .insert_resource(PlayStatistics(HashMap::new()))
+        .init_resource::<SnapAssist>()
+        .init_resource::<GameSettings>()
+        .add_systems(Startup, play_statistics::setup)
+        .add_systems(OnEnter(PuzzleState::Playing), play_statistics::on_play)
+        .add_systems(
+            Update,
+            (player_path::update, victory_transition.run_if(in_state(PuzzleState::Playing))).chain(),
+        )
+        .add_systems(OnEnter(PuzzleState::Victory), play_statistics::on_victory);
+
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands, asset_server: Res<AssetServer>| {
+            custom_maze::spawn(&mut commands, &asset_server, level_data.clone())
+        })
+        .expect("spawning the level runs once")?;
+
+    let solution = app
+        .world_mut()
+        .query::<&SolutionComponent>()
+        .single(app.world())
+        .0
+        .clone();
+
+    let start = *solution.first().ok_or(MazonicError::EmptySolution)?;
+    let player = app
+        .world_mut()
+        .spawn((PlayerMazeState::Node(start), PlayerPath::default()))
+        .id();
+    app.world_mut().spawn(CurrentPuzzle(puzzle_identifier.clone()));
+
+    app.world_mut().resource_mut::<NextState<GameState>>().set(GameState::Puzzle);
+    app.update();
+    app.world_mut().resource_mut::<NextState<PuzzleState>>().set(PuzzleState::Playing);
+    app.update();
+
+    let is_completed = |app: &App| {
+        app.world()
+            .resource::<PlayStatistics>()
+            .0
+            .get(&puzzle_identifier)
+            .is_some_and(|puzzle_statistics| puzzle_statistics.completed)
+    };
+
+    let completed_before_victory = is_completed(&app);
+    let mut victory_after_moves = None;
+
+    for moves in 1..solution.len() {
+        let player_maze_state = app.world().get::<PlayerMazeState>(player).cloned();
+        let Some(next_room) = player_maze_state.and_then(|state| next_solution_room(&state, &solution).copied()) else {
+            break;
+        };
+
+        *app.world_mut().get_mut::<PlayerMazeState>(player).unwrap() = PlayerMazeState::Node(next_room);
+        // One update fires the transition; the next runs the systems on entering the new state.
+        app.update();
+        app.update();
+
+        if *app.world().resource::<State<PuzzleState>>().get() == PuzzleState::Victory {
+            victory_after_moves = Some(moves);
+            break;
+        }
+    }
+
+    Ok(Playthrough {
+        solution_length: solution.len(),
+        victory_after_moves,
+        completed_before_victory,
+        completed: is_completed(&app),
+    })
+}
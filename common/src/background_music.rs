@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use bevy::{
+    audio::{PlaybackMode, Volume},
+    prelude::*,
+};
+use bevy_rustysynth::{MidiAudio, MidiNote};
+use serde::{Deserialize, Serialize};
+
+use crate::{game_settings::GameSettings, level_selector::SelectorEntity, sound::{AudioSettings, MelodyPlayback}};
+
+const PAD_VELOCITY: i32 = 30;
+const PAD_NOTE_SECONDS: f32 = 8.0;
+// Melody playback is the point of a discovery, so the loop drops well under it.
+const DUCKED_VOLUME_FACTOR: f32 = 0.25;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum BackgroundLoop {
+    Asset(String),
+    // Held synth notes, each looping on its own so they sound together as a chord.
+    AmbientPad(Vec<i32>),
+}
+
+// Attached to whatever owns the loop: the level entity, or the selector.
+#[derive(Component, Clone, Debug)]
+pub struct BackgroundLoopSource(pub BackgroundLoop);
+
+#[derive(Component)]
+pub struct BackgroundLoopPlayback;
+
+pub fn background_music_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.background_music
+}
+
+pub fn spawn_selector_source(mut commands: Commands, game_settings: Res<GameSettings>) {
+    if let Some(background_loop) = &game_settings.selector_background_loop {
+        commands.spawn((BackgroundLoopSource(background_loop.clone()), SelectorEntity));
+    }
+}
+
+pub fn start(
+    mut commands: Commands,
+    source_query: Query<&BackgroundLoopSource>,
+    playback_query: Query<(), With<BackgroundLoopPlayback>>,
+    game_settings: Res<GameSettings>,
+    audio_settings: Res<AudioSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    if !playback_query.is_empty() {
+        return;
+    }
+
+    let Ok(BackgroundLoopSource(background_loop)) = source_query.get_single() else {
+        return;
+    };
+
+    let settings = PlaybackSettings {
+        mode: PlaybackMode::Loop,
+        volume: Volume::new(game_settings.background_music_volume * audio_settings.volume()),
+        ..default()
+    };
+
+    match background_loop {
+        BackgroundLoop::Asset(path) => {
+            commands.spawn((
+                AudioPlayer::<AudioSource>(asset_server.load(path)),
+                settings,
+                BackgroundLoopPlayback,
+            ));
+        }
+        BackgroundLoop::AmbientPad(keys) => {
+            for key in keys {
+                let midi_note = MidiNote {
+                    key: *key,
+                    velocity: PAD_VELOCITY,
+                    duration: Duration::from_secs_f32(PAD_NOTE_SECONDS),
+                    ..Default::default()
+                };
+                let audio_handle = asset_server.add::<MidiAudio>(MidiAudio::Sequence(vec![midi_note]));
+
+                commands.spawn((AudioPlayer(audio_handle), settings, BackgroundLoopPlayback));
+            }
+        }
+    }
+}
+
+pub fn stop(mut commands: Commands, playback_query: Query<Entity, With<BackgroundLoopPlayback>>) {
+    for entity in playback_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// Also applies volume and mute changes made while the loop is playing.
+pub fn update_volume(
+    game_settings: Res<GameSettings>,
+    audio_settings: Res<AudioSettings>,
+    melody_playback_query: Query<(), With<MelodyPlayback>>,
+    sink_query: Query<&AudioSink, With<BackgroundLoopPlayback>>,
+) {
+    let volume = match (game_settings.background_music, melody_playback_query.is_empty()) {
+        (false, _) => 0.0,
+        (true, true) => game_settings.background_music_volume,
+        (true, false) => game_settings.background_music_volume * DUCKED_VOLUME_FACTOR,
+    } * audio_settings.volume();
+
+    for sink in sink_query.iter() {
+        if sink.volume() != volume {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{asset::AssetPlugin, ecs::system::RunSystemOnce};
+
+    use super::*;
+
+    fn music_app(game_settings: GameSettings) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<AudioSource>()
+            .init_asset::<MidiAudio>()
+            .insert_resource(game_settings)
+            .init_resource::<AudioSettings>();
+        app
+    }
+
+    fn playback_count(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query_filtered::<Entity, With<BackgroundLoopPlayback>>().iter(world).count()
+    }
+
+    #[test]
+    fn entering_and_leaving_a_level_starts_and_stops_its_loop() {
+        let mut app = music_app(GameSettings::default());
+        let level = app
+            .world_mut()
+            .spawn(BackgroundLoopSource(BackgroundLoop::AmbientPad(vec![48, 55, 60])))
+            .id();
+
+        app.world_mut().run_system_once(start).unwrap();
+        assert_eq!(playback_count(&mut app), 3);
+
+        // Starting again, as re-entering Playing does, must not stack a second loop.
+        app.world_mut().run_system_once(start).unwrap();
+        assert_eq!(playback_count(&mut app), 3);
+
+        app.world_mut().despawn(level);
+        app.world_mut().run_system_once(stop).unwrap();
+        assert_eq!(playback_count(&mut app), 0);
+    }
+
+    #[test]
+    fn entering_and_leaving_the_selector_starts_and_stops_its_loop() {
+        let mut app = music_app(GameSettings {
+            selector_background_loop: Some(BackgroundLoop::Asset("music/selector.ogg".to_string())),
+            ..default()
+        });
+
+        app.world_mut().run_system_once(spawn_selector_source).unwrap();
+        app.world_mut().run_system_once(start).unwrap();
+        assert_eq!(playback_count(&mut app), 1);
+
+        app.world_mut().run_system_once(stop).unwrap();
+        assert_eq!(playback_count(&mut app), 0);
+    }
+}
@@ -0,0 +1,103 @@
+// Times loading and solving every level file in a directory, without starting Bevy.
+//
+//     cargo run --release -p mazonic --bin maze_bench -- desktop/assets/levels
+use std::{
+    env, fs,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use mazonic::{
+    goal_placement::farthest_node_solution,
+    is_room_junction::is_junction,
+    levels::Shape,
+    maze::solver::shortest_path,
+    shape::loader::MazeLevelData,
+};
+
+const DEFAULT_LEVELS_DIRECTORY: &str = "desktop/assets/levels";
+// Solving takes microseconds, so it is repeated to get a stable average.
+const SOLVE_ITERATIONS: u32 = 100;
+
+fn shape_name(shape: &Shape) -> &'static str {
+    match shape {
+        Shape::Tetrahedron(_) => "tetrahedron",
+        Shape::Cube(_) => "cube",
+        Shape::Octahedron(_) => "octahedron",
+        Shape::Dodecahedron(_) => "dodecahedron",
+        Shape::Icosahedron(_) => "icosahedron",
+    }
+}
+
+fn average<T>(iterations: u32, mut run: impl FnMut() -> T) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        std::hint::black_box(run());
+    }
+    start.elapsed() / iterations
+}
+
+fn main() {
+    let levels_directory = env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_LEVELS_DIRECTORY));
+
+    let Ok(entries) = fs::read_dir(&levels_directory) else {
+        eprintln!("could not read {}", levels_directory.display());
+        std::process::exit(1);
+    };
+
+    let mut level_paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect::<Vec<PathBuf>>();
+    level_paths.sort();
+
+    println!(
+        "{:<16} {:<13} {:>5} {:>6} {:>6} {:>9} {:>9} {:>11} {:>11} {:>11}",
+        "file", "shape", "nodes", "rooms", "edges", "junctions", "solution", "parse", "solve", "farthest"
+    );
+
+    for path in level_paths {
+        let Ok(json) = fs::read_to_string(&path) else {
+            eprintln!("could not read {}", path.display());
+            continue;
+        };
+
+        let parse_start = Instant::now();
+        let level_data = match serde_json::from_str::<MazeLevelData>(&json) {
+            Ok(level_data) => level_data,
+            Err(err) => {
+                eprintln!("could not parse {}: {err}", path.display());
+                continue;
+            }
+        };
+        let parse_time = parse_start.elapsed();
+
+        let graph = &level_data.graph;
+        let (Some(start), Some(goal)) = (level_data.solution.first(), level_data.solution.last()) else {
+            eprintln!("{} has an empty solution", path.display());
+            continue;
+        };
+
+        let solution_length = shortest_path(graph, *start, *goal).map_or(0, |path| path.len());
+        let solve_time = average(SOLVE_ITERATIONS, || shortest_path(graph, *start, *goal));
+        let farthest_time = average(SOLVE_ITERATIONS, || farthest_node_solution(graph, *start));
+        let junction_count = graph.nodes().filter(|room| is_junction(room, graph)).count();
+
+        println!(
+            "{:<16} {:<13} {:>5} {:>6} {:>6} {:>9} {:>9} {:>11?} {:>11?} {:>11?}",
+            path.file_name().map_or_else(String::new, |name| name.to_string_lossy().into_owned()),
+            shape_name(&level_data.shape),
+            level_data.nodes_per_edge,
+            graph.node_count(),
+            graph.edge_count(),
+            junction_count,
+            solution_length,
+            parse_time,
+            solve_time,
+            farthest_time,
+        );
+    }
+}
@@ -1,13 +1,16 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, time::Duration};
 
 use crate::{
     constants::PHI,
+    controller::ControllerState,
     controller_screen_position::ControllerScreenPosition,
+    game_save::{CurrentPuzzle, PuzzleIdentifier},
     game_settings::GameSettings,
     game_state::GameState,
     game_systems::SystemHandles,
     level_selector::SelectableLevel,
     levels::{GameLevel, PuzzleEntityMarker, Shape},
+    play_statistics::PlayStatistics,
     player::{Player, PlayerMazeState},
 };
 use bevy::{
@@ -21,8 +24,49 @@ use bevy_rapier3d::na::ComplexField;
 use ringbuffer::RingBuffer;
 
 const CAMERA_MOVE_THRESHOLD: f32 = 0.005;
+// Zoom limits away from a level, where there is no solid to fit to.
 pub const CAMERA_MAX_NORM: f32 = 10.0;
 pub const CAMERA_MIN_NORM: f32 = 2.4;
+const CAMERA_NEAR: f32 = 1.0;
+// In a level the limits are measured in circumradii of the solid. The closest zoom keeps the near
+// plane clear of the furthest out vertex, with room for the player above the surface.
+const MIN_NORM_CIRCUMRADII: f32 = 1.2;
+const MAX_NORM_CIRCUMRADII: f32 = 7.0;
+const PLAYER_IN_VIEW_THRESHOLD: f32 = 0.2;
+// Past this distance from the screen centre, in normalized device coordinates, auto zoom eases out.
+const AUTO_ZOOM_EDGE_NDC: f32 = 0.7;
+// Once the player is back within this distance of the centre, auto zoom eases back to the
+// player's own zoom. The gap to AUTO_ZOOM_EDGE_NDC keeps it from see-sawing between the two.
+const AUTO_ZOOM_RETURN_NDC: f32 = 0.45;
+// The largest fraction of the current distance a single auto zoom may add or remove.
+const AUTO_ZOOM_MAX_STEP: f32 = 0.25;
+const REFERENCE_FRAME_RATE: f32 = 60.0;
+const MIN_RELEASE_SAMPLES: usize = 2;
+// A pointer held still for longer than this before release has stopped dragging, so it releases no spin.
+const DOLLY_RELEASE_WINDOW_SECONDS: f32 = 0.1;
+const INTRO_SWEEP_SECONDS: f32 = 2.0;
+const INTRO_SWEEP_ANGLE: f32 = std::f32::consts::PI;
+const VICTORY_ORBIT_SPEED: f32 = 0.15;
+const OVERVIEW_ZOOM_FACTOR: f32 = 1.6;
+const RELEVEL_SPEED: f32 = 0.1;
+// How closely the camera must face the player, as a dot product, before an idle recenter is needed or done.
+const IDLE_RECENTER_CENTERED: f32 = 0.95;
+const IDLE_RECENTER_DONE: f32 = 0.999;
+const IDLE_RECENTER_MAX_SPIN: f32 = 0.001;
+const FOCUS_PLAYER_SPEED: f32 = 0.05;
+// Pixels of drag per frame to radians per frame at a sensitivity of 1.
+const DOLLY_PIXELS_PER_RADIAN: f32 = 90.0;
+// A held rotation key behaves like a steady drag of this many pixels per frame.
+const KEYBOARD_DOLLY_PIXELS: f32 = 2.0;
+pub const MIN_FIELD_OF_VIEW_DEGREES: f32 = 30.0;
+pub const MAX_FIELD_OF_VIEW_DEGREES: f32 = 90.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VictoryFraming {
+    #[default]
+    FollowPlayer,
+    OrbitSolid,
+}
 
 #[derive(Component)]
 pub struct MainCamera;
@@ -33,24 +77,81 @@ pub struct CameraTarget {
     pub translation_norm: f32,
     pub up: Vec3,
     pub looking_at: Vec3,
+    pub min_norm: f32,
+    pub max_norm: f32,
 }
 
 impl CameraTarget {
     pub fn set_zoom(&mut self, zoom: f32) {
-        self.translation_norm = zoom.clamp(CAMERA_MIN_NORM, CAMERA_MAX_NORM);
+        self.translation_norm = self.clamp_zoom(zoom);
+    }
+
+    pub fn clamp_zoom(&self, zoom: f32) -> f32 {
+        zoom.clamp(self.min_norm, self.max_norm)
+    }
+
+    // Fits the zoom limits to a solid, so larger shapes can never be zoomed into.
+    pub fn set_zoom_bounds(&mut self, circumradius: f32) {
+        self.min_norm = CAMERA_NEAR + circumradius * MIN_NORM_CIRCUMRADII;
+        self.max_norm = (circumradius * MAX_NORM_CIRCUMRADII).max(self.min_norm);
+    }
+
+    pub fn reset_zoom_bounds(&mut self) {
+        self.min_norm = CAMERA_MIN_NORM;
+        self.max_norm = CAMERA_MAX_NORM;
     }
 }
 
+// The point the camera is currently turned towards, easing after `CameraTarget::looking_at`.
+#[derive(Component, Debug, Clone)]
+pub struct CameraFocus(pub Vec3);
+
+#[derive(Component, Debug, Clone)]
+pub struct IntroSweep {
+    elapsed: f32,
+    axis: Vec3,
+    final_direction: Vec3,
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct Overview {
+    remaining: f32,
+    play_zoom: f32,
+}
+
+#[derive(Component, Debug, Clone, Default)]
+pub struct IdleRecenter {
+    idle_for: f32,
+    recentering: bool,
+}
+
 #[derive(Component, Debug, Clone)]
 pub struct DollyAngularMotion {
     axis: Vec3,
     angular_velocity: f32,
 }
 
-const NUM_STORED_POSITIONS: usize = 5;
-
+// The motion of the latest drag and when it was sampled, carried on as spin when the drag is released.
+// None until the drag has enough samples to have a velocity.
 #[derive(Component, Debug, Clone, Default)]
-pub struct DollyScreenPositions(ringbuffer::ConstGenericRingBuffer<Vec2, NUM_STORED_POSITIONS>);
+pub struct DollyReleaseMotion {
+    motion: Option<DollyAngularMotion>,
+    sampled_at: Duration,
+}
+
+// A delta needs two samples; more smooth out flicks at the cost of latency.
+const MIN_DOLLY_SAMPLES: usize = 2;
+const MAX_DOLLY_SAMPLES: usize = 32;
+
+#[derive(Component, Debug, Clone)]
+pub struct DollyScreenPositions(ringbuffer::AllocRingBuffer<Vec2>);
+
+impl DollyScreenPositions {
+    fn new(game_settings: &GameSettings) -> Self {
+        let capacity = game_settings.dolly_samples.clamp(MIN_DOLLY_SAMPLES, MAX_DOLLY_SAMPLES);
+        Self(ringbuffer::AllocRingBuffer::new(capacity))
+    }
+}
 
 pub fn setup(mut commands: Commands, game_settings: Res<GameSettings>) {
     let translation_dir = Vec3::Z;
@@ -58,7 +159,7 @@ pub fn setup(mut commands: Commands, game_settings: Res<GameSettings>) {
     let looking_at = Vec3::ZERO;
     let up = Vec3::Y;
 
-    let transform = Transform::from_translation(translation_dir * translation_norm)
+    let transform = Transform::from_translation(looking_at + translation_dir * translation_norm)
         .looking_at(looking_at.clone(), up.clone());
 
     commands
@@ -71,9 +172,12 @@ pub fn setup(mut commands: Commands, game_settings: Res<GameSettings>) {
             axis: Vec3::X,
             angular_velocity: 0.0,
         })
-        .insert(DollyScreenPositions::default())
+        .insert(DollyScreenPositions::new(&game_settings))
+        .insert(IdleRecenter::default())
+        .insert(DollyReleaseMotion::default())
         .insert(Projection::Perspective(PerspectiveProjection {
-            near: 1.0,
+            fov: field_of_view(&game_settings),
+            near: CAMERA_NEAR,
             far: 2.5,
             ..default()
         }))
@@ -84,11 +188,76 @@ pub fn setup(mut commands: Commands, game_settings: Res<GameSettings>) {
             translation_norm,
             up,
             looking_at,
+            min_norm: CAMERA_MIN_NORM,
+            max_norm: CAMERA_MAX_NORM,
         })
+        .insert(CameraFocus(looking_at))
         .insert(IsDefaultUiCamera)
         .insert(MainCamera);
 }
 
+fn field_of_view(game_settings: &GameSettings) -> f32 {
+    game_settings
+        .field_of_view_degrees
+        .clamp(MIN_FIELD_OF_VIEW_DEGREES, MAX_FIELD_OF_VIEW_DEGREES)
+        .to_radians()
+}
+
+// The refit waits a frame because update_distance projects through the camera matrices, which
+// only pick up the new field of view once the projection has been recomputed.
+pub fn apply_field_of_view(
+    mut commands: Commands,
+    game_settings: Res<GameSettings>,
+    systems: Res<SystemHandles>,
+    mut camera_query: Query<&mut Projection, With<MainCamera>>,
+    mut refit_pending: Local<bool>,
+) {
+    if *refit_pending {
+        *refit_pending = false;
+        commands.run_system(systems.resize_camera_distance);
+    }
+
+    if !game_settings.is_changed() {
+        return;
+    }
+
+    let Ok(mut projection) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let Projection::Perspective(perspective_projection) = projection.as_mut() else {
+        return;
+    };
+
+    let fov = field_of_view(&game_settings);
+    if (perspective_projection.fov - fov).abs() < f32::EPSILON {
+        return;
+    }
+
+    debug!(target: "mazonic::camera", "Setting field of view to {:?} degrees", fov.to_degrees());
+    perspective_projection.fov = fov;
+    *refit_pending = true;
+}
+
+pub fn update_clear_color(
+    game_settings: Res<GameSettings>,
+    mut camera_query: Query<&mut Camera, With<MainCamera>>,
+) {
+    if !game_settings.is_changed() {
+        return;
+    }
+
+    let Ok(mut camera) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    camera.clear_color = ClearColorConfig::Custom(game_settings.palette.background_color);
+}
+
+pub fn camera_follow_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.camera_follow
+}
+
 pub fn follow_player(
     mut camera_target_query: Query<&mut CameraTarget, With<MainCamera>>,
     player_query: Query<&PlayerMazeState, (With<Player>, Without<MainCamera>)>,
@@ -99,7 +268,358 @@ pub fn follow_player(
 
     let mut camera_target = camera_target_query.single_mut();
 
-    let target_unit_translation = match player_maze_state {
+    camera_target.translation_dir = get_player_view_direction(player_maze_state);
+}
+
+pub fn idle_recenter_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.idle_recenter
+}
+
+pub fn idle_recentering(idle_recenter_query: Query<&IdleRecenter, With<MainCamera>>) -> bool {
+    idle_recenter_query
+        .get_single()
+        .is_ok_and(|idle_recenter| idle_recenter.recentering)
+}
+
+// Only counts time spent idle between moves; the recenter itself is eased by camera_rotate_to_target
+// and any press or key stops it where it is.
+pub fn update_idle_recenter(
+    mut camera_query: Query<
+        (&mut CameraTarget, &mut IdleRecenter, &mut DollyAngularMotion, &Transform),
+        With<MainCamera>,
+    >,
+    player_query: Query<&PlayerMazeState, (With<Player>, Without<MainCamera>)>,
+    controller_screen_position_query: Query<&ControllerScreenPosition>,
+    controller_state: Res<State<ControllerState>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    game_settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let Ok((mut camera_target, mut idle_recenter, mut dolly_angular_motion, camera_transform)) =
+        camera_query.get_single_mut()
+    else {
+        return;
+    };
+
+    let Ok(player_maze_state) = player_query.get_single() else {
+        return;
+    };
+
+    let camera_direction = camera_transform.translation.normalize();
+
+    let idle = matches!(
+        controller_state.get(),
+        ControllerState::IdlePostSolve | ControllerState::IdlePostView
+    ) && keys.get_pressed().next().is_none()
+        && !matches!(
+            controller_screen_position_query.get_single(),
+            Ok(ControllerScreenPosition::Position(_))
+        );
+
+    if !idle {
+        if idle_recenter.recentering {
+            camera_target.translation_dir = camera_direction;
+        }
+        *idle_recenter = IdleRecenter::default();
+        return;
+    }
+
+    if idle_recenter.recentering {
+        if camera_direction.dot(camera_target.translation_dir) > IDLE_RECENTER_DONE {
+            idle_recenter.recentering = false;
+        }
+        return;
+    }
+
+    // A flick is still spinning the camera, so the player has not settled on a view yet.
+    if dolly_angular_motion.angular_velocity.abs() > IDLE_RECENTER_MAX_SPIN {
+        idle_recenter.idle_for = 0.0;
+        return;
+    }
+
+    idle_recenter.idle_for += time.delta_secs();
+    if idle_recenter.idle_for < game_settings.idle_recenter_seconds {
+        return;
+    }
+
+    let player_view_direction = get_player_view_direction(player_maze_state);
+    if camera_direction.dot(player_view_direction) >= IDLE_RECENTER_CENTERED {
+        return;
+    }
+
+    dolly_angular_motion.angular_velocity = 0.0;
+    camera_target.translation_dir = player_view_direction;
+    idle_recenter.recentering = true;
+}
+
+pub fn victory_follows_player(game_settings: Res<GameSettings>) -> bool {
+    game_settings.victory_framing == VictoryFraming::FollowPlayer
+}
+
+pub fn victory_orbits_solid(game_settings: Res<GameSettings>) -> bool {
+    game_settings.victory_framing == VictoryFraming::OrbitSolid
+}
+
+// Zooming to fit the solid is left to update_distance and camera_zoom_to_target; this only turns the camera.
+pub fn orbit_solid(
+    mut camera_query: Query<(&mut CameraTarget, &mut Transform, &CameraFocus), With<MainCamera>>,
+    game_settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if game_settings.reduced_motion {
+        return;
+    }
+
+    let Ok((mut camera_target, mut camera_transform, CameraFocus(focus))) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let up = *camera_transform.up();
+    let rotation = Quat::from_axis_angle(up, VICTORY_ORBIT_SPEED * time.delta_secs());
+
+    camera_transform.translation = *focus + rotation * (camera_transform.translation - *focus);
+    camera_transform.look_at(*focus, up);
+
+    camera_target.translation_dir = (camera_transform.translation - *focus).normalize();
+    camera_target.up = up;
+}
+
+pub fn follow_player_if_out_of_view(
+    mut camera_target_query: Query<(&mut CameraTarget, &Transform), With<MainCamera>>,
+    player_query: Query<&PlayerMazeState, (With<Player>, Without<MainCamera>)>,
+) {
+    let Ok(player_maze_state) = player_query.get_single() else {
+        return;
+    };
+
+    let (mut camera_target, camera_transform) = camera_target_query.single_mut();
+
+    let player_view_direction = get_player_view_direction(player_maze_state);
+    let camera_direction = camera_transform.translation.normalize();
+
+    if camera_direction.dot(player_view_direction) < PLAYER_IN_VIEW_THRESHOLD {
+        camera_target.translation_dir = player_view_direction;
+    }
+}
+
+// Camera follow turns the camera and auto zoom only changes its distance, so each runs on its own.
+pub fn auto_zoom_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.auto_zoom
+}
+
+// Returns the next camera distance for a player at `player_ndc`. It eases out while the player is
+// near the edge of the screen and back towards `preferred_norm` once they are well inside it,
+// never moving by more than AUTO_ZOOM_MAX_STEP of the current distance.
+pub fn auto_zoom_distance(player_ndc: Vec2, camera_target: &CameraTarget, preferred_norm: f32) -> f32 {
+    let translation_norm = camera_target.translation_norm;
+    let edge_distance = player_ndc.abs().max_element();
+
+    let target_norm = if edge_distance > AUTO_ZOOM_EDGE_NDC {
+        let excess = ((edge_distance - AUTO_ZOOM_EDGE_NDC) / (1.0 - AUTO_ZOOM_EDGE_NDC)).min(1.0);
+        translation_norm * (1.0 + excess * AUTO_ZOOM_MAX_STEP)
+    } else if edge_distance < AUTO_ZOOM_RETURN_NDC {
+        preferred_norm
+    } else {
+        return translation_norm;
+    };
+
+    let max_step = translation_norm * AUTO_ZOOM_MAX_STEP;
+    camera_target.clamp_zoom(translation_norm + (target_norm - translation_norm).clamp(-max_step, max_step))
+}
+
+#[derive(Default)]
+pub struct AutoZoom {
+    // The distance chosen by the player or the level, which auto zoom returns to.
+    preferred_norm: f32,
+    // The distance auto zoom last set, so any other change is taken as a new preference.
+    applied_norm: Option<f32>,
+}
+
+pub fn auto_zoom_to_player(
+    mut camera_query: Query<(&Camera, &GlobalTransform, &mut CameraTarget), With<MainCamera>>,
+    player_query: Query<&GlobalTransform, (With<Player>, Without<MainCamera>)>,
+    mut auto_zoom: Local<AutoZoom>,
+) {
+    let Ok((camera, camera_global_transform, mut camera_target)) = camera_query.get_single_mut()
+    else {
+        return;
+    };
+
+    let Ok(player_global_transform) = player_query.get_single() else {
+        return;
+    };
+
+    let Some(player_ndc) =
+        camera.world_to_ndc(camera_global_transform, player_global_transform.translation())
+    else {
+        return;
+    };
+
+    if auto_zoom.applied_norm != Some(camera_target.translation_norm) {
+        auto_zoom.preferred_norm = camera_target.translation_norm;
+    }
+
+    let new_norm = auto_zoom_distance(player_ndc.truncate(), &camera_target, auto_zoom.preferred_norm);
+    if new_norm != camera_target.translation_norm {
+        debug!(target: "mazonic::camera", "Auto zooming to {:?}", new_norm);
+        camera_target.translation_norm = new_norm;
+    }
+    auto_zoom.applied_norm = Some(new_norm);
+}
+
+pub fn hold_current_direction(
+    mut camera_query: Query<(&mut CameraTarget, &Transform), With<MainCamera>>,
+) {
+    let Ok((mut camera_target, camera_transform)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    camera_target.translation_dir = camera_transform.translation.normalize();
+    camera_target.up = *camera_transform.up();
+}
+
+pub fn start_intro_sweep(
+    mut commands: Commands,
+    camera_query: Query<(Entity, &CameraTarget), With<MainCamera>>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    play_statistics: Res<PlayStatistics>,
+    game_settings: Res<GameSettings>,
+) {
+    let Ok((camera_entity, camera_target)) = camera_query.get_single() else {
+        return;
+    };
+
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let completed = play_statistics
+        .0
+        .get(puzzle_identifier)
+        .map_or(false, |puzzle_statistics| puzzle_statistics.completed);
+
+    // Dailies are timed, so only unfinished campaign levels get the sweep.
+    let sweep = matches!(puzzle_identifier, PuzzleIdentifier::Level(_))
+        && game_settings.campaign_intro_sweep
+        && !game_settings.reduced_motion
+        && !completed;
+
+    if !sweep {
+        commands.entity(camera_entity).remove::<IntroSweep>();
+        return;
+    }
+
+    let final_direction = camera_target.translation_dir.normalize();
+    let axis = camera_target
+        .up
+        .reject_from(final_direction)
+        .try_normalize()
+        .unwrap_or_else(|| final_direction.any_orthogonal_vector());
+
+    commands.entity(camera_entity).insert(IntroSweep {
+        elapsed: 0.0,
+        axis,
+        final_direction,
+    });
+}
+
+pub fn start_overview(
+    mut commands: Commands,
+    mut camera_query: Query<(Entity, &mut CameraTarget), With<MainCamera>>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    game_settings: Res<GameSettings>,
+) {
+    let Ok((camera_entity, mut camera_target)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let overview = matches!(puzzle_identifier, PuzzleIdentifier::Level(_))
+        && game_settings.campaign_overview_seconds > 0.0
+        && !game_settings.reduced_motion;
+
+    if !overview {
+        commands.entity(camera_entity).remove::<Overview>();
+        return;
+    }
+
+    let play_zoom = camera_target.translation_norm;
+    camera_target.set_zoom(play_zoom * OVERVIEW_ZOOM_FACTOR);
+
+    commands.entity(camera_entity).insert(Overview {
+        remaining: game_settings.campaign_overview_seconds,
+        play_zoom,
+    });
+}
+
+// Input is never blocked during the overview, so the first touch both ends it and starts solving.
+pub fn update_overview(
+    mut commands: Commands,
+    mut camera_query: Query<(Entity, &mut CameraTarget, &mut Overview), With<MainCamera>>,
+    controller_screen_position_query: Query<&ControllerScreenPosition>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let Ok((camera_entity, mut camera_target, mut overview)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let interrupted = keys.get_just_pressed().next().is_some()
+        || matches!(
+            controller_screen_position_query.get_single(),
+            Ok(ControllerScreenPosition::Position(_))
+        );
+
+    overview.remaining -= time.delta_secs();
+
+    if interrupted || overview.remaining <= 0.0 {
+        camera_target.set_zoom(overview.play_zoom);
+        commands.entity(camera_entity).remove::<Overview>();
+    }
+}
+
+pub fn update_intro_sweep(
+    mut commands: Commands,
+    mut camera_query: Query<
+        (Entity, &mut CameraTarget, &mut IntroSweep),
+        (With<MainCamera>, Without<Overview>),
+    >,
+    controller_screen_position_query: Query<&ControllerScreenPosition>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let Ok((camera_entity, mut camera_target, mut intro_sweep)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let skipped = keys.get_just_pressed().next().is_some()
+        || matches!(
+            controller_screen_position_query.get_single(),
+            Ok(ControllerScreenPosition::Position(_))
+        );
+
+    intro_sweep.elapsed += time.delta_secs();
+    let progress = (intro_sweep.elapsed / INTRO_SWEEP_SECONDS).clamp(0.0, 1.0);
+
+    if skipped || progress >= 1.0 {
+        camera_target.translation_dir = intro_sweep.final_direction;
+        commands.entity(camera_entity).remove::<IntroSweep>();
+        return;
+    }
+
+    let eased_progress = progress * progress * (3.0 - 2.0 * progress);
+    let remaining_angle = INTRO_SWEEP_ANGLE * (1.0 - eased_progress);
+
+    // Only the direction is driven here, camera_zoom_to_target keeps handling the load zoom.
+    camera_target.translation_dir =
+        Quat::from_axis_angle(intro_sweep.axis, remaining_angle) * intro_sweep.final_direction;
+}
+
+fn get_player_view_direction(player_maze_state: &PlayerMazeState) -> Vec3 {
+    match player_maze_state {
         PlayerMazeState::Node(node) => node.face().normal(),
         PlayerMazeState::Edge(from_node, to_node, _) => {
             let from_face_normal = from_node.face().normal();
@@ -107,110 +627,240 @@ pub fn follow_player(
 
             from_face_normal.midpoint(to_face_normal).normalize()
         }
+    }
+}
+
+// Drifts the framing towards the player on large solids, and back to the solid's centre once the
+// setting is turned off.
+pub fn update_looking_at(
+    mut camera_target_query: Query<&mut CameraTarget, With<MainCamera>>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    game_settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let Ok(mut camera_target) = camera_target_query.get_single_mut() else {
+        return;
     };
-    
-    camera_target.translation_dir = target_unit_translation;
+
+    let target_looking_at = match player_query.get_single() {
+        Ok(player_transform) if game_settings.focus_on_player => player_transform.translation(),
+        _ => Vec3::ZERO,
+    };
+
+    if camera_target.looking_at.distance(target_looking_at) < CAMERA_MOVE_THRESHOLD {
+        return;
+    }
+
+    let factor = get_frame_rate_independent_factor(FOCUS_PLAYER_SPEED, time.delta_secs());
+    camera_target.looking_at = camera_target.looking_at.lerp(target_looking_at, factor);
 }
 
+pub fn reset_looking_at(mut camera_target_query: Query<&mut CameraTarget, With<MainCamera>>) {
+    let Ok(mut camera_target) = camera_target_query.get_single_mut() else {
+        return;
+    };
+
+    camera_target.looking_at = Vec3::ZERO;
+}
+
+// The camera orbits its focus, which eases towards `looking_at` at the same rate the camera turns.
 pub fn camera_rotate_to_target(
     target_query: Query<&CameraTarget>,
-    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+    mut camera_query: Query<(&mut Transform, &mut CameraFocus), With<MainCamera>>,
     game_settings: Res<GameSettings>,
+    time: Res<Time>,
 ) {
     let Ok(CameraTarget {
         translation_dir,
         translation_norm,
         up,
         looking_at,
+        ..
     }) = target_query.get_single()
     else {
         return;
     };
 
-    let mut camera_transform = camera_query.single_mut();
+    let (mut camera_transform, mut camera_focus) = camera_query.single_mut();
     if camera_transform
         .translation
-        .distance(translation_dir * translation_norm)
+        .distance(*looking_at + *translation_dir * *translation_norm)
         < CAMERA_MOVE_THRESHOLD
+        && camera_focus.0.distance(*looking_at) < CAMERA_MOVE_THRESHOLD
     {
         return;
     }
 
-    let camera_follow_speed = game_settings.camera_follow_speed;
+    let camera_follow_speed =
+        get_frame_rate_independent_factor(game_settings.camera_follow_speed, time.delta_secs());
 
-    let normalized_new_translation = camera_transform
-        .translation
+    let offset = camera_transform.translation - camera_focus.0;
+
+    let normalized_new_offset = offset
         .lerp(*translation_dir, camera_follow_speed)
         .normalize();
 
-    let new_translation = normalized_new_translation * camera_transform.translation.norm();
+    let new_focus = camera_focus.0.lerp(*looking_at, camera_follow_speed);
+    let new_translation = new_focus + normalized_new_offset * offset.norm();
 
-    let new_up = camera_transform.up().lerp(*up, camera_follow_speed);
+    let target_up = if game_settings.keep_upright { Vec3::Y } else { *up };
+    let new_up = camera_transform.up().lerp(target_up, camera_follow_speed);
 
+    camera_focus.0 = new_focus;
     camera_transform.translation = new_translation;
-    camera_transform.look_at(Vec3::ZERO, new_up);
+    camera_transform.look_at(new_focus, new_up);
 }
 
 pub fn camera_zoom_to_target(
     target_query: Query<&CameraTarget>,
-    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+    mut camera_query: Query<(&mut Transform, &CameraFocus), With<MainCamera>>,
     game_settings: Res<GameSettings>,
+    time: Res<Time>,
 ) {
     let Ok(CameraTarget {
         translation_dir,
         translation_norm,
         up,
         looking_at,
+        ..
     }) = target_query.get_single()
     else {
         return;
     };
 
-    let mut camera_transform = camera_query.single_mut();
-    let current_camera_norm = camera_transform.translation.norm();
+    // Zoom is the distance to the focus, so an off-centre framing keeps its focus while zooming.
+    let (mut camera_transform, CameraFocus(focus)) = camera_query.single_mut();
+    let offset = camera_transform.translation - *focus;
+    let current_camera_norm = offset.norm();
     if (current_camera_norm - translation_norm).abs() < CAMERA_MOVE_THRESHOLD {
         return;
     }
 
     let new_translation_norm = FloatExt::lerp(
-        camera_transform.translation.norm(),
+        current_camera_norm,
         *translation_norm,
-        game_settings.camera_zoom_speed,
+        get_frame_rate_independent_factor(game_settings.camera_zoom_speed, time.delta_secs()),
     );
 
-    camera_transform.translation *= new_translation_norm / current_camera_norm;
+    camera_transform.translation = *focus + offset * (new_translation_norm / current_camera_norm);
 }
 
 pub fn update_dolly(
-    mut camera_query: Query<(&mut Transform, &mut DollyAngularMotion), With<MainCamera>>,
+    mut camera_query: Query<(&mut Transform, &mut DollyAngularMotion, &CameraFocus), With<MainCamera>>,
+    game_settings: Res<GameSettings>,
+    time: Res<Time>,
 ) {
-    let (mut transform, mut dolly_rotation_target) = camera_query.single_mut();
+    let (mut transform, mut dolly_rotation_target, CameraFocus(focus)) = camera_query.single_mut();
     
     if dolly_rotation_target.angular_velocity.abs() < 0.001 {
         return;
     }
 
-    let rotation = Quat::from_axis_angle(dolly_rotation_target.axis, -dolly_rotation_target.angular_velocity);
+    let reference_frames = time.delta_secs() * REFERENCE_FRAME_RATE;
+    let rotation = Quat::from_axis_angle(
+        dolly_rotation_target.axis,
+        -dolly_rotation_target.angular_velocity * reference_frames,
+    );
 
-    dolly_rotation_target.angular_velocity *= 0.95;
+    let decay_per_reference_frame = 1.0 - game_settings.dolly_friction.clamp(0.0, 1.0);
+    dolly_rotation_target.angular_velocity *= decay_per_reference_frame.powf(reference_frames);
 
-    let distance = transform.translation.norm();
+    let distance = transform.translation.distance(*focus);
 
-    transform.rotate_around(Vec3::new(0.0, 0.0, 0.0), -rotation);
+    transform.rotate_around(*focus, -rotation);
 
     let up_vector = transform.up();
-    transform.look_at(Vec3::new(0., 0., 0.), up_vector);
-    transform.translation = transform.translation.normalize() * distance;
+    transform.look_at(*focus, up_vector);
+    transform.translation = *focus + (transform.translation - *focus).normalize() * distance;
+}
+
+// Holding a key keeps the dolly spinning; on release update_dolly's friction brings it to rest
+// just as it does after a flick.
+pub fn keyboard_dolly(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut camera_query: Query<(&Transform, &mut DollyAngularMotion), With<MainCamera>>,
+    controller_state: Option<Res<State<ControllerState>>>,
+    mut next_controller_state: ResMut<NextState<ControllerState>>,
+    game_settings: Res<GameSettings>,
+) {
+    // Modified keys belong to shortcuts such as Ctrl+Shift+D, so they never orbit the camera.
+    if keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+        return;
+    }
+
+    let direction = Vec2::new(
+        axis_input(&keys, [KeyCode::ArrowLeft, KeyCode::KeyA], [KeyCode::ArrowRight, KeyCode::KeyD]),
+        axis_input(&keys, [KeyCode::ArrowUp, KeyCode::KeyW], [KeyCode::ArrowDown, KeyCode::KeyS]),
+    );
+
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    let Ok((camera_transform, mut dolly_rotation_target)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    // Leaves follow-the-player framing the same way a mouse drag does, so the two don't fight.
+    if controller_state.is_some_and(|state| *state.get() == ControllerState::IdlePostSolve) {
+        next_controller_state.set(ControllerState::IdlePostView);
+    }
+
+    let delta_device_pixels = direction.normalize() * KEYBOARD_DOLLY_PIXELS;
+    set_dolly_motion(camera_transform, &mut dolly_rotation_target, delta_device_pixels, &game_settings);
+}
+
+fn axis_input(keys: &ButtonInput<KeyCode>, negative: [KeyCode; 2], positive: [KeyCode; 2]) -> f32 {
+    let negative = if keys.any_pressed(negative) { -1.0 } else { 0.0 };
+    let positive = if keys.any_pressed(positive) { 1.0 } else { 0.0 };
+    negative + positive
+}
+
+pub fn keep_upright_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.keep_upright
+}
+
+// Orbiting still yaws and pitches freely; only the roll is eased back so world up stays up on screen.
+pub fn relevel_camera(
+    mut camera_query: Query<(&mut Transform, &CameraFocus), With<MainCamera>>,
+    time: Res<Time>,
+) {
+    let Ok((mut camera_transform, CameraFocus(focus))) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let current_up = *camera_transform.up();
+    let level_up = Vec3::Y.reject_from(*camera_transform.forward());
+
+    let Some(level_up) = level_up.try_normalize() else {
+        return;
+    };
+
+    if current_up.distance(level_up) < CAMERA_MOVE_THRESHOLD {
+        return;
+    }
+
+    let factor = get_frame_rate_independent_factor(RELEVEL_SPEED, time.delta_secs());
+    let new_up = current_up.lerp(level_up, factor);
+    camera_transform.look_at(*focus, new_up);
 }
 
+// Lerp factors are tuned per frame at the reference frame rate; this keeps smoothing identical at other frame rates.
+fn get_frame_rate_independent_factor(factor_per_reference_frame: f32, delta_secs: f32) -> f32 {
+    1.0 - (1.0 - factor_per_reference_frame).powf(delta_secs * REFERENCE_FRAME_RATE)
+}
+
+// Also picks up a changed sample count, since the buffer is empty at this point anyway.
 pub fn reset_dolly_screen_positions(
-    mut dolly_screen_positions_query: Query<&mut DollyScreenPositions>,
+    mut dolly_screen_positions_query: Query<(&mut DollyScreenPositions, &mut DollyReleaseMotion)>,
+    game_settings: Res<GameSettings>,
 ) {
-    let Ok(mut dolly_screen_positions) = dolly_screen_positions_query.get_single_mut() else {
+    let Ok((mut dolly_screen_positions, mut dolly_release_motion)) = dolly_screen_positions_query.get_single_mut() else {
         return;
     };
 
-    dolly_screen_positions.0.clear();
+    *dolly_screen_positions = DollyScreenPositions::new(&game_settings);
+    dolly_release_motion.motion = None;
 }
 
 pub fn camera_dolly(
@@ -223,10 +873,12 @@ pub fn camera_dolly(
             &Transform,
             &mut DollyAngularMotion,
             &mut DollyScreenPositions,
+            &mut DollyReleaseMotion,
         ),
         With<MainCamera>,
     >,
     game_settings: Res<GameSettings>,
+    time: Res<Time>,
 ) {
     let Ok(ControllerScreenPosition::Position(cursor_position)) =
         controller_screen_position_query.get_single()
@@ -234,17 +886,50 @@ pub fn camera_dolly(
         return;
     };
 
-    let (camera_transform, mut dolly_rotation_target, mut dolly_screen_positions) = camera_query.single_mut();
+    let (camera_transform, mut dolly_rotation_target, mut dolly_screen_positions, mut dolly_release_motion) =
+        camera_query.single_mut();
     dolly_screen_positions.0.push(*cursor_position);
 
     let average_delta_device_pixels = get_average_delta(&dolly_screen_positions.0);
 
-    dolly_rotation_target.angular_velocity = if average_delta_device_pixels.norm() < 1.0 {
-        0.0
-    } else {
-        average_delta_device_pixels.norm() / 90.0
+    set_dolly_motion(camera_transform, &mut dolly_rotation_target, average_delta_device_pixels, &game_settings);
+
+    dolly_release_motion.motion = (dolly_screen_positions.0.len() >= MIN_RELEASE_SAMPLES).then(|| dolly_rotation_target.clone());
+    dolly_release_motion.sampled_at = time.elapsed();
+}
+
+// update_dolly has been decaying the motion since the last drag sample, so the drag's own velocity
+// is restored for friction to take over from. A pointer that was held still before release has no
+// drag velocity left to restore, so the decayed motion is kept.
+pub fn release_dolly(
+    mut camera_query: Query<(&mut DollyAngularMotion, &mut DollyReleaseMotion), With<MainCamera>>,
+    time: Res<Time>,
+) {
+    let Ok((mut dolly_rotation_target, mut dolly_release_motion)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    let Some(release_motion) = dolly_release_motion.motion.take() else {
+        return;
     };
 
+    let held_still_for = time.elapsed().saturating_sub(dolly_release_motion.sampled_at);
+    if held_still_for.as_secs_f32() > DOLLY_RELEASE_WINDOW_SECONDS {
+        return;
+    }
+
+    *dolly_rotation_target = release_motion;
+}
+
+// Settings are read on every call, so sensitivity and inversion changes apply to the next drag.
+fn set_dolly_motion(
+    camera_transform: &Transform,
+    dolly_rotation_target: &mut DollyAngularMotion,
+    average_delta_device_pixels: Vec2,
+    game_settings: &GameSettings,
+) {
+    dolly_rotation_target.angular_velocity = dolly_angular_velocity(average_delta_device_pixels, game_settings.dolly_sensitivity);
+
     if dolly_rotation_target.angular_velocity < 0.001 {
         return;
     }
@@ -255,14 +940,24 @@ pub fn camera_dolly(
         .cross(camera_transform.forward().as_vec3())
         .normalize();
 
-    dolly_rotation_target.axis = axis;
+    dolly_rotation_target.axis = if game_settings.invert_dolly { -axis } else { axis };
 }
 
-fn get_average_delta(
-    last_positions: &ringbuffer::ConstGenericRingBuffer<Vec2, NUM_STORED_POSITIONS>,
-) -> Vec2 {
-    let size = (last_positions.len() - 1).max(1) as f32;
-    (last_positions.back().unwrap() - last_positions.front().unwrap()) / size
+fn dolly_angular_velocity(average_delta_device_pixels: Vec2, sensitivity: f32) -> f32 {
+    if average_delta_device_pixels.norm() < 1.0 {
+        0.0
+    } else {
+        average_delta_device_pixels.norm() / DOLLY_PIXELS_PER_RADIAN * sensitivity.max(0.0)
+    }
+}
+
+fn get_average_delta(last_positions: &ringbuffer::AllocRingBuffer<Vec2>) -> Vec2 {
+    let (Some(back), Some(front)) = (last_positions.back(), last_positions.front()) else {
+        return Vec2::ZERO;
+    };
+
+    let size = last_positions.len().saturating_sub(1).max(1) as f32;
+    (back - front) / size
 }
 
 pub fn trigger_camera_resize_on_window_change(
@@ -272,7 +967,7 @@ pub fn trigger_camera_resize_on_window_change(
     mut previous_window_size: Local<Option<(f32, f32)>>,
 ) {
     for e in resize_reader.read() {
-        println!("Resizing camera on window size change");
+        debug!(target: "mazonic::camera", "Resizing camera on window size change");
         let trigger_resize = match *previous_window_size {
             Some((previous_width, previous_height)) => {
                 let abs_width_delta = (e.width - previous_width).abs();
@@ -308,17 +1003,11 @@ pub fn update_distance(
         return;
     };
 
-    let circumradius_factor = match &level.shape {
-        Shape::Tetrahedron(_) => 1.5_f32.sqrt(),
-        Shape::Cube(_) => 3.0_f32.sqrt(),
-        Shape::Octahedron(_) => 2.0_f32.sqrt(),
-        Shape::Dodecahedron(_) => 3.0_f32.sqrt() * PHI,
-        Shape::Icosahedron(_) => PHI * (3.0 - PHI).sqrt(),
-    };
-
-    let circumradius = circumradius_factor / 2.0;
+    let circumradius = level.shape.circumradius();
     let target_view_radius = circumradius * 1.3;
 
+    camera_target.set_zoom_bounds(circumradius);
+
     let target_camera_y_axis_point = transform.up().normalize() * target_view_radius;
     let target_camera_x_axis_point = transform.right().normalize() * target_view_radius;
 
@@ -336,8 +1025,398 @@ pub fn update_distance(
     let new_zoom = camera_target.translation_norm * max_abs_ndc;
 
     camera_target.set_zoom(new_zoom);
-    println!(
+    debug!(
+        target: "mazonic::camera",
         "Adjusting camera norm to: {:?}, max absolute normalized device coordinate: {:?}",
         camera_target.translation_norm, max_abs_ndc
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::room::{test_support::room_on_face, Face};
+
+    use super::*;
+
+    fn recenter_world(player_face_normal: Vec3, controller_state: ControllerState) -> World {
+        let mut world = World::new();
+        world.insert_resource(GameSettings {
+            idle_recenter: true,
+            idle_recenter_seconds: 1.0,
+            ..Default::default()
+        });
+        world.insert_resource(State::new(controller_state));
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Time>();
+
+        world.spawn((
+            MainCamera,
+            Transform::from_translation(Vec3::Z * 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+            CameraTarget {
+                translation_dir: Vec3::Z,
+                translation_norm: 3.0,
+                up: Vec3::Y,
+                looking_at: Vec3::ZERO,
+                min_norm: CAMERA_MIN_NORM,
+                max_norm: CAMERA_MAX_NORM,
+            },
+            IdleRecenter::default(),
+            DollyAngularMotion {
+                axis: Vec3::X,
+                angular_velocity: 0.0,
+            },
+        ));
+
+        let room = room_on_face(0, player_face_normal * 0.5, Face { id: 0, normal: player_face_normal });
+        world.spawn((Player { radius: 0.1 }, PlayerMazeState::Node(room)));
+        world.spawn(ControllerScreenPosition::None);
+
+        world
+    }
+
+    fn idle_for(world: &mut World, seconds: f32) {
+        world.resource_mut::<Time>().advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_once(update_idle_recenter).unwrap();
+    }
+
+    fn camera_target_direction(world: &mut World) -> Vec3 {
+        world
+            .query_filtered::<&CameraTarget, With<MainCamera>>()
+            .single(world)
+            .translation_dir
+    }
+
+    fn is_recentering(world: &mut World) -> bool {
+        world.run_system_once(idle_recentering).unwrap()
+    }
+
+    #[test]
+    fn idle_recenter_waits_for_the_idle_timeout() {
+        let mut world = recenter_world(Vec3::X, ControllerState::IdlePostView);
+
+        idle_for(&mut world, 0.6);
+        assert_eq!(camera_target_direction(&mut world), Vec3::Z);
+        assert!(!is_recentering(&mut world));
+
+        idle_for(&mut world, 0.6);
+        assert_eq!(camera_target_direction(&mut world), Vec3::X);
+        assert!(is_recentering(&mut world));
+    }
+
+    #[test]
+    fn idle_recenter_leaves_a_centered_player_alone() {
+        let mut world = recenter_world(Vec3::Z, ControllerState::IdlePostView);
+
+        idle_for(&mut world, 2.0);
+        assert!(!is_recentering(&mut world));
+    }
+
+    #[test]
+    fn idle_recenter_does_not_count_time_spent_moving() {
+        let mut world = recenter_world(Vec3::X, ControllerState::Solving);
+
+        idle_for(&mut world, 2.0);
+        assert_eq!(camera_target_direction(&mut world), Vec3::Z);
+        assert!(!is_recentering(&mut world));
+    }
+
+    #[test]
+    fn any_press_cancels_the_recenter_where_it_is() {
+        let mut world = recenter_world(Vec3::X, ControllerState::IdlePostView);
+
+        idle_for(&mut world, 1.2);
+        assert!(is_recentering(&mut world));
+
+        *world
+            .query::<&mut ControllerScreenPosition>()
+            .single_mut(&mut world) = ControllerScreenPosition::Position(Vec2::ZERO);
+        idle_for(&mut world, 0.1);
+
+        assert!(!is_recentering(&mut world));
+        assert_eq!(camera_target_direction(&mut world), Vec3::Z);
+    }
+
+    fn spawn_camera(world: &mut World, dolly_release_motion: DollyReleaseMotion) -> Entity {
+        let game_settings = GameSettings::default();
+        let dolly_screen_positions = DollyScreenPositions::new(&game_settings);
+        world.insert_resource(game_settings);
+        world.init_resource::<Time>();
+
+        world
+            .spawn((
+                MainCamera,
+                Transform::from_translation(Vec3::Z * 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+                DollyAngularMotion {
+                    axis: Vec3::X,
+                    angular_velocity: 0.0,
+                },
+                dolly_screen_positions,
+                dolly_release_motion,
+            ))
+            .id()
+    }
+
+    fn drag_to(world: &mut World, position: Vec2) {
+        let mut controller_query = world.query::<&mut ControllerScreenPosition>();
+        match controller_query.iter_mut(world).next() {
+            Some(mut controller_screen_position) => {
+                *controller_screen_position = ControllerScreenPosition::Position(position)
+            }
+            None => {
+                world.spawn(ControllerScreenPosition::Position(position));
+            }
+        }
+
+        world.run_system_once(camera_dolly).unwrap();
+    }
+
+    fn press_keys(world: &mut World, key_codes: &[KeyCode]) -> f32 {
+        let mut keys = ButtonInput::<KeyCode>::default();
+        for key_code in key_codes {
+            keys.press(*key_code);
+        }
+        world.insert_resource(keys);
+        world.init_resource::<NextState<ControllerState>>();
+
+        world.run_system_once(keyboard_dolly).unwrap();
+        world.query::<&DollyAngularMotion>().single(world).angular_velocity
+    }
+
+    #[test]
+    fn keyboard_dolly_ignores_keys_held_with_a_modifier() {
+        let mut world = World::new();
+        spawn_camera(&mut world, DollyReleaseMotion::default());
+
+        assert_eq!(press_keys(&mut world, &[KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::KeyD]), 0.0);
+        assert_eq!(press_keys(&mut world, &[KeyCode::ShiftRight, KeyCode::KeyW]), 0.0);
+        assert_ne!(press_keys(&mut world, &[KeyCode::KeyD]), 0.0);
+    }
+
+    #[test]
+    fn release_continues_the_last_drag_velocity() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world, DollyReleaseMotion::default());
+
+        drag_to(&mut world, Vec2::new(100.0, 100.0));
+        drag_to(&mut world, Vec2::new(140.0, 100.0));
+
+        let drag_motion = world.get::<DollyAngularMotion>(camera).unwrap().clone();
+        assert!(drag_motion.angular_velocity > 0.0);
+
+        // Friction has worn the spin down since the last drag sample.
+        world.get_mut::<DollyAngularMotion>(camera).unwrap().angular_velocity *= 0.5;
+        world.run_system_once(release_dolly).unwrap();
+
+        let released_motion = world.get::<DollyAngularMotion>(camera).unwrap();
+        assert_eq!(released_motion.angular_velocity, drag_motion.angular_velocity);
+        assert_eq!(released_motion.axis, drag_motion.axis);
+        assert!(world.get::<DollyReleaseMotion>(camera).unwrap().motion.is_none());
+    }
+
+    #[test]
+    fn release_after_holding_still_does_not_spin() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world, DollyReleaseMotion::default());
+
+        drag_to(&mut world, Vec2::new(100.0, 100.0));
+        drag_to(&mut world, Vec2::new(140.0, 100.0));
+
+        // Held still until friction stopped the spin.
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(1));
+        world.get_mut::<DollyAngularMotion>(camera).unwrap().angular_velocity = 0.0;
+        world.run_system_once(release_dolly).unwrap();
+
+        assert_eq!(world.get::<DollyAngularMotion>(camera).unwrap().angular_velocity, 0.0);
+        assert!(world.get::<DollyReleaseMotion>(camera).unwrap().motion.is_none());
+    }
+
+    #[test]
+    fn release_after_a_single_sample_leaves_the_motion_alone() {
+        let mut world = World::new();
+        let camera = spawn_camera(&mut world, DollyReleaseMotion::default());
+
+        drag_to(&mut world, Vec2::new(100.0, 100.0));
+        assert!(world.get::<DollyReleaseMotion>(camera).unwrap().motion.is_none());
+
+        world.get_mut::<DollyAngularMotion>(camera).unwrap().angular_velocity = 0.2;
+        world.run_system_once(release_dolly).unwrap();
+
+        assert_eq!(world.get::<DollyAngularMotion>(camera).unwrap().angular_velocity, 0.2);
+    }
+
+    #[test]
+    fn resetting_screen_positions_forgets_the_release_motion() {
+        let mut world = World::new();
+        let camera = spawn_camera(
+            &mut world,
+            DollyReleaseMotion {
+                motion: Some(DollyAngularMotion {
+                    axis: Vec3::Y,
+                    angular_velocity: 0.3,
+                }),
+                sampled_at: Duration::ZERO,
+            },
+        );
+
+        world.run_system_once(reset_dolly_screen_positions).unwrap();
+        world.run_system_once(release_dolly).unwrap();
+
+        assert_eq!(world.get::<DollyAngularMotion>(camera).unwrap().angular_velocity, 0.0);
+    }
+
+    fn ring_buffer(capacity: usize, positions: &[Vec2]) -> ringbuffer::AllocRingBuffer<Vec2> {
+        let mut buffer = ringbuffer::AllocRingBuffer::new(capacity);
+        for position in positions {
+            buffer.push(*position);
+        }
+        buffer
+    }
+
+    #[test]
+    fn average_delta_is_zero_without_two_samples() {
+        assert_eq!(get_average_delta(&ring_buffer(2, &[])), Vec2::ZERO);
+        assert_eq!(get_average_delta(&ring_buffer(1, &[Vec2::new(4.0, 2.0)])), Vec2::ZERO);
+        // A buffer of one only ever holds the latest sample.
+        assert_eq!(get_average_delta(&ring_buffer(1, &[Vec2::ZERO, Vec2::new(4.0, 2.0)])), Vec2::ZERO);
+    }
+
+    #[test]
+    fn average_delta_of_two_samples_is_their_difference() {
+        let buffer = ring_buffer(2, &[Vec2::new(1.0, 1.0), Vec2::new(4.0, -1.0)]);
+
+        assert_eq!(get_average_delta(&buffer), Vec2::new(3.0, -2.0));
+    }
+
+    #[test]
+    fn average_delta_of_a_full_buffer_uses_the_newest_samples() {
+        let buffer = ring_buffer(2, &[Vec2::new(-10.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(4.0, -1.0)]);
+
+        assert_eq!(get_average_delta(&buffer), Vec2::new(3.0, -2.0));
+    }
+
+    #[test]
+    fn dolly_angular_velocity_scales_linearly_with_sensitivity() {
+        let delta = Vec2::new(30.0, 40.0);
+        let base = dolly_angular_velocity(delta, 1.0);
+
+        assert!((base - 50.0 / DOLLY_PIXELS_PER_RADIAN).abs() < 1e-6);
+        assert!((dolly_angular_velocity(delta, 2.0) - 2.0 * base).abs() < 1e-6);
+        assert!((dolly_angular_velocity(delta, 0.5) - 0.5 * base).abs() < 1e-6);
+        assert_eq!(dolly_angular_velocity(delta, 0.0), 0.0);
+        assert_eq!(dolly_angular_velocity(delta, -1.0), 0.0);
+    }
+
+    const OFF_CENTRE_FOCUS: Vec3 = Vec3::new(0.4, -0.2, 0.1);
+
+    fn focus_world(looking_at: Vec3, focus: Vec3) -> World {
+        let mut world = World::new();
+        world.init_resource::<GameSettings>();
+        world.init_resource::<Time>();
+        world.spawn((
+            MainCamera,
+            Transform::from_translation(focus + Vec3::Z * 3.0).looking_at(focus, Vec3::Y),
+            CameraTarget {
+                translation_dir: Vec3::Z,
+                translation_norm: 3.0,
+                up: Vec3::Y,
+                looking_at,
+                min_norm: CAMERA_MIN_NORM,
+                max_norm: CAMERA_MAX_NORM,
+            },
+            CameraFocus(focus),
+            DollyAngularMotion {
+                axis: Vec3::Y,
+                angular_velocity: 0.05,
+            },
+        ));
+        world
+    }
+
+    fn camera_transform(world: &mut World) -> Transform {
+        *world.query_filtered::<&Transform, With<MainCamera>>().single(world)
+    }
+
+    fn faces(transform: &Transform, point: Vec3) -> bool {
+        transform.forward().dot((point - transform.translation).normalize()) > 0.999
+    }
+
+    #[test]
+    fn camera_turns_towards_an_off_centre_looking_at() {
+        let mut world = focus_world(OFF_CENTRE_FOCUS, Vec3::ZERO);
+
+        for _ in 0..500 {
+            world.resource_mut::<Time>().advance_by(Duration::from_millis(16));
+            world.run_system_once(camera_rotate_to_target).unwrap();
+        }
+
+        let transform = camera_transform(&mut world);
+        assert!(faces(&transform, OFF_CENTRE_FOCUS));
+        assert!(transform.translation.distance(OFF_CENTRE_FOCUS + Vec3::Z * 3.0) < 0.01);
+    }
+
+    #[test]
+    fn dolly_relevel_and_orbit_keep_an_off_centre_focus() {
+        let mut world = focus_world(OFF_CENTRE_FOCUS, OFF_CENTRE_FOCUS);
+        world.resource_mut::<Time>().advance_by(Duration::from_millis(16));
+
+        world.run_system_once(update_dolly).unwrap();
+        world.run_system_once(relevel_camera).unwrap();
+        world.run_system_once(orbit_solid).unwrap();
+
+        let transform = camera_transform(&mut world);
+        assert!(faces(&transform, OFF_CENTRE_FOCUS));
+        assert!((transform.translation.distance(OFF_CENTRE_FOCUS) - 3.0).abs() < 1e-4);
+        assert_ne!(transform.translation, OFF_CENTRE_FOCUS + Vec3::Z * 3.0);
+    }
+
+    #[test]
+    fn dolly_angular_velocity_ignores_sub_pixel_drift() {
+        assert_eq!(dolly_angular_velocity(Vec2::new(0.5, 0.5), 4.0), 0.0);
+    }
+
+    fn zoom_target(translation_norm: f32) -> CameraTarget {
+        CameraTarget {
+            translation_dir: Vec3::Z,
+            translation_norm,
+            up: Vec3::Y,
+            looking_at: Vec3::ZERO,
+            min_norm: 2.0,
+            max_norm: 10.0,
+        }
+    }
+
+    #[test]
+    fn a_player_drifting_to_the_edge_eases_the_camera_out_by_a_bounded_step() {
+        let camera_target = zoom_target(4.0);
+
+        assert_eq!(auto_zoom_distance(Vec2::new(0.6, 0.0), &camera_target, 4.0), 4.0);
+
+        let eased_out = auto_zoom_distance(Vec2::new(0.85, -0.2), &camera_target, 4.0);
+        assert!(eased_out > 4.0);
+        assert!(eased_out <= 4.0 * (1.0 + AUTO_ZOOM_MAX_STEP));
+
+        assert_eq!(auto_zoom_distance(Vec2::new(0.0, 1.5), &zoom_target(9.5), 9.5), 10.0);
+    }
+
+    #[test]
+    fn auto_zoom_returns_to_the_preferred_distance_once_the_player_is_framed() {
+        let mut camera_target = zoom_target(4.0);
+        for _ in 0..3 {
+            camera_target.translation_norm = auto_zoom_distance(Vec2::X, &camera_target, 4.0);
+        }
+        assert!(camera_target.translation_norm > 6.0);
+
+        // Between the two thresholds nothing changes, so the zoom doesn't see-saw.
+        let held = camera_target.translation_norm;
+        assert_eq!(auto_zoom_distance(Vec2::new(0.6, 0.0), &camera_target, 4.0), held);
+
+        for _ in 0..10 {
+            camera_target.translation_norm = auto_zoom_distance(Vec2::ZERO, &camera_target, 4.0);
+        }
+        assert!((camera_target.translation_norm - 4.0).abs() < 1e-4);
+    }
+}
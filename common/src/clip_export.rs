@@ -0,0 +1,327 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::TextureFormat,
+        view::screenshot::{Screenshot, ScreenshotCaptured},
+    },
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+};
+use chrono::Utc;
+
+use crate::{
+    game_save::SaveLocation,
+    game_state::{GameState, PuzzleState},
+    ui::message::{MessagePopup, MessagePopupUpperMarker},
+};
+
+const CLIP_DIRECTORY: &str = "clips";
+
+// Hard limits so a misconfigured clip can't exhaust memory or produce a huge file.
+const MAX_CLIP_SECONDS: f32 = 8.0;
+const MAX_FRAMES_PER_SECOND: f32 = 15.0;
+const MAX_CLIP_WIDTH: u32 = 480;
+
+// Lower is better quality but slower; the encode runs off the main thread either way.
+const QUANTIZATION_SPEED: i32 = 20;
+
+#[derive(Resource, Debug, Clone)]
+pub struct ClipExportSettings {
+    pub seconds_before_victory: f32,
+    pub seconds_after_victory: f32,
+    pub frames_per_second: f32,
+    pub max_width: u32,
+}
+
+impl Default for ClipExportSettings {
+    fn default() -> Self {
+        Self {
+            seconds_before_victory: 3.0,
+            seconds_after_victory: 2.0,
+            frames_per_second: 10.0,
+            max_width: 320,
+        }
+    }
+}
+
+impl ClipExportSettings {
+    fn frame_interval(&self) -> f32 {
+        1.0 / self.frames_per_second.clamp(1.0, MAX_FRAMES_PER_SECOND)
+    }
+
+    fn seconds_after_victory(&self) -> f32 {
+        self.seconds_after_victory.clamp(0.0, MAX_CLIP_SECONDS)
+    }
+
+    fn max_frames(&self) -> usize {
+        let seconds = (self.seconds_before_victory.max(0.0) + self.seconds_after_victory())
+            .min(MAX_CLIP_SECONDS);
+        (seconds / self.frame_interval()).ceil() as usize
+    }
+
+    fn max_width(&self) -> u32 {
+        self.max_width.clamp(16, MAX_CLIP_WIDTH)
+    }
+}
+
+struct ClipFrame {
+    width: u16,
+    height: u16,
+    rgba: Vec<u8>,
+}
+
+#[derive(Resource, Default)]
+pub struct ClipRecorder {
+    frames: VecDeque<ClipFrame>,
+    since_last_capture: f32,
+    pending_capture: bool,
+    remaining_after_victory: Option<f32>,
+}
+
+#[derive(Resource)]
+pub struct ClipEncoding(Task<Result<Vec<u8>, String>>);
+
+#[derive(Default)]
+pub struct ClipExportPlugin;
+
+impl Plugin for ClipExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClipExportSettings>()
+            .init_resource::<ClipRecorder>()
+            .add_systems(OnEnter(PuzzleState::Playing), reset)
+            .add_systems(OnEnter(PuzzleState::Victory), start_victory_countdown)
+            .add_systems(
+                Update,
+                (capture, finish_recording.after(capture)).run_if(in_state(GameState::Puzzle)),
+            )
+            .add_systems(Update, poll_encoding.run_if(resource_exists::<ClipEncoding>));
+    }
+}
+
+pub fn reset(mut recorder: ResMut<ClipRecorder>) {
+    *recorder = ClipRecorder::default();
+}
+
+pub fn start_victory_countdown(
+    mut recorder: ResMut<ClipRecorder>,
+    settings: Res<ClipExportSettings>,
+) {
+    recorder.remaining_after_victory = Some(settings.seconds_after_victory());
+}
+
+pub fn capture(
+    mut commands: Commands,
+    mut recorder: ResMut<ClipRecorder>,
+    settings: Res<ClipExportSettings>,
+    time: Res<Time>,
+) {
+    recorder.since_last_capture += time.delta_secs();
+
+    // Only one screenshot is ever in flight, so a slow readback lowers the frame rate of the clip
+    // rather than queueing work behind gameplay.
+    if recorder.pending_capture || recorder.since_last_capture < settings.frame_interval() {
+        return;
+    }
+
+    recorder.since_last_capture = 0.0;
+    recorder.pending_capture = true;
+
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(receive_frame);
+}
+
+fn receive_frame(
+    trigger: Trigger<ScreenshotCaptured>,
+    mut recorder: ResMut<ClipRecorder>,
+    settings: Res<ClipExportSettings>,
+) {
+    recorder.pending_capture = false;
+
+    let Some(frame) = downscale(&trigger.event().0, settings.max_width()) else {
+        return;
+    };
+
+    recorder.frames.push_back(frame);
+    while recorder.frames.len() > settings.max_frames() {
+        recorder.frames.pop_front();
+    }
+}
+
+pub fn finish_recording(
+    mut commands: Commands,
+    mut recorder: ResMut<ClipRecorder>,
+    settings: Res<ClipExportSettings>,
+    time: Res<Time>,
+    encoding: Option<Res<ClipEncoding>>,
+) {
+    let Some(remaining) = recorder.remaining_after_victory.as_mut() else {
+        return;
+    };
+
+    *remaining -= time.delta_secs();
+    if *remaining > 0.0 {
+        return;
+    }
+
+    recorder.remaining_after_victory = None;
+    let frames = recorder.frames.drain(..).collect::<Vec<_>>();
+
+    if frames.is_empty() || encoding.is_some() {
+        return;
+    }
+
+    let delay = (settings.frame_interval() * 100.0).round() as u16;
+    let task = AsyncComputeTaskPool::get().spawn(async move { encode_gif(frames, delay) });
+    commands.insert_resource(ClipEncoding(task));
+}
+
+pub fn poll_encoding(
+    mut commands: Commands,
+    mut encoding: ResMut<ClipEncoding>,
+    save_location: Option<Res<SaveLocation>>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+) {
+    let Some(result) = block_on(future::poll_once(&mut encoding.0)) else {
+        return;
+    };
+
+    commands.remove_resource::<ClipEncoding>();
+
+    let file_name = format!("mazonic-{}.gif", Utc::now().format("%Y%m%d-%H%M%S"));
+    let result = result.and_then(|bytes| platform::save(save_location.as_deref(), &file_name, &bytes));
+
+    let message = match result {
+        Ok(()) => {
+            info!(target: "mazonic::clip_export", "Exported clip {}", file_name);
+            "clip saved"
+        }
+        Err(err) => {
+            warn!(target: "mazonic::clip_export", "Failed to export clip {}: {}", file_name, err);
+            "could not save clip"
+        }
+    };
+
+    if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+        message_popup.0 = message.to_string();
+    }
+}
+
+// Nearest-neighbour downscale straight from the screenshot bytes, so only small frames are kept.
+fn downscale(image: &Image, max_width: u32) -> Option<ClipFrame> {
+    let swap_red_blue = match image.texture_descriptor.format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+        format => {
+            warn!(target: "mazonic::clip_export", "Unsupported screenshot format {:?}", format);
+            return None;
+        }
+    };
+
+    let source_width = image.width();
+    let source_height = image.height();
+    if source_width == 0 || source_height == 0 {
+        return None;
+    }
+
+    let width = source_width.min(max_width);
+    let height = (source_height * width / source_width).max(1);
+
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        let source_y = y * source_height / height;
+        for x in 0..width {
+            let source_x = x * source_width / width;
+            let index = ((source_y * source_width + source_x) * 4) as usize;
+            let [r, g, b, _]: [u8; 4] = image.data.get(index..index + 4)?.try_into().ok()?;
+
+            let (r, b) = if swap_red_blue { (b, r) } else { (r, b) };
+            rgba.extend_from_slice(&[r, g, b, u8::MAX]);
+        }
+    }
+
+    Some(ClipFrame {
+        width: width as u16,
+        height: height as u16,
+        rgba,
+    })
+}
+
+fn encode_gif(mut frames: Vec<ClipFrame>, delay: u16) -> Result<Vec<u8>, String> {
+    let Some((width, height)) = frames.last().map(|frame| (frame.width, frame.height)) else {
+        return Err("no frames captured".to_string());
+    };
+
+    // A resize mid-recording changes the frame size; keep only frames matching the latest one.
+    frames.retain(|frame| frame.width == width && frame.height == height);
+
+    let mut bytes = Vec::new();
+    {
+        let mut encoder =
+            gif::Encoder::new(&mut bytes, width, height, &[]).map_err(|e| e.to_string())?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(|e| e.to_string())?;
+
+        for mut frame in frames {
+            let mut gif_frame =
+                gif::Frame::from_rgba_speed(width, height, &mut frame.rgba, QUANTIZATION_SPEED);
+            gif_frame.delay = delay;
+            encoder.write_frame(&gif_frame).map_err(|e| e.to_string())?;
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use std::fs;
+
+    use crate::game_save::{save_directory, SaveLocation};
+
+    use super::CLIP_DIRECTORY;
+
+    pub fn save(save_location: Option<&SaveLocation>, file_name: &str, bytes: &[u8]) -> Result<(), String> {
+        let directory = save_directory(save_location).join(CLIP_DIRECTORY);
+
+        fs::create_dir_all(&directory)
+            .and_then(|_| fs::write(directory.join(file_name), bytes))
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    use wasm_bindgen::JsCast;
+
+    use crate::game_save::SaveLocation;
+
+    pub fn save(_save_location: Option<&SaveLocation>, file_name: &str, bytes: &[u8]) -> Result<(), String> {
+        let failed = |reason: &str| reason.to_string();
+
+        let window = web_sys::window().ok_or_else(|| failed("no window"))?;
+        let document = window.document().ok_or_else(|| failed("no document"))?;
+
+        let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes));
+        let mut options = web_sys::BlobPropertyBag::new();
+        options.type_("image/gif");
+        let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+            .map_err(|_| failed("could not create blob"))?;
+        let url = web_sys::Url::create_object_url_with_blob(&blob)
+            .map_err(|_| failed("could not create download url"))?;
+
+        let anchor = document
+            .create_element("a")
+            .map_err(|_| failed("could not create link"))?
+            .dyn_into::<web_sys::HtmlAnchorElement>()
+            .map_err(|_| failed("could not create link"))?;
+        anchor.set_href(&url);
+        anchor.set_download(file_name);
+        anchor.click();
+
+        let _ = web_sys::Url::revoke_object_url(&url);
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+#[derive(Debug)]
+pub enum ClipboardError {
+    Unavailable,
+    WriteFailed(String),
+}
+
+impl ClipboardError {
+    pub fn message(&self) -> &str {
+        match self {
+            ClipboardError::Unavailable => "clipboard unavailable",
+            ClipboardError::WriteFailed(_) => "could not copy to clipboard",
+        }
+    }
+}
+
+pub fn copy(text: &str) -> Result<(), ClipboardError> {
+    platform::copy(text)
+}
+
+#[cfg(feature = "noop_clipboard")]
+mod platform {
+    use super::ClipboardError;
+
+    pub fn copy(_text: &str) -> Result<(), ClipboardError> {
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android", feature = "noop_clipboard")))]
+mod platform {
+    use std::sync::{Mutex, OnceLock};
+
+    use super::ClipboardError;
+
+    // Some linux clipboards only serve their contents while the owning handle is alive.
+    static CLIPBOARD: OnceLock<Option<Mutex<arboard::Clipboard>>> = OnceLock::new();
+
+    pub fn copy(text: &str) -> Result<(), ClipboardError> {
+        let clipboard = CLIPBOARD
+            .get_or_init(|| arboard::Clipboard::new().ok().map(Mutex::new))
+            .as_ref()
+            .ok_or(ClipboardError::Unavailable)?;
+
+        let mut clipboard = clipboard.lock().map_err(|_| ClipboardError::Unavailable)?;
+
+        clipboard
+            .set_text(text)
+            .map_err(|e| ClipboardError::WriteFailed(e.to_string()))
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", not(feature = "noop_clipboard")))]
+mod platform {
+    use super::ClipboardError;
+
+    pub fn copy(text: &str) -> Result<(), ClipboardError> {
+        let window = web_sys::window().ok_or(ClipboardError::Unavailable)?;
+        let clipboard = window.navigator().clipboard();
+
+        // The write resolves asynchronously; failures after this point are not observable here.
+        let _ = clipboard.write_text(text);
+        Ok(())
+    }
+}
+
+#[cfg(all(target_os = "android", not(feature = "noop_clipboard")))]
+mod platform {
+    use jni::{objects::JObject, JavaVM};
+
+    use super::ClipboardError;
+
+    const CLIP_LABEL: &str = "mazonic";
+
+    pub fn copy(text: &str) -> Result<(), ClipboardError> {
+        let context = ndk_context::android_context();
+        let vm = unsafe { JavaVM::from_raw(context.vm().cast()) }
+            .map_err(|_| ClipboardError::Unavailable)?;
+        let activity = unsafe { JObject::from_raw(context.context().cast()) };
+
+        let mut env = vm
+            .attach_current_thread()
+            .map_err(|_| ClipboardError::Unavailable)?;
+
+        let write_failed = |e: jni::errors::Error| ClipboardError::WriteFailed(e.to_string());
+
+        let service_name = env.new_string("clipboard").map_err(write_failed)?;
+        let clipboard_manager = env
+            .call_method(
+                &activity,
+                "getSystemService",
+                "(Ljava/lang/String;)Ljava/lang/Object;",
+                &[(&service_name).into()],
+            )
+            .and_then(|value| value.l())
+            .map_err(write_failed)?;
+
+        if clipboard_manager.is_null() {
+            return Err(ClipboardError::Unavailable);
+        }
+
+        let label = env.new_string(CLIP_LABEL).map_err(write_failed)?;
+        let content = env.new_string(text).map_err(write_failed)?;
+        let clip_data = env
+            .call_static_method(
+                "android/content/ClipData",
+                "newPlainText",
+                "(Ljava/lang/CharSequence;Ljava/lang/CharSequence;)Landroid/content/ClipData;",
+                &[(&label).into(), (&content).into()],
+            )
+            .and_then(|value| value.l())
+            .map_err(write_failed)?;
+
+        env.call_method(
+            &clipboard_manager,
+            "setPrimaryClip",
+            "(Landroid/content/ClipData;)V",
+            &[(&clip_data).into()],
+        )
+        .map_err(write_failed)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_carry_a_message_the_ui_can_show() {
+        assert_eq!(ClipboardError::Unavailable.message(), "clipboard unavailable");
+        assert_eq!(
+            ClipboardError::WriteFailed("denied".to_string()).message(),
+            "could not copy to clipboard"
+        );
+    }
+
+    // Run with `cargo test -p mazonic --features noop_clipboard` on machines without a clipboard.
+    #[cfg(feature = "noop_clipboard")]
+    #[test]
+    fn noop_clipboard_accepts_any_text() {
+        assert!(copy("").is_ok());
+        assert!(copy("mazonic daily · 3 stars").is_ok());
+    }
+}
@@ -9,6 +9,5 @@ pub const TRANSPARENCY: f32 = 0.99;
 pub const TEXT_COLOR: Color = Color::srgba(0.9, 0.9, 0.9, TRANSPARENCY);
 pub const FONT_PATH: &str = "fonts/Slimamifbold.ttf";
 
-pub const SYMBOL_TEXTURE_DIMENSIONS: Vec2 = Vec2::new(5.0, 5.0);
 
 
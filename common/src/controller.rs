@@ -2,12 +2,14 @@ use crate::{
     camera::MainCamera,
     controller_screen_position::ControllerScreenPosition,
     game_settings::GameSettings,
+    geometry::{move_player_on_edge, move_player_on_node, project_ray_to_controller_face, NodeMove},
     game_state::PuzzleState,
     levels::GameLevel,
-    maze::border_type::BorderType,
     player::{Player, PlayerMazeState},
-    room::{Edge, Room},
+    room::Room,
     shape::loader::GraphComponent,
+    ui::message::{MessagePopup, MessagePopupUpperMarker},
+    undo::PlayerMoveHistory,
 };
 use bevy::{
     ecs::system::{Query, ResMut},
@@ -15,7 +17,7 @@ use bevy::{
         mouse::{MouseButton, MouseButtonInput},
         ButtonInput, ButtonState,
     },
-    math::{primitives::InfinitePlane3d, NormedVectorSpace, Ray3d, Vec3},
+    math::{NormedVectorSpace, Ray3d, Vec3},
     prelude::*,
     render::camera::Camera,
     state::state::NextState,
@@ -23,7 +25,8 @@ use bevy::{
     window::PrimaryWindow,
 };
 use bevy_rapier3d::{pipeline::QueryFilter, plugin::RapierContext};
-use petgraph::{graphmap::GraphMap, Directed};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 #[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
 #[source(PuzzleState = PuzzleState::Playing)]
@@ -35,12 +38,42 @@ pub enum ControllerState {
     Viewing,
 }
 
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolveGesture {
+    #[default]
+    Incremental,
+    ContinuousPath,
+}
+
+impl SolveGesture {
+    // Touch screens favour drawing the whole route, while mice keep the step-by-step drag.
+    pub fn platform_default() -> Self {
+        if cfg!(target_os = "android") {
+            SolveGesture::ContinuousPath
+        } else {
+            SolveGesture::Incremental
+        }
+    }
+}
+
+const GESTURE_MAX_EDGE_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+const INCREMENTAL_MAX_EDGE_ANGLE: f32 = 3.0 * std::f32::consts::FRAC_PI_4;
+const ONE_WAY_EDGE_MESSAGE: &str = "one way";
+
+// Sent once per attempt to leave a node where there is no edge, or against a one-way edge.
+#[derive(Event, Debug, Clone)]
+pub struct IllegalMove {
+    pub node: Room,
+    pub direction: Vec3,
+}
+
 #[derive(Default)]
 pub struct Controller;
 
 impl Plugin for Controller {
     fn build(&self, app: &mut App) {
-        app.add_sub_state::<ControllerState>();
+        app.add_sub_state::<ControllerState>()
+            .add_event::<IllegalMove>();
     }
 }
 
@@ -122,6 +155,12 @@ pub fn solve(
     mut next_controller_state: ResMut<NextState<ControllerState>>,
     game_settings: Res<GameSettings>,
     mut previous_cursor_position: Local<Option<Vec2>>,
+    mut gesture_points: Local<VecDeque<Vec2>>,
+    mut blocked_edge: Local<Option<(Room, Room)>>,
+    mut nudged: Local<bool>,
+    mut illegal_move_writer: EventWriter<IllegalMove>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+    mut move_history: ResMut<PlayerMoveHistory>,
 ) {
     let Ok(shape) = level.get_single() else {
         return;
@@ -136,201 +175,410 @@ pub fn solve(
     };
 
     let ControllerScreenPosition::Position(cursor_position) = controller_screen_position else {
+        gesture_points.clear();
+        *blocked_edge = None;
+        *nudged = false;
         next_controller_state.set(ControllerState::IdlePostSolve);
         return;
     };
 
-    if previous_cursor_position
+    let cursor_moved = previous_cursor_position
         .filter(|position| position.distance(*cursor_position) < 2.0)
-        .is_some()
-    {
-        return;
-    } else {
+        .is_none();
+
+    if cursor_moved {
         *previous_cursor_position = Some(*cursor_position);
     }
 
     let (camera_global_transform, camera) = camera_query.single();
+    let (mut player_maze_state, Player { radius }) = player_query.single_mut();
+    let player_elevation = game_settings.player_elevation + radius;
+    let node_snap_threshold = shape.node_distance() * game_settings.node_snap_fraction;
+    // Leaving a node must clear the snap threshold, or the player would snap straight back onto it.
+    let dead_zone_radius = shape.node_distance() * game_settings.dead_zone_factor.max(game_settings.node_snap_fraction);
+
+    let gesture_point = match game_settings.solve_gesture {
+        SolveGesture::Incremental => {
+            if !cursor_moved {
+                return;
+            }
+            *cursor_position
+        }
+        SolveGesture::ContinuousPath => {
+            if cursor_moved {
+                gesture_points.push_back(*cursor_position);
+            }
+            let Some(gesture_point) = gesture_points.front() else {
+                return;
+            };
+            *gesture_point
+        }
+    };
 
     let Some(ray) = camera
-        .viewport_to_world(camera_global_transform, *cursor_position)
+        .viewport_to_world(camera_global_transform, gesture_point)
         .ok()
     else {
         // if it was impossible to compute for whatever reason; we can't do anything
+        gesture_points.pop_front();
         return;
     };
 
-    let (mut player_maze_state, Player { radius }) = player_query.single_mut();
-    let player_elevation = game_settings.player_elevation + radius;
-    let node_snap_threshold = shape.node_distance() * 0.1;
+    let max_edge_angle = match game_settings.solve_gesture {
+        SolveGesture::Incremental => INCREMENTAL_MAX_EDGE_ANGLE,
+        SolveGesture::ContinuousPath => GESTURE_MAX_EDGE_ANGLE,
+    };
 
-    if let Some(new_player_maze_state) = match player_maze_state.as_ref() {
-        PlayerMazeState::Node(node) => {
-            move_player_on_node(&node, &graph, player_elevation, node_snap_threshold, ray)
-        }
-        PlayerMazeState::Edge(from_node, to_node, _) => move_player_on_edge(
-            &from_node,
-            &to_node,
+    let new_player_maze_state = match player_maze_state.as_ref() {
+        PlayerMazeState::Node(node) => match move_player_on_node(
+            &node,
+            &graph,
+            player_elevation,
+            dead_zone_radius,
+            max_edge_angle,
             ray,
+        ) {
+            NodeMove::Enter(new_player_maze_state) => {
+                *blocked_edge = None;
+                *nudged = false;
+                Some(new_player_maze_state)
+            }
+            NodeMove::OneWayBlocked(to_node) => {
+                if *blocked_edge != Some((*node, to_node)) {
+                    *blocked_edge = Some((*node, to_node));
+                    if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+                        message_popup.0 = ONE_WAY_EDGE_MESSAGE.to_string();
+                    }
+                }
+                if !*nudged {
+                    *nudged = true;
+                    illegal_move_writer.send(IllegalMove {
+                        node: *node,
+                        direction: (to_node.position() - node.position()).normalize_or_zero(),
+                    });
+                }
+                None
+            }
+            NodeMove::NoEdge(direction) => {
+                if !*nudged {
+                    *nudged = true;
+                    illegal_move_writer.send(IllegalMove {
+                        node: *node,
+                        direction,
+                    });
+                }
+                None
+            }
+            NodeMove::Stay => {
+                // Back inside the dead zone, so the next push away counts as a fresh attempt.
+                *nudged = false;
+                None
+            }
+        },
+        PlayerMazeState::Edge(from_node, to_node, _) => shape
+            .border_type(&from_node.face(), &to_node.face())
+            .and_then(|border_type| {
+                move_player_on_edge(&from_node, &to_node, ray, player_elevation, node_snap_threshold, &border_type)
+            }),
+    };
+
+    if game_settings.solve_gesture == SolveGesture::ContinuousPath {
+        advance_gesture(
+            &mut gesture_points,
+            player_maze_state.as_ref(),
+            new_player_maze_state.as_ref(),
+            dead_zone_radius,
             player_elevation,
-            node_snap_threshold,
-            &shape,
-        ),
-    } {
+            ray,
+        );
+    }
+
+    if let Some(new_player_maze_state) = new_player_maze_state {
+        if let PlayerMazeState::Node(room) = new_player_maze_state {
+            move_history.record(room);
+        }
         *player_maze_state = new_player_maze_state;
     }
 }
 
-fn project_ray_to_controller_face(
-    ray: Ray3d,
-    cube_node: &Room,
+// A gesture point stays queued while the player is still making node to node progress towards
+// it, and is consumed once the player is sliding along an edge under it or has nowhere to go.
+fn advance_gesture(
+    gesture_points: &mut VecDeque<Vec2>,
+    player_maze_state: &PlayerMazeState,
+    new_player_maze_state: Option<&PlayerMazeState>,
+    dead_zone_radius: f32,
     player_elevation: f32,
-) -> Option<Vec3> {
-    let plane_normal = cube_node.face().normal();
-    let plane_point = cube_node.position() + player_elevation * plane_normal;
-
-    ray.intersect_plane(plane_point, InfinitePlane3d::new(plane_normal))
-        .map(|ray_distance| ray.origin + ray.direction.normalize() * ray_distance)
-}
-
-fn project_point_to_plane(point: Vec3, plane_position: Vec3, plane_normal: Vec3) -> Vec3 {
-    point - plane_normal.dot(point - plane_position) * plane_normal
+    ray: Ray3d,
+) {
+    match (player_maze_state, new_player_maze_state) {
+        (PlayerMazeState::Node(node), None) => {
+            let reached_point = project_ray_to_controller_face(ray, node, player_elevation)
+                .map(|point| {
+                    let node_player_position =
+                        node.position() + node.face().normal() * player_elevation;
+                    (point - node_player_position).norm() <= dead_zone_radius
+                })
+                .unwrap_or(false);
+
+            if reached_point {
+                gesture_points.pop_front();
+            } else {
+                // The drawn path left the graph; stop at the last valid node.
+                gesture_points.clear();
+            }
+        }
+        (PlayerMazeState::Edge(from_node, ..), Some(PlayerMazeState::Node(node)))
+            if from_node == node =>
+        {
+            gesture_points.pop_front();
+        }
+        (PlayerMazeState::Edge(..), Some(PlayerMazeState::Edge(..))) | (_, None) => {
+            gesture_points.pop_front();
+        }
+        _ => {}
+    }
 }
 
-fn move_player_on_node(
-    node: &Room,
-    graph: &GraphMap<Room, Edge, Directed>,
-    player_elevation: f32,
-    node_snap_threshold: f32,
-    ray: Ray3d,
-) -> Option<PlayerMazeState> {
-    let face_intersection_point = project_ray_to_controller_face(ray, node, player_elevation)?;
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetEvent,
+        ecs::system::RunSystemOnce,
+        render::camera::{camera_system, ManualTextureViews},
+        window::{WindowCreated, WindowResized, WindowScaleFactorChanged},
+    };
+    use petgraph::{graphmap::GraphMap, Directed};
+
+    use crate::{
+        geometry::move_player_on_edge,
+        maze::border_type::BorderType,
+        play_statistics::{start_timer_on_first_move, SolveTime},
+        player::{start_nudge, PlayerNudge},
+        room::{test_support::room_at, Edge},
+        shape::cube,
+    };
 
-    let node_player_position = node.position() + node.face().normal() * player_elevation;
+    use super::*;
 
-    let face_intersection_from_player = face_intersection_point - node_player_position;
+    const ELEVATION: f32 = 0.1;
+    const DEAD_ZONE: f32 = 0.1;
+    const SNAP_THRESHOLD: f32 = 0.1;
 
-    if face_intersection_from_player.norm() <= node_snap_threshold {
-        return None;
+    fn room(id: u64, x: f32, y: f32) -> Room {
+        room_at(id, Vec3::new(x, y, 0.0))
     }
 
-    let node_face_normal = node.face().normal();
-    let node_player_plane_position = node.position() + player_elevation * node_face_normal;
+    // An L-shaped corridor on one face: 0 -> 1 -> 2 along x, then 2 -> 3 along y.
+    fn corridor() -> (Vec<Room>, GraphMap<Room, Edge, Directed>) {
+        let rooms = vec![room(0, 0.0, 0.0), room(1, 1.0, 0.0), room(2, 2.0, 0.0), room(3, 2.0, 1.0)];
 
-    graph
-        .edges(node.clone())
-        .map(|(_, to_node, _)| to_node)
-        .min_by_key(|to_node| {
-            let to_node_position = to_node.position();
+        let mut graph = GraphMap::new();
+        for (from, to) in [(0, 1), (1, 2), (2, 3)] {
+            graph.add_edge(rooms[from], rooms[to], Edge);
+            graph.add_edge(rooms[to], rooms[from], Edge);
+        }
 
-            let to_node_player_plane_position =
-                project_point_to_plane(to_node_position, node_player_position, node_face_normal);
+        (rooms, graph)
+    }
 
-            let edge_vec = to_node_player_plane_position - node_player_plane_position;
+    // Feeds the drawn points through the same node and edge steps as `solve` in continuous path mode,
+    // with the camera looking straight down at the face.
+    fn draw_gesture(start: Room, graph: &GraphMap<Room, Edge, Directed>, points: &[Vec2]) -> (PlayerMazeState, Vec<u64>) {
+        let mut gesture_points = points.iter().copied().collect::<VecDeque<Vec2>>();
+        let mut player_maze_state = PlayerMazeState::Node(start);
+        let mut arrivals = vec![];
+
+        for _ in 0..100 {
+            let Some(point) = gesture_points.front().copied() else {
+                break;
+            };
+            let ray = Ray3d::new(point.extend(5.0), Dir3::NEG_Z);
+
+            let new_player_maze_state = match &player_maze_state {
+                PlayerMazeState::Node(node) => {
+                    match move_player_on_node(node, graph, ELEVATION, DEAD_ZONE, GESTURE_MAX_EDGE_ANGLE, ray) {
+                        NodeMove::Enter(new_player_maze_state) => Some(new_player_maze_state),
+                        _ => None,
+                    }
+                }
+                PlayerMazeState::Edge(from_node, to_node, _) => {
+                    move_player_on_edge(from_node, to_node, ray, ELEVATION, SNAP_THRESHOLD, &BorderType::SameFace)
+                }
+            };
+
+            advance_gesture(
+                &mut gesture_points,
+                &player_maze_state,
+                new_player_maze_state.as_ref(),
+                DEAD_ZONE,
+                ELEVATION,
+                ray,
+            );
 
-            (edge_vec.angle_between(face_intersection_from_player) * 50.0) as u16
-        })
-        .map(|to_node| PlayerMazeState::Edge(node.clone(), to_node, node_player_plane_position))
-}
+            if let Some(new_player_maze_state) = new_player_maze_state {
+                if let PlayerMazeState::Node(room) = &new_player_maze_state {
+                    arrivals.push(room.id);
+                }
+                player_maze_state = new_player_maze_state;
+            }
+        }
 
-fn move_player_on_edge(
-    from_node: &Room,
-    to_node: &Room,
-    ray: Ray3d,
-    player_elevation: f32,
-    node_snap_threshold: f32,
-    level: &GameLevel,
-) -> Option<PlayerMazeState> {
-    let player_plane_edge_intersection =
-        compute_player_plane_edge_intersection(ray, from_node, to_node, player_elevation, level)?;
-
-    let to_node_to_intersection = to_node.position() + to_node.face().normal() * player_elevation
-        - player_plane_edge_intersection;
-
-    let from_node_to_intersection = from_node.position()
-        + from_node.face().normal() * player_elevation
-        - player_plane_edge_intersection;
-
-    let new_player_state = if to_node_to_intersection.norm() < node_snap_threshold {
-        PlayerMazeState::Node(to_node.clone())
-    } else if from_node_to_intersection.norm() < node_snap_threshold {
-        PlayerMazeState::Node(from_node.clone())
-    } else {
-        PlayerMazeState::Edge(
-            from_node.clone(),
-            to_node.clone(),
-            player_plane_edge_intersection,
-        )
-    };
+        (player_maze_state, arrivals)
+    }
 
-    Some(new_player_state)
-}
+    #[test]
+    fn a_drawn_route_commits_along_every_edge_it_crosses() {
+        let (rooms, graph) = corridor();
+        let points = [
+            Vec2::new(0.5, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.5, 0.0),
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 0.5),
+            Vec2::new(2.0, 1.0),
+        ];
+
+        let (player_maze_state, arrivals) = draw_gesture(rooms[0], &graph, &points);
+
+        assert!(matches!(player_maze_state, PlayerMazeState::Node(room) if room.id == 3));
+        assert_eq!(arrivals, vec![1, 2, 3]);
+    }
 
-fn compute_player_plane_edge_intersection(
-    screen_ray: Ray3d,
-    from_node: &Room,
-    to_node: &Room,
-    player_elevation: f32,
-    level: &GameLevel,
-) -> Option<Vec3> {
-    let from_face = from_node.face();
-    let to_face = to_node.face();
+    #[test]
+    fn a_route_leaving_the_graph_stops_at_the_last_valid_node() {
+        let (rooms, graph) = corridor();
+        // Room 1 has no edge towards negative y, so the rest of the route is dropped.
+        let points = [
+            Vec2::new(0.5, 0.0),
+            Vec2::new(1.0, 0.0),
+            Vec2::new(1.0, -0.5),
+            Vec2::new(1.5, 0.0),
+            Vec2::new(2.0, 0.0),
+        ];
+
+        let (player_maze_state, arrivals) = draw_gesture(rooms[0], &graph, &points);
+
+        assert!(matches!(player_maze_state, PlayerMazeState::Node(room) if room.id == 1));
+        assert_eq!(arrivals, vec![1]);
+    }
 
-    let border_type = level.border_type(&from_face, &to_face)?;
+    // A camera looking straight down at the face from above, with its viewport computed the way
+    // the renderer would.
+    fn solve_world(graph: GraphMap<Room, Edge, Directed>, start: Room) -> World {
+        let mut world = World::new();
+        world.init_resource::<Events<WindowResized>>();
+        world.init_resource::<Events<WindowCreated>>();
+        world.init_resource::<Events<WindowScaleFactorChanged>>();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<ManualTextureViews>();
+        world.spawn((Window::default(), PrimaryWindow));
+
+        let camera_transform = Transform::from_xyz(0.0, 0.0, 5.0).looking_at(Vec3::ZERO, Vec3::Y);
+        world.spawn((Camera::default(), Projection::default(), camera_transform, GlobalTransform::from(camera_transform)));
+        world.run_system_once(camera_system::<Projection>).unwrap();
+
+        world.init_resource::<Events<MouseButtonInput>>();
+        world.init_resource::<Events<IllegalMove>>();
+        world.init_resource::<NextState<ControllerState>>();
+        world.init_resource::<PlayerMoveHistory>();
+        world.insert_resource(GameSettings { solve_gesture: SolveGesture::Incremental, ..default() });
+        world.spawn((MessagePopup::default(), MessagePopupUpperMarker));
+        world.spawn(GameLevel::cube(2, cube::Coloring::Full([0, 1, 2])));
+        world.spawn(GraphComponent(graph));
+        world.spawn((Player { radius: 0.1 }, PlayerMazeState::Node(start)));
+        world
+    }
 
-    match border_type {
-        BorderType::SameFace => {
-            compute_intersection_point_of_edge(screen_ray, &from_node, player_elevation, &to_node)
-        }
-        BorderType::Connected => {
-            let to_plane_intersection = compute_intersection_point_of_edge(
-                screen_ray,
-                &to_node,
-                player_elevation,
-                &from_node,
-            );
+    fn point_at(world: &mut World, target: Vec3) {
+        let (camera_global_transform, camera) = world.query::<(&GlobalTransform, &Camera)>().single(world);
+        let cursor_position = ControllerScreenPosition::Position(camera.world_to_viewport(camera_global_transform, target).unwrap());
 
-            let from_plane_intersection = compute_intersection_point_of_edge(
-                screen_ray,
-                &from_node,
-                player_elevation,
-                &to_node,
-            );
-            
-            std::cmp::max_by_key(
-                from_plane_intersection,
-                to_plane_intersection,
-                |opt_intersection| opt_intersection.map(|x| (1000.0 / x.norm()) as u16),
-            )
+        if let Ok(mut controller_screen_position) = world.query::<&mut ControllerScreenPosition>().get_single_mut(world) {
+            *controller_screen_position = cursor_position;
+            return;
         }
+        world.spawn(cursor_position);
     }
-}
 
-fn compute_intersection_point_of_edge(
-    ray: Ray3d,
-    from_room: &Room,
-    elevation: f32,
-    to_room: &Room,
-) -> Option<Vec3> {
-    let from_normal = from_room.face().normal();
-
-    if from_normal.dot(Vec3::from(ray.direction)) > 0.0 {
-        return None;
+    #[test]
+    fn pushing_against_a_one_way_edge_is_an_illegal_move_that_leaves_the_player_in_place() {
+        let (start, end) = (room(0, 0.0, 0.0), room(1, 1.0, 0.0));
+        let mut graph = GraphMap::new();
+        graph.add_edge(end, start, Edge);
+
+        let mut world = solve_world(graph, start);
+        point_at(&mut world, Vec3::new(0.8, 0.0, 0.15));
+
+        world.run_system_once(solve).unwrap();
+
+        let illegal_moves = world
+            .resource_mut::<Events<IllegalMove>>()
+            .drain()
+            .collect::<Vec<_>>();
+        assert_eq!(illegal_moves.len(), 1);
+        assert_eq!(illegal_moves[0].node.id, start.id);
+        assert!(illegal_moves[0].direction.distance(Vec3::X) < 1e-4);
+
+        let (player, player_maze_state) = world.query::<(Entity, &PlayerMazeState)>().single(&world);
+        assert!(matches!(player_maze_state, PlayerMazeState::Node(room) if room.id == start.id));
+        assert!(world.resource::<PlayerMoveHistory>().0.is_empty());
+        assert_eq!(world.query::<&MessagePopup>().single(&world).0, ONE_WAY_EDGE_MESSAGE);
+
+        // The same move, read by the marker, starts its nudge.
+        world.send_event(illegal_moves[0].clone());
+        world.run_system_once(start_nudge).unwrap();
+        assert!(world.get::<PlayerNudge>(player).is_some());
     }
 
-    let room_controller_position = from_room.position() + elevation * from_normal;
-    let to_room_controller_position = to_room.position() + elevation * to_room.face().normal();
-
-    let other_node_on_from_controller_plane = project_point_to_plane(
-        to_room_controller_position,
-        room_controller_position,
-        from_normal,
-    );
+    #[test]
+    fn incremental_drags_far_from_every_edge_are_illegal_rather_than_snapped() {
+        let (start, end) = (room(0, 0.0, 0.0), room(1, 1.0, 0.0));
+        let mut graph = GraphMap::new();
+        graph.add_edge(start, end, Edge);
+        graph.add_edge(end, start, Edge);
+
+        let backwards = Ray3d::new(Vec3::new(-0.5, 0.0, 5.0), Dir3::NEG_Z);
+        let sideways = Ray3d::new(Vec3::new(0.0, 0.5, 5.0), Dir3::NEG_Z);
+
+        assert!(matches!(
+            move_player_on_node(&start, &graph, ELEVATION, DEAD_ZONE, INCREMENTAL_MAX_EDGE_ANGLE, backwards),
+            NodeMove::NoEdge(_)
+        ));
+        assert!(matches!(
+            move_player_on_node(&start, &graph, ELEVATION, DEAD_ZONE, INCREMENTAL_MAX_EDGE_ANGLE, sideways),
+            NodeMove::Enter(PlayerMazeState::Edge(_, to_node, _)) if to_node.id == end.id
+        ));
+    }
 
-    let from_controller_to = other_node_on_from_controller_plane - room_controller_position;
-    let projected_ray_on_face = project_ray_to_controller_face(ray, from_room, elevation)?;
-    
-    let relative_intersection_point = projected_ray_on_face - room_controller_position;
-    let distance_along_node_other_vec = relative_intersection_point.dot(from_controller_to) / from_controller_to.dot(from_controller_to);
-    
-    Some(distance_along_node_other_vec.clamp(0.0, 1.0) * from_controller_to + room_controller_position)
+    #[test]
+    fn a_tap_inside_the_dead_zone_leaves_the_player_on_its_node_until_dragged_past_it() {
+        let (start, end) = (room(0, 0.0, 0.0), room(1, 1.0, 0.0));
+        let mut graph = GraphMap::new();
+        graph.add_edge(start, end, Edge);
+        graph.add_edge(end, start, Edge);
+
+        let mut world = solve_world(graph, start);
+        world.insert_resource(SolveTime { awaiting_start: true, ..default() });
+
+        // The cube has a node distance of 0.5, so the dead zone reaches 0.05 from the node.
+        point_at(&mut world, Vec3::new(0.03, 0.0, 0.15));
+        world.run_system_once(solve).unwrap();
+        world.run_system_once(start_timer_on_first_move).unwrap();
+
+        assert!(matches!(world.query::<&PlayerMazeState>().single(&world), PlayerMazeState::Node(room) if room.id == start.id));
+        assert!(world.resource::<Events<IllegalMove>>().is_empty());
+        assert!(world.resource::<SolveTime>().awaiting_start);
+
+        point_at(&mut world, Vec3::new(0.3, 0.0, 0.15));
+        world.run_system_once(solve).unwrap();
+        world.run_system_once(start_timer_on_first_move).unwrap();
+
+        assert!(matches!(
+            world.query::<&PlayerMazeState>().single(&world),
+            PlayerMazeState::Edge(from_node, to_node, _) if from_node.id == start.id && to_node.id == end.id
+        ));
+        assert!(world.resource::<SolveTime>().running);
+    }
 }
@@ -0,0 +1,251 @@
+use std::fmt;
+
+use bevy::{prelude::*, utils::HashMap};
+use itertools::Itertools;
+use petgraph::{graphmap::GraphMap, Directed};
+
+use crate::{
+    error::MazonicError,
+    geometry::project_point_to_plane,
+    goal_placement::GoalPlacement,
+    levels::GameLevel,
+    load_level_asset::spawn_maze_level_data,
+    maze::solver::shortest_path,
+    room::{Edge, Room},
+    shape::loader::MazeLevelData,
+    sound::{Note, NoteValue},
+};
+
+const MIN_EDGE_LENGTH: f32 = 1e-3;
+const MIN_EDGE_ANGLE: f32 = 0.2;
+const PENTATONIC_OFFSETS: [i32; 5] = [0, 2, 4, 7, 9];
+const BASE_KEY: i32 = 60;
+const NOTE_VELOCITY: i32 = 90;
+
+#[derive(Debug)]
+pub enum CustomMazeError {
+    MissingRoom(u64),
+    UnreachableGoal { start: u64, goal: u64 },
+    InvalidBorder { from: u64, to: u64 },
+    DegenerateEdge { from: u64, to: u64 },
+    DegenerateAngle { room: u64, first: u64, second: u64 },
+}
+
+impl fmt::Display for CustomMazeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomMazeError::MissingRoom(room) => write!(f, "room {room} is not in the graph"),
+            CustomMazeError::UnreachableGoal { start, goal } => {
+                write!(f, "goal room {goal} cannot be reached from start room {start}")
+            }
+            CustomMazeError::InvalidBorder { from, to } => write!(
+                f,
+                "edge {from} -> {to} joins faces that do not share a border on this shape"
+            ),
+            CustomMazeError::DegenerateEdge { from, to } => {
+                write!(f, "edge {from} -> {to} has no length")
+            }
+            CustomMazeError::DegenerateAngle { room, first, second } => write!(
+                f,
+                "edges from room {room} to {first} and {second} point in nearly the same direction"
+            ),
+        }
+    }
+}
+
+// Builds level data from a hand authored graph, so it can go through the same spawn path as
+// bundled and daily levels. Every room gets a note from a pentatonic scale.
+pub fn build_level_data(
+    level: GameLevel,
+    graph: GraphMap<Room, Edge, Directed>,
+    start: Room,
+    goal: Room,
+) -> Result<MazeLevelData, CustomMazeError> {
+    validate(&level, &graph, start, goal)?;
+
+    let solution = shortest_path(&graph, start, goal).ok_or(CustomMazeError::UnreachableGoal {
+        start: start.id,
+        goal: goal.id,
+    })?;
+
+    let node_id_to_note = graph
+        .nodes()
+        .map(|room| (room.id, default_note(room.id)))
+        .collect::<HashMap<u64, Note>>();
+
+    Ok(MazeLevelData {
+        shape: level.shape,
+        nodes_per_edge: level.nodes_per_edge,
+        graph,
+        solution,
+        goal_placement: GoalPlacement::Generated,
+        node_id_to_note,
+        encrypted_melody: None,
+        background_loop: None,
+        #[cfg(debug_assertions)]
+        melody: None,
+    })
+}
+
+// Spawns the graph and solution components; the maze meshes follow on entering `PuzzleState::Playing`.
+pub fn spawn(commands: &mut Commands, asset_server: &AssetServer, level_data: MazeLevelData) -> Result<(), MazonicError> {
+    spawn_maze_level_data(commands, asset_server, level_data).map(|_| ())
+}
+
+pub fn validate(
+    level: &GameLevel,
+    graph: &GraphMap<Room, Edge, Directed>,
+    start: Room,
+    goal: Room,
+) -> Result<(), CustomMazeError> {
+    for room in [start, goal] {
+        if !graph.contains_node(room) {
+            return Err(CustomMazeError::MissingRoom(room.id));
+        }
+    }
+
+    for (from, to, _) in graph.all_edges() {
+        if from.position().distance(to.position()) < MIN_EDGE_LENGTH {
+            return Err(CustomMazeError::DegenerateEdge { from: from.id, to: to.id });
+        }
+
+        if level.border_type(&from.face(), &to.face()).is_none() {
+            return Err(CustomMazeError::InvalidBorder { from: from.id, to: to.id });
+        }
+    }
+
+    for room in graph.nodes() {
+        let normal = room.face().normal();
+
+        let edge_directions = graph
+            .neighbors(room)
+            .map(|neighbor| {
+                let projected = project_point_to_plane(neighbor.position(), room.position(), normal);
+                (neighbor, projected - room.position())
+            })
+            .collect::<Vec<(Room, Vec3)>>();
+
+        // The controller picks an edge by the angle to the cursor, so near-parallel edges are ambiguous.
+        let degenerate_pair = edge_directions
+            .iter()
+            .tuple_combinations()
+            .find(|((_, first), (_, second))| first.angle_between(*second) < MIN_EDGE_ANGLE);
+
+        if let Some(((first, _), (second, _))) = degenerate_pair {
+            return Err(CustomMazeError::DegenerateAngle {
+                room: room.id,
+                first: first.id,
+                second: second.id,
+            });
+        }
+    }
+
+    if shortest_path(graph, start, goal).is_none() {
+        return Err(CustomMazeError::UnreachableGoal { start: start.id, goal: goal.id });
+    }
+
+    Ok(())
+}
+
+pub(crate) fn default_note(room_id: u64) -> Note {
+    let scale_degree = (room_id % PENTATONIC_OFFSETS.len() as u64) as usize;
+    let octave = ((room_id / PENTATONIC_OFFSETS.len() as u64) % 2) as i32;
+
+    Note {
+        key: BASE_KEY + PENTATONIC_OFFSETS[scale_degree] + 12 * octave,
+        velocity: NOTE_VELOCITY,
+        value: NoteValue::Crotchet,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        room::{test_support::room_on_face, Face},
+        shape::cube,
+    };
+
+    use super::*;
+
+    const TOP: Face = Face { id: 5, normal: Vec3::Z };
+    const RIGHT: Face = Face { id: 2, normal: Vec3::X };
+    const BOTTOM: Face = Face { id: 0, normal: Vec3::NEG_Z };
+
+    fn level() -> GameLevel {
+        GameLevel::cube(2, cube::Coloring::Full([1, 2, 3]))
+    }
+
+    // Two rooms on the top face and one over the edge on the +X face.
+    fn tiny_cube_maze() -> (GraphMap<Room, Edge, Directed>, Room, Room, Room) {
+        let start = room_on_face(0, Vec3::new(-0.25, -0.25, 0.5), TOP);
+        let middle = room_on_face(1, Vec3::new(0.25, -0.25, 0.5), TOP);
+        let goal = room_on_face(2, Vec3::new(0.5, -0.25, 0.25), RIGHT);
+
+        let mut graph = GraphMap::new();
+        for (from, to) in [(start, middle), (middle, goal)] {
+            graph.add_edge(from, to, Edge);
+            graph.add_edge(to, from, Edge);
+        }
+
+        (graph, start, middle, goal)
+    }
+
+    #[test]
+    fn builds_a_hand_made_cube_maze() {
+        let (graph, start, middle, goal) = tiny_cube_maze();
+
+        let level_data = build_level_data(level(), graph, start, goal).unwrap();
+
+        let solution_ids = level_data.solution.iter().map(|room| room.id).collect_vec();
+        assert_eq!(solution_ids, vec![start.id, middle.id, goal.id]);
+        assert_eq!(level_data.node_id_to_note.len(), 3);
+        assert_eq!(level_data.nodes_per_edge, 2);
+    }
+
+    #[test]
+    fn rejects_a_goal_outside_the_graph() {
+        let (graph, start, _, _) = tiny_cube_maze();
+        let stray = room_on_face(9, Vec3::new(0.25, 0.25, 0.5), TOP);
+
+        let result = build_level_data(level(), graph, start, stray);
+        assert!(matches!(result, Err(CustomMazeError::MissingRoom(9))));
+    }
+
+    #[test]
+    fn rejects_an_unreachable_goal() {
+        let (mut graph, start, middle, goal) = tiny_cube_maze();
+        graph.remove_edge(middle, goal);
+
+        let result = build_level_data(level(), graph, start, goal);
+        assert!(matches!(result, Err(CustomMazeError::UnreachableGoal { start: 0, goal: 2 })));
+    }
+
+    #[test]
+    fn rejects_an_edge_between_opposite_faces() {
+        let (mut graph, start, middle, goal) = tiny_cube_maze();
+        let underneath = room_on_face(3, Vec3::new(0.25, -0.25, -0.5), BOTTOM);
+        graph.add_edge(middle, underneath, Edge);
+
+        let result = build_level_data(level(), graph, start, goal);
+        assert!(matches!(result, Err(CustomMazeError::InvalidBorder { from: 1, to: 3 })));
+    }
+
+    #[test]
+    fn rejects_an_edge_without_length() {
+        let (mut graph, start, middle, goal) = tiny_cube_maze();
+        graph.add_edge(middle, middle, Edge);
+
+        let result = build_level_data(level(), graph, start, goal);
+        assert!(matches!(result, Err(CustomMazeError::DegenerateEdge { from: 1, to: 1 })));
+    }
+
+    #[test]
+    fn rejects_nearly_parallel_edges() {
+        let (mut graph, start, _, goal) = tiny_cube_maze();
+        let beside_middle = room_on_face(4, Vec3::new(0.25, -0.2, 0.5), TOP);
+        graph.add_edge(start, beside_middle, Edge);
+
+        let result = build_level_data(level(), graph, start, goal);
+        assert!(matches!(result, Err(CustomMazeError::DegenerateAngle { room: 0, .. })));
+    }
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::game_save::{CurrentPuzzle, PuzzleIdentifier};
+
+// Consecutive days on which a daily puzzle was completed, either difficulty counting.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DailyStreak {
+    pub days: u32,
+    pub last_completed: Option<NaiveDate>,
+}
+
+impl DailyStreak {
+    pub fn record_completion(&mut self, today: NaiveDate) {
+        match self.last_completed {
+            Some(last_completed) if last_completed == today => return,
+            Some(last_completed) if last_completed.succ_opt() == Some(today) => self.days += 1,
+            _ => self.days = 1,
+        }
+
+        self.last_completed = Some(today);
+    }
+
+    // A streak survives until the end of the day after its last completion.
+    pub fn current(&self, today: NaiveDate) -> u32 {
+        match self.last_completed {
+            Some(last_completed) if last_completed == today || last_completed.succ_opt() == Some(today) => self.days,
+            _ => 0,
+        }
+    }
+
+    // Keeps whichever streak was extended most recently.
+    pub fn merge(self, other: Self) -> Self {
+        match self.last_completed.cmp(&other.last_completed) {
+            std::cmp::Ordering::Less => other,
+            std::cmp::Ordering::Greater => self,
+            std::cmp::Ordering::Equal => if self.days >= other.days { self } else { other },
+        }
+    }
+}
+
+pub fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+// Only today's daily extends the streak; one left open past midnight counts for nothing.
+pub fn on_victory(current_puzzle_query: Query<&CurrentPuzzle>, mut daily_streak: ResMut<DailyStreak>) {
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let (PuzzleIdentifier::EasyDaily(daily_level_id) | PuzzleIdentifier::HardDaily(daily_level_id)) = puzzle_identifier else {
+        return;
+    };
+
+    let today = today();
+    if NaiveDate::parse_from_str(daily_level_id, "%Y-%m-%d") != Ok(today) {
+        return;
+    }
+
+    daily_streak.record_completion(today);
+    info!(target: "mazonic::daily_streak", "Daily streak is now {} days", daily_streak.days);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap()
+    }
+
+    fn streak(days: u32, last_completed: u32) -> DailyStreak {
+        DailyStreak {
+            days,
+            last_completed: Some(date(last_completed)),
+        }
+    }
+
+    #[test]
+    fn first_completion_starts_a_streak() {
+        let mut daily_streak = DailyStreak::default();
+        daily_streak.record_completion(date(10));
+
+        assert_eq!(daily_streak, streak(1, 10));
+    }
+
+    #[test]
+    fn completing_on_consecutive_days_increments() {
+        let mut daily_streak = streak(1, 10);
+        daily_streak.record_completion(date(11));
+        daily_streak.record_completion(date(12));
+
+        assert_eq!(daily_streak, streak(3, 12));
+    }
+
+    #[test]
+    fn completing_twice_in_a_day_counts_once() {
+        let mut daily_streak = streak(4, 10);
+        daily_streak.record_completion(date(10));
+
+        assert_eq!(daily_streak, streak(4, 10));
+    }
+
+    #[test]
+    fn skipping_a_day_resets_to_one() {
+        let mut daily_streak = streak(5, 10);
+        daily_streak.record_completion(date(12));
+
+        assert_eq!(daily_streak, streak(1, 12));
+    }
+
+    #[test]
+    fn streak_crosses_month_ends() {
+        let mut daily_streak = DailyStreak {
+            days: 2,
+            last_completed: NaiveDate::from_ymd_opt(2025, 2, 28),
+        };
+        daily_streak.record_completion(date(1));
+
+        assert_eq!(daily_streak, streak(3, 1));
+    }
+
+    #[test]
+    fn current_streak_lapses_after_a_missed_day() {
+        let daily_streak = streak(3, 10);
+
+        assert_eq!(daily_streak.current(date(10)), 3);
+        assert_eq!(daily_streak.current(date(11)), 3);
+        assert_eq!(daily_streak.current(date(12)), 0);
+        assert_eq!(DailyStreak::default().current(date(10)), 0);
+    }
+
+    #[test]
+    fn merge_keeps_the_most_recent_streak() {
+        assert_eq!(streak(7, 9).merge(streak(2, 11)), streak(2, 11));
+        assert_eq!(streak(2, 11).merge(streak(7, 9)), streak(2, 11));
+        assert_eq!(streak(2, 11).merge(streak(3, 11)), streak(3, 11));
+    }
+}
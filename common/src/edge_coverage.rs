@@ -0,0 +1,106 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    game_save::CurrentPuzzle, game_settings::GameSettings, play_statistics::PlayStatistics,
+    player_path::{undirected_edge_key, PlayerPath},
+    shape::loader::GraphComponent,
+};
+
+#[derive(Resource, Default, Debug)]
+pub struct EdgeCoverage {
+    pub crossed: HashSet<(u64, u64)>,
+    pub total: usize,
+}
+
+pub fn edge_coverage_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.edge_counter
+}
+
+pub fn setup(mut commands: Commands) {
+    commands.insert_resource(EdgeCoverage::default());
+}
+
+pub fn reset(mut edge_coverage: ResMut<EdgeCoverage>, graph_query: Query<&GraphComponent>) {
+    let total = graph_query
+        .get_single()
+        .map(|GraphComponent(graph)| {
+            graph
+                .all_edges()
+                .map(|(from, to, _)| undirected_edge_key(&from, &to))
+                .collect::<HashSet<_>>()
+                .len()
+        })
+        .unwrap_or(0);
+
+    *edge_coverage = EdgeCoverage {
+        crossed: HashSet::new(),
+        total,
+    };
+}
+
+pub fn update(
+    mut edge_coverage: ResMut<EdgeCoverage>,
+    player_path_query: Query<&PlayerPath, Changed<PlayerPath>>,
+) {
+    let Ok(PlayerPath(path)) = player_path_query.get_single() else {
+        return;
+    };
+
+    let crossed = path
+        .iter()
+        .zip(path.iter().skip(1))
+        .filter(|(from, to)| from != to)
+        .map(|(from, to)| undirected_edge_key(from, to))
+        .collect::<HashSet<_>>();
+
+    if crossed != edge_coverage.crossed {
+        edge_coverage.crossed = crossed;
+    }
+}
+
+pub fn on_victory(
+    edge_coverage: Res<EdgeCoverage>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    mut play_statistics: ResMut<PlayStatistics>,
+) {
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let crossed = edge_coverage.crossed.len();
+
+    play_statistics.0.entry(puzzle_identifier.clone()).and_modify(|puzzle_statistics| {
+        let best_crossed = puzzle_statistics.edges_crossed.map_or(crossed, |previous| previous.max(crossed));
+        puzzle_statistics.edges_crossed = Some(best_crossed);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::room::{test_support::room, Room};
+
+    use super::*;
+
+    fn crossed_after(path: Vec<Room>) -> usize {
+        let mut world = World::new();
+        world.init_resource::<EdgeCoverage>();
+        world.spawn(PlayerPath(path));
+
+        world.run_system_once(update).unwrap();
+
+        world.resource::<EdgeCoverage>().crossed.len()
+    }
+
+    #[test]
+    fn crossing_the_same_edge_again_counts_it_once() {
+        assert_eq!(crossed_after(vec![room(0), room(1)]), 1);
+        assert_eq!(crossed_after(vec![room(0), room(1), room(0), room(1)]), 1);
+    }
+
+    #[test]
+    fn each_new_edge_adds_to_the_count() {
+        assert_eq!(crossed_after(vec![room(0), room(1), room(0), room(1), room(2)]), 2);
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+};
+
+use crate::{
+    effects::EffectsQuality,
+    game_settings::GameSettings,
+    ui::message::{MessagePopup, MessagePopupUpperMarker},
+};
+
+// Hysteresis: quality drops after a short spell below 40 fps, but only comes back after a much
+// longer spell above 55 fps. The gap between the two thresholds and the two windows stops the
+// lighter workload from immediately looking like a recovery and flipping effects back on.
+const LOWER_FRAME_TIME_MS: f64 = 1000.0 / 40.0;
+const RAISE_FRAME_TIME_MS: f64 = 1000.0 / 55.0;
+const LOWER_AFTER_SECONDS: f32 = 3.0;
+const RAISE_AFTER_SECONDS: f32 = 10.0;
+
+const LOWERED_MESSAGE: &str = "effects reduced for performance";
+const RAISED_MESSAGE: &str = "effects restored";
+
+#[derive(Resource, Debug, Default)]
+pub struct AdaptiveQuality {
+    pub lowered: bool,
+    slow_seconds: f32,
+    fast_seconds: f32,
+}
+
+pub fn adaptive_quality_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.adaptive_quality
+}
+
+pub fn update(
+    time: Res<Time>,
+    diagnostics: Res<DiagnosticsStore>,
+    mut adaptive_quality: ResMut<AdaptiveQuality>,
+    mut game_settings: ResMut<GameSettings>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+) {
+    let Some(frame_time_ms) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|frame_time| frame_time.smoothed())
+    else {
+        return;
+    };
+
+    let delta = time.delta_secs();
+
+    adaptive_quality.slow_seconds = if frame_time_ms > LOWER_FRAME_TIME_MS {
+        adaptive_quality.slow_seconds + delta
+    } else {
+        0.0
+    };
+
+    adaptive_quality.fast_seconds = if frame_time_ms < RAISE_FRAME_TIME_MS {
+        adaptive_quality.fast_seconds + delta
+    } else {
+        0.0
+    };
+
+    let message = if !adaptive_quality.lowered
+        && game_settings.effects_quality != EffectsQuality::Off
+        && adaptive_quality.slow_seconds > LOWER_AFTER_SECONDS
+    {
+        info!(target: "mazonic::effects", "Frame time {frame_time_ms:.1}ms, lowering effects quality");
+        adaptive_quality.lowered = true;
+        game_settings.effects_quality = EffectsQuality::Off;
+        LOWERED_MESSAGE
+    } else if adaptive_quality.lowered && adaptive_quality.fast_seconds > RAISE_AFTER_SECONDS {
+        info!(target: "mazonic::effects", "Frame time {frame_time_ms:.1}ms, restoring effects quality");
+        adaptive_quality.lowered = false;
+        game_settings.effects_quality = EffectsQuality::Full;
+        RAISED_MESSAGE
+    } else {
+        return;
+    };
+
+    adaptive_quality.slow_seconds = 0.0;
+    adaptive_quality.fast_seconds = 0.0;
+
+    if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+        message_popup.0 = message.to_string();
+    }
+}
@@ -1,4 +1,79 @@
+use bevy::{prelude::*, render::{renderer::RenderDevice, settings::WgpuLimits}};
+
+use crate::game_settings::GameSettings;
+
+pub mod adaptive_quality;
 pub mod musical_note_burst;
 pub mod musical_notes;
 pub mod node_arrival;
 pub mod player_particles;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EffectsQuality {
+    #[default]
+    Full,
+    Off,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct ParticlesAvailable(pub bool);
+
+impl ParticlesAvailable {
+    pub fn enabled(&self, game_settings: &GameSettings) -> bool {
+        self.0 && game_settings.effects_quality != EffectsQuality::Off
+    }
+}
+
+// Hanabi simulates particles in compute shaders with storage buffers, which downlevel backends such as WebGL2 lack.
+fn supports_particles(limits: &WgpuLimits) -> bool {
+    limits.max_compute_workgroups_per_dimension > 0 && limits.max_storage_buffers_per_shader_stage > 0
+}
+
+pub fn detect_particle_support(mut commands: Commands, render_device: Option<Res<RenderDevice>>) {
+    let available = render_device.map_or(false, |render_device| supports_particles(&render_device.limits()));
+
+    if !available {
+        warn!(target: "mazonic::effects", "Particle effects are unsupported on this device, disabling them");
+    }
+
+    commands.insert_resource(ParticlesAvailable(available));
+}
+
+pub fn particles_enabled(
+    game_settings: Res<GameSettings>,
+    particles_available: Option<Res<ParticlesAvailable>>,
+) -> bool {
+    particles_available.map_or(false, |particles_available| particles_available.enabled(&game_settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn desktop_limits_support_particles() {
+        assert!(supports_particles(&WgpuLimits::default()));
+    }
+
+    #[test]
+    fn webgl2_limits_fall_back_to_no_particles() {
+        assert!(!supports_particles(&WgpuLimits::downlevel_webgl2_defaults()));
+        assert!(!supports_particles(&WgpuLimits {
+            max_storage_buffers_per_shader_stage: 0,
+            ..WgpuLimits::default()
+        }));
+    }
+
+    #[test]
+    fn missing_render_device_disables_particles() {
+        let mut world = World::new();
+        world.insert_resource(GameSettings::default());
+
+        world.run_system_once(detect_particle_support).unwrap();
+
+        assert!(!world.resource::<ParticlesAvailable>().0);
+        assert!(!world.run_system_once(particles_enabled).unwrap());
+    }
+}
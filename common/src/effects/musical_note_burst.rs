@@ -5,7 +5,7 @@ use crate::{
     game_save::CurrentPuzzle, game_settings::GameSettings, levels::PuzzleEntityMarker, play_statistics::PlayStatistics, room::Room, shape::loader::GraphComponent
 };
 
-use super::musical_notes::MusicalNoteImageHandles;
+use super::{musical_notes::MusicalNoteImageHandles, ParticlesAvailable};
 
 #[derive(Component, Debug, Clone)]
 pub struct NoteBurstEffectHandles {
@@ -108,9 +108,14 @@ pub fn spawn(
     keys: Res<ButtonInput<KeyCode>>,
     musical_note_image_handle_query: Query<&MusicalNoteImageHandles>,
     note_burst_effect_handles_query: Query<&NoteBurstEffectHandles>,
+    particles_available: Res<ParticlesAvailable>,
     mut commands: Commands,
     time: Res<Time>,
 ) {
+    if !particles_available.enabled(&game_settings) {
+        return;
+    }
+
     let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle.get_single() else {
         return;
     };
@@ -0,0 +1,79 @@
+use std::fmt;
+
+use crate::{custom_maze::CustomMazeError, game_save::{PuzzleIdentifier, SaveImportError}};
+
+pub use crate::load_level_asset::DailyLevelLoadError;
+
+// Failures that stop a level from being generated, loaded or saved. Each one carries a short
+// `message` suitable for the message popup, so callers can surface it instead of panicking.
+#[derive(Debug)]
+pub enum MazonicError {
+    // A hand authored or generated maze graph that cannot become a level.
+    Generation(CustomMazeError),
+    // Level data with no solution path, so there is no start or goal room.
+    EmptySolution,
+    // Level data with an edge between faces that share no border on its shape.
+    UnknownBorder { from: u64, to: u64 },
+    // Level data with a room on a face its shape does not have.
+    InvalidFace { room: u64 },
+    // A puzzle was routed to a loader that cannot provide it.
+    WrongLevelSource(PuzzleIdentifier),
+    RemoteLevel(DailyLevelLoadError),
+    MissingAsset(String),
+    Save(SaveImportError),
+}
+
+impl MazonicError {
+    pub fn message(&self) -> &str {
+        match self {
+            MazonicError::Generation(_) => "could not build maze",
+            MazonicError::EmptySolution
+            | MazonicError::UnknownBorder { .. }
+            | MazonicError::InvalidFace { .. } => "level data is invalid",
+            MazonicError::WrongLevelSource(_) | MazonicError::MissingAsset(_) => "could not load level",
+            MazonicError::RemoteLevel(DailyLevelLoadError::JsonParseError(_)) => "failed to parse json",
+            MazonicError::RemoteLevel(DailyLevelLoadError::HttpError(_)) => "could not fetch level from web",
+            MazonicError::RemoteLevel(DailyLevelLoadError::StringParseError(_)) => "failed to parse level data",
+            MazonicError::Save(save_import_error) => save_import_error.message(),
+        }
+    }
+}
+
+impl fmt::Display for MazonicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MazonicError::Generation(err) => write!(f, "maze generation failed: {err}"),
+            MazonicError::EmptySolution => write!(f, "level has an empty solution"),
+            MazonicError::UnknownBorder { from, to } => {
+                write!(f, "edge {from} -> {to} joins faces that do not share a border")
+            }
+            MazonicError::InvalidFace { room } => write!(f, "room {room} lies on a face the shape does not have"),
+            MazonicError::WrongLevelSource(puzzle_identifier) => {
+                write!(f, "{puzzle_identifier:?} cannot be loaded from this source")
+            }
+            MazonicError::RemoteLevel(err) => write!(f, "remote level failed to load: {err:?}"),
+            MazonicError::MissingAsset(path) => write!(f, "asset {path} failed to load"),
+            MazonicError::Save(err) => write!(f, "save import failed: {err:?}"),
+        }
+    }
+}
+
+impl std::error::Error for MazonicError {}
+
+impl From<CustomMazeError> for MazonicError {
+    fn from(err: CustomMazeError) -> Self {
+        MazonicError::Generation(err)
+    }
+}
+
+impl From<DailyLevelLoadError> for MazonicError {
+    fn from(err: DailyLevelLoadError) -> Self {
+        MazonicError::RemoteLevel(err)
+    }
+}
+
+impl From<SaveImportError> for MazonicError {
+    fn from(err: SaveImportError) -> Self {
+        MazonicError::Save(err)
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::game_save::PuzzleIdentifier;
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Favorites(pub HashSet<PuzzleIdentifier>);
+
+impl Favorites {
+    pub fn contains(&self, puzzle_identifier: &PuzzleIdentifier) -> bool {
+        self.0.contains(&favorite_key(puzzle_identifier))
+    }
+
+    pub fn toggle(&mut self, puzzle_identifier: &PuzzleIdentifier) {
+        let key = favorite_key(puzzle_identifier);
+
+        if !self.0.remove(&key) {
+            self.0.insert(key);
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct FavoritesFilter(pub bool);
+
+impl FavoritesFilter {
+    pub fn hides(&self, favorites: &Favorites, puzzle_identifier: &PuzzleIdentifier) -> bool {
+        self.0 && !favorites.contains(puzzle_identifier)
+    }
+}
+
+// Daily puzzles change every day, so the tier is favorited rather than a specific date.
+fn favorite_key(puzzle_identifier: &PuzzleIdentifier) -> PuzzleIdentifier {
+    match puzzle_identifier {
+        PuzzleIdentifier::Level(level_index) => PuzzleIdentifier::Level(*level_index),
+        PuzzleIdentifier::EasyDaily(_) => PuzzleIdentifier::EasyDaily(String::new()),
+        PuzzleIdentifier::HardDaily(_) => PuzzleIdentifier::HardDaily(String::new()),
+    }
+}
+
+pub fn setup(mut commands: Commands) {
+    commands.init_resource::<FavoritesFilter>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_twice_unfavorites_a_level() {
+        let mut favorites = Favorites::default();
+        let level = PuzzleIdentifier::Level(3);
+
+        favorites.toggle(&level);
+        assert!(favorites.contains(&level));
+
+        favorites.toggle(&level);
+        assert!(!favorites.contains(&level));
+    }
+
+    #[test]
+    fn dailies_of_one_tier_share_a_single_favorite() {
+        let mut favorites = Favorites::default();
+        let today = PuzzleIdentifier::EasyDaily("2025-03-01".to_string());
+        let tomorrow = PuzzleIdentifier::EasyDaily("2025-03-02".to_string());
+
+        favorites.toggle(&today);
+
+        assert!(favorites.contains(&tomorrow));
+        assert!(!favorites.contains(&PuzzleIdentifier::HardDaily("2025-03-01".to_string())));
+        assert_eq!(favorites.0.len(), 1);
+
+        favorites.toggle(&tomorrow);
+        assert!(!favorites.contains(&today));
+    }
+}
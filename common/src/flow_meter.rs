@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_save::CurrentPuzzle, game_settings::GameSettings, play_statistics::PlayStatistics,
+    player_path::PlayerPath, room::Room, shape::loader::SolutionComponent,
+};
+
+const QUICK_MOVE_SECONDS: f32 = 0.8;
+const FILL_PER_MOVE: f32 = 0.08;
+const BACKTRACK_PENALTY: f32 = 0.25;
+const DECAY_PER_SECOND: f32 = 0.3;
+
+#[derive(Resource, Default, Debug)]
+pub struct FlowMeter {
+    pub value: f32,
+    pub peak: f32,
+    last_move_seconds: Option<f32>,
+    last_path_length: usize,
+}
+
+pub fn flow_meter_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.flow_meter
+}
+
+pub fn setup(mut commands: Commands) {
+    commands.insert_resource(FlowMeter::default());
+}
+
+pub fn reset(mut flow_meter: ResMut<FlowMeter>) {
+    *flow_meter = FlowMeter::default();
+}
+
+pub fn update(
+    time: Res<Time>,
+    mut flow_meter: ResMut<FlowMeter>,
+    player_path_query: Query<&PlayerPath>,
+    solution_query: Query<&SolutionComponent>,
+) {
+    let Ok(PlayerPath(path)) = player_path_query.get_single() else {
+        return;
+    };
+
+    let Ok(SolutionComponent(solution)) = solution_query.get_single() else {
+        return;
+    };
+
+    let current_seconds = time.elapsed_secs();
+    let seconds_since_last_move = flow_meter
+        .last_move_seconds
+        .map(|last_move_seconds| current_seconds - last_move_seconds);
+
+    // Undo shortens the path; without following it down, moves would be ignored until the path
+    // grew past its old length again.
+    if path.len() < flow_meter.last_path_length {
+        flow_meter.last_path_length = path.len();
+    }
+
+    if path.len() > flow_meter.last_path_length {
+        let moved_forward = match path.as_slice() {
+            [.., previous_room, current_room] => is_forward_move(previous_room, current_room, solution),
+            _ => false,
+        };
+
+        let is_quick = seconds_since_last_move.is_some_and(|seconds| seconds < QUICK_MOVE_SECONDS);
+
+        flow_meter.value = match (moved_forward, is_quick) {
+            (true, true) => (flow_meter.value + FILL_PER_MOVE).min(1.0),
+            (true, false) => flow_meter.value,
+            (false, _) => (flow_meter.value - BACKTRACK_PENALTY).max(0.0),
+        };
+        flow_meter.peak = flow_meter.peak.max(flow_meter.value);
+        flow_meter.last_move_seconds = Some(current_seconds);
+        flow_meter.last_path_length = path.len();
+    } else if flow_meter.value > 0.0
+        && seconds_since_last_move.is_some_and(|seconds| seconds > QUICK_MOVE_SECONDS)
+    {
+        flow_meter.value = (flow_meter.value - DECAY_PER_SECOND * time.delta_secs()).max(0.0);
+    }
+}
+
+fn is_forward_move(previous_room: &Room, current_room: &Room, solution: &Vec<Room>) -> bool {
+    let solution_index = |room: &Room| solution.iter().position(|solution_room| solution_room == room);
+
+    match (solution_index(previous_room), solution_index(current_room)) {
+        (Some(previous_index), Some(current_index)) => current_index == previous_index + 1,
+        _ => false,
+    }
+}
+
+pub fn on_victory(
+    flow_meter: Res<FlowMeter>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    mut play_statistics: ResMut<PlayStatistics>,
+) {
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let peak = flow_meter.peak;
+
+    play_statistics.0.entry(puzzle_identifier.clone()).and_modify(|puzzle_statistics| {
+        let best_peak = puzzle_statistics.peak_flow.map_or(peak, |previous| previous.max(peak));
+        puzzle_statistics.peak_flow = Some(best_peak);
+    });
+}
@@ -0,0 +1,51 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
+
+use crate::game_settings::GameSettings;
+
+pub fn apply_present_mode(
+    game_settings: Res<GameSettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !game_settings.is_changed() {
+        return;
+    }
+
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+
+    let present_mode = if game_settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+
+    if window.present_mode != present_mode {
+        window.present_mode = present_mode;
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn limit_frame_rate(game_settings: Res<GameSettings>, mut last_frame_end: Local<Option<Instant>>) {
+    let Some(max_frame_rate) = game_settings.max_frame_rate.filter(|rate| *rate > 0.0) else {
+        *last_frame_end = None;
+        return;
+    };
+
+    let min_frame_duration = Duration::from_secs_f32(1.0 / max_frame_rate);
+
+    if let Some(remaining) = last_frame_end
+        .map(|frame_end| frame_end.elapsed())
+        .and_then(|elapsed| min_frame_duration.checked_sub(elapsed))
+    {
+        std::thread::sleep(remaining);
+    }
+
+    *last_frame_end = Some(Instant::now());
+}
@@ -6,8 +6,19 @@ use bevy_pkv::PkvStore;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
+use crate::controller::SolveGesture;
+use crate::daily_streak::DailyStreak;
+use crate::favorites::Favorites;
+use crate::game_settings::GameSettings;
 use crate::play_statistics::{PlayStatistics, PuzzleStatistics};
-use crate::sound::Melody;
+use crate::player::PlayerMazeState;
+use crate::player_path::PlayerPath;
+use crate::room::Room;
+use crate::shape::loader::GraphComponent;
+use crate::undo::PlayerMoveHistory;
+use crate::sound::{AudioSettings, Melody};
+use crate::ui::message::{MessagePopup, MessagePopupUpperMarker};
+use crate::whats_new::{LastSeenVersion, CURRENT_VERSION};
 
 pub type LevelIndex = usize;
 pub type DailyLevelId = String;
@@ -31,18 +42,69 @@ pub struct DiscoveredMelody {
     pub room_ids: Vec<u64>,
 }
 
+// Where the player had got to in an unfinished puzzle, so it can be picked up on the next launch.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PuzzleProgress {
+    pub puzzle: PuzzleIdentifier,
+    pub player_maze_state: PlayerMazeState,
+    pub path: Vec<Room>,
+}
+
+impl PuzzleProgress {
+    // A daily can only be resumed on its own day; by the next one the remote level has moved on.
+    pub fn is_stale(&self, today: NaiveDate) -> bool {
+        match &self.puzzle {
+            PuzzleIdentifier::Level(_) => false,
+            PuzzleIdentifier::EasyDaily(daily_level_id) | PuzzleIdentifier::HardDaily(daily_level_id) => {
+                NaiveDate::parse_from_str(daily_level_id, "%Y-%m-%d").map_or(true, |date| date != today)
+            }
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ResumablePuzzle(pub Option<PuzzleProgress>);
+
+// Progress chosen to be resumed, applied once its puzzle has loaded.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct PendingResume(pub Option<PuzzleProgress>);
+
+// Bumped whenever a save can no longer be read by older builds; saves without it predate versioning.
+pub const SAVE_VERSION: u32 = 1;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GameSave {
+    #[serde(default)]
+    pub version: u32,
     pub current_index: PuzzleIdentifier,
     pub play_statistics: HashMap<PuzzleIdentifier, PuzzleStatistics>,
+    #[serde(default)]
+    pub favorites: HashSet<PuzzleIdentifier>,
+    #[serde(default)]
+    pub last_seen_version: Option<String>,
+    #[serde(default)]
+    pub solve_gesture: Option<SolveGesture>,
+    #[serde(default)]
+    pub in_progress: Option<PuzzleProgress>,
+    #[serde(default)]
+    pub daily_streak: DailyStreak,
+    #[serde(default)]
+    pub audio_settings: AudioSettings,
 }
 
 impl GameSave {
     pub fn completed(highest_level: usize) -> Self {
         let play_statistics = (0..highest_level).map(|level_index| (PuzzleIdentifier::Level(level_index), PuzzleStatistics::completed())).collect();
         Self {
+            version: SAVE_VERSION,
             current_index: PuzzleIdentifier::Level(0),
             play_statistics,
+            favorites: HashSet::new(),
+            last_seen_version: None,
+            solve_gesture: None,
+            in_progress: None,
+            daily_streak: DailyStreak::default(),
+            audio_settings: AudioSettings::default(),
         }
     }
 }
@@ -50,36 +112,86 @@ impl GameSave {
 #[derive(Resource, Clone)]
 pub struct SaveLocation(pub PathBuf);
 
+const SAVE_ORGANIZATION: &str = "hallayus";
+const SAVE_APPLICATION: &str = "mazonic";
+
+// The directory the save lives in, for files written alongside it: the entry point's SaveLocation,
+// otherwise the platform data directory bevy_pkv picks for the same organization and application.
+pub fn save_directory(save_location: Option<&SaveLocation>) -> PathBuf {
+    if let Some(SaveLocation(path)) = save_location {
+        return path.clone();
+    }
+
+    #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+    if let Some(project_dirs) = directories::ProjectDirs::from("", SAVE_ORGANIZATION, SAVE_APPLICATION) {
+        return project_dirs.data_dir().to_path_buf();
+    }
+
+    PathBuf::from(".")
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ChosenSolveGesture(pub Option<SolveGesture>);
+
 impl Default for GameSave {
     fn default() -> Self {
         GameSave {
+            version: SAVE_VERSION,
             current_index: PuzzleIdentifier::Level(0),
             play_statistics: HashMap::new(),
+            favorites: HashSet::new(),
+            last_seen_version: None,
+            solve_gesture: None,
+            in_progress: None,
+            daily_streak: DailyStreak::default(),
+            audio_settings: AudioSettings::default(),
         }
     }
 }
 
 const SAVE_DATA_KEY: &str = "save_data";
 
-pub fn setup(mut commands: Commands, save_location: Option<Res<SaveLocation>>) {
+pub fn setup(
+    mut commands: Commands,
+    save_location: Option<Res<SaveLocation>>,
+    mut game_settings: ResMut<GameSettings>,
+) {
     let pkv_store = match save_location {
-        None => PkvStore::new("hallayus", "mazonic"),
+        None => PkvStore::new(SAVE_ORGANIZATION, SAVE_APPLICATION),
         Some(save_location) => PkvStore::new_in_dir(save_location.0.clone()),
     };
 
-    let save_data = match pkv_store.get::<GameSave>(SAVE_DATA_KEY) {
-        Ok(game_save) => GameSave::completed(5),
-        Err(_) => GameSave::default(),
+    let (save_data, first_launch) = match pkv_store.get::<GameSave>(SAVE_DATA_KEY) {
+        Ok(game_save) => (game_save, false),
+        Err(_) => (GameSave::default(), true),
     };
 
+    // The tutorial takes precedence on first launch, so the changelog starts out as seen.
+    let last_seen_version = if first_launch {
+        Some(CURRENT_VERSION.to_string())
+    } else {
+        save_data.last_seen_version
+    };
+
+    // A fresh install keeps the platform default control scheme until the player picks one.
+    if let Some(solve_gesture) = save_data.solve_gesture {
+        game_settings.solve_gesture = solve_gesture;
+    }
+
     let play_statistics = PlayStatistics(save_data.play_statistics);
 
     commands.spawn((
         CurrentPuzzle(save_data.current_index),
-        WorkingLevelIndex(play_statistics.get_working_level()),
+        WorkingLevelIndex(play_statistics.get_working_level(&game_settings.campaign_order)),
     ));
 
     commands.insert_resource(play_statistics);
+    commands.insert_resource(Favorites(save_data.favorites));
+    commands.insert_resource(LastSeenVersion(last_seen_version));
+    commands.insert_resource(ChosenSolveGesture(save_data.solve_gesture));
+    commands.insert_resource(ResumablePuzzle(save_data.in_progress));
+    commands.insert_resource(save_data.daily_streak);
+    commands.insert_resource(save_data.audio_settings);
     commands.insert_resource(pkv_store);
 }
 
@@ -87,32 +199,603 @@ pub fn update(
     current_level_index_query: Query<Ref<CurrentPuzzle>>,
     working_level_index_query: Query<Ref<WorkingLevelIndex>>,
     play_statistics: Res<PlayStatistics>,
+    favorites: Res<Favorites>,
+    last_seen_version: Res<LastSeenVersion>,
+    resumable_puzzle: Res<ResumablePuzzle>,
+    daily_streak: Res<DailyStreak>,
+    audio_settings: Res<AudioSettings>,
+    game_settings: Res<GameSettings>,
+    mut chosen_solve_gesture: ResMut<ChosenSolveGesture>,
+    mut previous_solve_gesture: Local<Option<SolveGesture>>,
     mut pkv_store: ResMut<PkvStore>,
 ) {
     let current_level_index = current_level_index_query.single();
-    
+
+    let solve_gesture = game_settings.solve_gesture;
+    if previous_solve_gesture
+        .replace(solve_gesture)
+        .is_some_and(|previous| previous != solve_gesture)
+    {
+        chosen_solve_gesture.0 = Some(solve_gesture);
+    }
 
     if current_level_index.is_changed()
         || play_statistics.is_changed()
+        || favorites.is_changed()
+        || last_seen_version.is_changed()
+        || chosen_solve_gesture.is_changed()
+        || resumable_puzzle.is_changed()
+        || daily_streak.is_changed()
+        || audio_settings.is_changed()
     {
-        println!("Saving Game");
+        info!(target: "mazonic::save", "Saving Game");
 
         let game_save = GameSave {
+            version: SAVE_VERSION,
             current_index: current_level_index.0.clone(),
             play_statistics: play_statistics.0.clone(),
+            favorites: favorites.0.clone(),
+            last_seen_version: last_seen_version.0.clone(),
+            solve_gesture: chosen_solve_gesture.0,
+            in_progress: resumable_puzzle.0.clone(),
+            daily_streak: daily_streak.clone(),
+            audio_settings: audio_settings.clone(),
         };
 
         pkv_store.set(SAVE_DATA_KEY, &game_save);
     }
 }
 
+// Kept up to date on every node arrival rather than on exit, since mobile apps are often killed
+// without an exit event. The save system persists it whenever it changes.
+pub fn record_progress(
+    player_query: Query<(&PlayerMazeState, &PlayerPath), Changed<PlayerMazeState>>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    mut resumable_puzzle: ResMut<ResumablePuzzle>,
+) {
+    let Ok((player_maze_state @ PlayerMazeState::Node(_), PlayerPath(path))) = player_query.get_single() else {
+        return;
+    };
+
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    resumable_puzzle.0 = Some(PuzzleProgress {
+        puzzle: puzzle_identifier.clone(),
+        player_maze_state: player_maze_state.clone(),
+        path: path.clone(),
+    });
+}
+
+pub fn clear_progress(mut resumable_puzzle: ResMut<ResumablePuzzle>) {
+    resumable_puzzle.0 = None;
+}
+
+pub fn apply_resume(
+    mut pending_resume: ResMut<PendingResume>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    graph_query: Query<&GraphComponent>,
+    mut player_query: Query<(&mut PlayerMazeState, &mut PlayerPath)>,
+    mut move_history: ResMut<PlayerMoveHistory>,
+) {
+    let Some(progress) = pending_resume.0.take() else {
+        return;
+    };
+
+    let (Ok(CurrentPuzzle(puzzle_identifier)), Ok(GraphComponent(graph))) =
+        (current_puzzle_query.get_single(), graph_query.get_single())
+    else {
+        return;
+    };
+
+    let PlayerMazeState::Node(room) = progress.player_maze_state else {
+        return;
+    };
+
+    // A level file that changed since the save could no longer hold the saved rooms.
+    if *puzzle_identifier != progress.puzzle
+        || !graph.contains_node(room)
+        || !progress.path.iter().all(|path_room| graph.contains_node(*path_room))
+    {
+        warn!(target: "mazonic::save", "Saved progress no longer matches {:?}; starting afresh", puzzle_identifier);
+        return;
+    }
+
+    let Ok((mut player_maze_state, mut player_path)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    info!(target: "mazonic::save", "Resuming {:?} at room {}", puzzle_identifier, room.id);
+    *player_maze_state = PlayerMazeState::Node(room);
+    player_path.0 = progress.path.clone();
+    move_history.0 = progress.path;
+}
+
 pub fn update_working_level(
     mut working_level_index_query: Query<&mut WorkingLevelIndex>,
     play_statistics: Res<PlayStatistics>,
+    game_settings: Res<GameSettings>,
 ) {
-    if play_statistics.is_changed() {
-        let level_index = play_statistics.get_working_level();
+    if play_statistics.is_changed() || game_settings.is_changed() {
+        let level_index = play_statistics.get_working_level(&game_settings.campaign_order);
 
         working_level_index_query.single_mut().0 = level_index;
     }
 }
+
+#[derive(Debug)]
+pub enum SaveImportError {
+    InvalidFormat(serde_json::Error),
+    IncompatibleVersion(u32),
+}
+
+impl SaveImportError {
+    pub fn message(&self) -> &str {
+        match self {
+            SaveImportError::InvalidFormat(_) => "save file could not be read",
+            SaveImportError::IncompatibleVersion(_) => "save file is from a newer version",
+        }
+    }
+}
+
+const IMPORT_KEY: KeyCode = KeyCode::KeyI;
+const IMPORT_FILE_NAME: &str = "import_save.json";
+
+#[derive(Event, Debug, Clone)]
+pub struct ImportSave(pub String);
+
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolveSaveImport {
+    Merge,
+    Overwrite,
+    Cancel,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct PendingSaveImport(pub Option<GameSave>);
+
+pub fn parse_imported_save(json: &str) -> Result<GameSave, SaveImportError> {
+    let game_save = serde_json::from_str::<GameSave>(json).map_err(SaveImportError::InvalidFormat)?;
+
+    if game_save.version > SAVE_VERSION {
+        return Err(SaveImportError::IncompatibleVersion(game_save.version));
+    }
+
+    Ok(game_save)
+}
+
+// Keeps the better record for every puzzle; settings-like fields stay with the current save.
+pub fn merge_saves(current: GameSave, imported: GameSave) -> GameSave {
+    let mut play_statistics = current.play_statistics;
+
+    for (puzzle_identifier, imported_statistics) in imported.play_statistics {
+        let merged_statistics = match play_statistics.remove(&puzzle_identifier) {
+            Some(current_statistics) => current_statistics.merge(imported_statistics),
+            None => imported_statistics,
+        };
+        play_statistics.insert(puzzle_identifier, merged_statistics);
+    }
+
+    GameSave {
+        version: SAVE_VERSION,
+        current_index: current.current_index,
+        play_statistics,
+        favorites: current.favorites.union(&imported.favorites).cloned().collect(),
+        last_seen_version: current.last_seen_version,
+        solve_gesture: current.solve_gesture.or(imported.solve_gesture),
+        in_progress: current.in_progress,
+        daily_streak: current.daily_streak.merge(imported.daily_streak),
+        audio_settings: current.audio_settings,
+    }
+}
+
+pub fn import_requested(keys: Res<ButtonInput<KeyCode>>) -> bool {
+    let control = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    control && shift && keys.just_pressed(IMPORT_KEY)
+}
+
+// Imports are picked up from a file dropped next to the save, which receive_import then validates.
+pub fn read_import_file(
+    save_location: Option<Res<SaveLocation>>,
+    mut import_save_events: EventWriter<ImportSave>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+) {
+    let path = save_directory(save_location.as_deref()).join(IMPORT_FILE_NAME);
+
+    match std::fs::read_to_string(&path) {
+        Ok(json) => {
+            info!(target: "mazonic::save", "Read save to import from {:?}", path);
+            import_save_events.send(ImportSave(json));
+        }
+        Err(err) => {
+            warn!(target: "mazonic::save", "Could not read save to import from {:?}: {}", path, err);
+            if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+                message_popup.0 = format!("no {} to import", IMPORT_FILE_NAME);
+            }
+        }
+    }
+}
+
+pub fn receive_import(
+    mut import_save_events: EventReader<ImportSave>,
+    mut pending_save_import: ResMut<PendingSaveImport>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+) {
+    let Some(ImportSave(json)) = import_save_events.read().last() else {
+        return;
+    };
+
+    match parse_imported_save(json) {
+        Ok(game_save) => pending_save_import.0 = Some(game_save),
+        Err(err) => {
+            warn!(target: "mazonic::save", "Rejected imported save: {:?}", err);
+            if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+                message_popup.0 = err.message().to_string();
+            }
+        }
+    }
+}
+
+pub fn resolve_import(
+    mut resolve_save_import_events: EventReader<ResolveSaveImport>,
+    mut pending_save_import: ResMut<PendingSaveImport>,
+    mut current_puzzle_query: Query<&mut CurrentPuzzle>,
+    mut play_statistics: ResMut<PlayStatistics>,
+    mut favorites: ResMut<Favorites>,
+    mut last_seen_version: ResMut<LastSeenVersion>,
+    mut chosen_solve_gesture: ResMut<ChosenSolveGesture>,
+    mut game_settings: ResMut<GameSettings>,
+    mut resumable_puzzle: ResMut<ResumablePuzzle>,
+    mut daily_streak: ResMut<DailyStreak>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    let Some(resolution) = resolve_save_import_events.read().last().copied() else {
+        return;
+    };
+
+    let Some(imported) = pending_save_import.0.take() else {
+        return;
+    };
+
+    let Ok(mut current_puzzle) = current_puzzle_query.get_single_mut() else {
+        return;
+    };
+
+    let current = GameSave {
+        version: SAVE_VERSION,
+        current_index: current_puzzle.0.clone(),
+        play_statistics: play_statistics.0.clone(),
+        favorites: favorites.0.clone(),
+        last_seen_version: last_seen_version.0.clone(),
+        solve_gesture: chosen_solve_gesture.0,
+        in_progress: resumable_puzzle.0.clone(),
+        daily_streak: daily_streak.clone(),
+        audio_settings: audio_settings.clone(),
+    };
+
+    let game_save = match resolution {
+        ResolveSaveImport::Merge => merge_saves(current, imported),
+        ResolveSaveImport::Overwrite => imported,
+        ResolveSaveImport::Cancel => return,
+    };
+
+    info!(target: "mazonic::save", "Importing save with {:?}", resolution);
+
+    // The regular save system persists these once it sees them change.
+    current_puzzle.0 = game_save.current_index;
+    play_statistics.0 = game_save.play_statistics;
+    favorites.0 = game_save.favorites;
+    last_seen_version.0 = game_save.last_seen_version;
+    chosen_solve_gesture.0 = game_save.solve_gesture;
+    resumable_puzzle.0 = game_save.in_progress;
+    *daily_streak = game_save.daily_streak;
+    *audio_settings = game_save.audio_settings;
+
+    // As on launch, a save without a chosen gesture keeps the one in use.
+    if let Some(solve_gesture) = game_save.solve_gesture {
+        game_settings.solve_gesture = solve_gesture;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use petgraph::graphmap::GraphMap;
+
+    use crate::room::{test_support::room, Edge};
+
+    use super::*;
+
+    // Writes the save to its own directory and runs setup against it, as a relaunch would.
+    fn launch_with(test_name: &str, game_save: &GameSave) -> World {
+        let directory = std::env::temp_dir().join(format!("mazonic-{}-{}", test_name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        PkvStore::new_in_dir(directory.clone())
+            .set(SAVE_DATA_KEY, game_save)
+            .unwrap();
+
+        let mut world = World::new();
+        world.insert_resource(SaveLocation(directory));
+        world.init_resource::<GameSettings>();
+        world.run_system_once(setup).unwrap();
+        world
+    }
+
+    #[test]
+    fn relaunch_keeps_the_saved_favorites() {
+        let game_save = GameSave {
+            favorites: HashSet::from([PuzzleIdentifier::Level(3), PuzzleIdentifier::Level(7)]),
+            ..GameSave::default()
+        };
+
+        let world = launch_with("favorites", &game_save);
+
+        assert_eq!(world.resource::<Favorites>().0, game_save.favorites);
+    }
+
+    #[test]
+    fn relaunch_keeps_the_last_seen_version() {
+        let game_save = GameSave {
+            last_seen_version: Some("0.0.1".to_string()),
+            ..GameSave::default()
+        };
+
+        let world = launch_with("last-seen-version", &game_save);
+
+        assert_eq!(world.resource::<LastSeenVersion>().0.as_deref(), Some("0.0.1"));
+    }
+
+    #[test]
+    fn relaunch_restores_the_chosen_solve_gesture() {
+        let chosen = match SolveGesture::platform_default() {
+            SolveGesture::Incremental => SolveGesture::ContinuousPath,
+            SolveGesture::ContinuousPath => SolveGesture::Incremental,
+        };
+        let game_save = GameSave {
+            solve_gesture: Some(chosen),
+            ..GameSave::default()
+        };
+
+        let world = launch_with("solve-gesture", &game_save);
+
+        assert_eq!(world.resource::<GameSettings>().solve_gesture, chosen);
+        assert_eq!(world.resource::<ChosenSolveGesture>().0, Some(chosen));
+    }
+
+    #[test]
+    fn best_scores_round_trip_through_the_save() {
+        let statistics = PuzzleStatistics {
+            completed: true,
+            best_score: Some(12),
+            best_stars: Some(2),
+            ..PuzzleStatistics::default()
+        };
+        let game_save = GameSave {
+            play_statistics: HashMap::from([(PuzzleIdentifier::Level(4), statistics)]),
+            ..GameSave::default()
+        };
+
+        let world = launch_with("best-scores", &game_save);
+
+        let loaded = &world.resource::<PlayStatistics>().0[&PuzzleIdentifier::Level(4)];
+        assert_eq!(loaded.best_score, Some(12));
+        assert_eq!(loaded.best_stars, Some(2));
+    }
+
+    #[test]
+    fn relaunch_keeps_the_progress_to_resume() {
+        let game_save = GameSave {
+            in_progress: Some(progress(PuzzleIdentifier::Level(2), 1, &[0, 1])),
+            ..GameSave::default()
+        };
+
+        let world = launch_with("in-progress", &game_save);
+
+        let resumable = world.resource::<ResumablePuzzle>().0.as_ref().unwrap();
+        assert_eq!(resumable.puzzle, PuzzleIdentifier::Level(2));
+        assert_eq!(resumable.path.iter().map(|room| room.id).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 14).unwrap()
+    }
+
+    #[test]
+    fn campaign_progress_never_goes_stale() {
+        assert!(!progress(PuzzleIdentifier::Level(0), 0, &[0]).is_stale(today()));
+    }
+
+    #[test]
+    fn daily_progress_is_stale_after_its_day() {
+        let easy = progress(PuzzleIdentifier::EasyDaily("2026-03-14".to_string()), 0, &[0]);
+        let hard = progress(PuzzleIdentifier::HardDaily("2026-03-13".to_string()), 0, &[0]);
+        let unreadable = progress(PuzzleIdentifier::EasyDaily("yesterday".to_string()), 0, &[0]);
+
+        assert!(!easy.is_stale(today()));
+        assert!(hard.is_stale(today()));
+        assert!(unreadable.is_stale(today()));
+    }
+
+    fn progress(puzzle: PuzzleIdentifier, at: u64, path: &[u64]) -> PuzzleProgress {
+        PuzzleProgress {
+            puzzle,
+            player_maze_state: PlayerMazeState::Node(room(at)),
+            path: path.iter().map(|id| room(*id)).collect(),
+        }
+    }
+
+    // A three room corridor loaded as level 2, with the player at its start.
+    fn resume_world(pending: PuzzleProgress) -> World {
+        let mut graph = GraphMap::new();
+        graph.add_edge(room(0), room(1), Edge);
+        graph.add_edge(room(1), room(2), Edge);
+
+        let mut world = World::new();
+        world.insert_resource(PendingResume(Some(pending)));
+        world.init_resource::<PlayerMoveHistory>();
+        world.spawn(CurrentPuzzle(PuzzleIdentifier::Level(2)));
+        world.spawn(GraphComponent(graph));
+        world.spawn((PlayerMazeState::Node(room(0)), PlayerPath(vec![room(0)])));
+        world
+    }
+
+    fn player_room_and_path(world: &mut World) -> (u64, Vec<u64>) {
+        let (player_maze_state, PlayerPath(path)) = world
+            .query::<(&PlayerMazeState, &PlayerPath)>()
+            .single(world);
+        let PlayerMazeState::Node(room) = player_maze_state else {
+            panic!("expected the player on a node");
+        };
+        (room.id, path.iter().map(|room| room.id).collect())
+    }
+
+    #[test]
+    fn resume_moves_the_player_to_the_saved_room() {
+        let mut world = resume_world(progress(PuzzleIdentifier::Level(2), 2, &[0, 1, 2]));
+
+        world.run_system_once(apply_resume).unwrap();
+
+        assert_eq!(player_room_and_path(&mut world), (2, vec![0, 1, 2]));
+        assert_eq!(world.resource::<PlayerMoveHistory>().0.len(), 3);
+        assert!(world.resource::<PendingResume>().0.is_none());
+    }
+
+    #[test]
+    fn resume_ignores_progress_from_another_puzzle() {
+        let mut world = resume_world(progress(PuzzleIdentifier::Level(3), 2, &[0, 1, 2]));
+
+        world.run_system_once(apply_resume).unwrap();
+
+        assert_eq!(player_room_and_path(&mut world), (0, vec![0]));
+        assert!(world.resource::<PendingResume>().0.is_none());
+    }
+
+    #[test]
+    fn resume_ignores_rooms_the_level_no_longer_has() {
+        let mut world = resume_world(progress(PuzzleIdentifier::Level(2), 9, &[0, 1, 9]));
+
+        world.run_system_once(apply_resume).unwrap();
+
+        assert_eq!(player_room_and_path(&mut world), (0, vec![0]));
+        assert!(world.resource::<PlayerMoveHistory>().0.is_empty());
+    }
+
+    #[test]
+    fn merging_saves_combines_statistics_and_keeps_local_preferences() {
+        let current = GameSave {
+            current_index: PuzzleIdentifier::Level(1),
+            play_statistics: HashMap::from([
+                (PuzzleIdentifier::Level(0), PuzzleStatistics { best_score: Some(8), ..PuzzleStatistics::completed() }),
+                (PuzzleIdentifier::Level(1), PuzzleStatistics::default()),
+            ]),
+            favorites: HashSet::from([PuzzleIdentifier::Level(0)]),
+            last_seen_version: Some("1.0.0".to_string()),
+            ..GameSave::default()
+        };
+        let imported = GameSave {
+            current_index: PuzzleIdentifier::Level(5),
+            play_statistics: HashMap::from([
+                (PuzzleIdentifier::Level(0), PuzzleStatistics { best_score: Some(5), ..PuzzleStatistics::completed() }),
+                (PuzzleIdentifier::Level(2), PuzzleStatistics::completed()),
+            ]),
+            favorites: HashSet::from([PuzzleIdentifier::Level(2)]),
+            last_seen_version: Some("0.9.0".to_string()),
+            ..GameSave::default()
+        };
+
+        let merged = merge_saves(current, imported);
+
+        assert_eq!(merged.current_index, PuzzleIdentifier::Level(1));
+        assert_eq!(merged.last_seen_version.as_deref(), Some("1.0.0"));
+        assert_eq!(merged.play_statistics.len(), 3);
+        assert_eq!(merged.play_statistics[&PuzzleIdentifier::Level(0)].best_score, Some(5));
+        assert!(!merged.play_statistics[&PuzzleIdentifier::Level(1)].completed);
+        assert!(merged.play_statistics[&PuzzleIdentifier::Level(2)].completed);
+        assert_eq!(
+            merged.favorites,
+            HashSet::from([PuzzleIdentifier::Level(0), PuzzleIdentifier::Level(2)])
+        );
+    }
+
+    #[test]
+    fn an_exported_save_parses_back_for_import() {
+        let game_save = GameSave {
+            version: SAVE_VERSION,
+            favorites: HashSet::from([PuzzleIdentifier::Level(2)]),
+            ..GameSave::default()
+        };
+
+        let parsed = parse_imported_save(&serde_json::to_string(&game_save).unwrap()).unwrap();
+
+        assert_eq!(parsed.favorites, game_save.favorites);
+    }
+
+    #[test]
+    fn a_save_from_a_newer_version_is_refused() {
+        let game_save = GameSave { version: SAVE_VERSION + 1, ..GameSave::default() };
+
+        assert!(matches!(
+            parse_imported_save(&serde_json::to_string(&game_save).unwrap()),
+            Err(SaveImportError::IncompatibleVersion(version)) if version == SAVE_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn a_file_that_is_not_a_save_is_refused() {
+        assert!(matches!(parse_imported_save("not a save"), Err(SaveImportError::InvalidFormat(_))));
+    }
+
+    #[test]
+    fn overwriting_applies_every_imported_field() {
+        let imported_gesture = match SolveGesture::platform_default() {
+            SolveGesture::Incremental => SolveGesture::ContinuousPath,
+            SolveGesture::ContinuousPath => SolveGesture::Incremental,
+        };
+        let imported = GameSave {
+            current_index: PuzzleIdentifier::Level(4),
+            play_statistics: HashMap::from([(PuzzleIdentifier::Level(2), PuzzleStatistics::completed())]),
+            favorites: HashSet::from([PuzzleIdentifier::Level(2)]),
+            last_seen_version: Some("0.9.0".to_string()),
+            solve_gesture: Some(imported_gesture),
+            in_progress: Some(progress(PuzzleIdentifier::Level(4), 1, &[0, 1])),
+            daily_streak: DailyStreak { days: 3, last_completed: Some(today()) },
+            audio_settings: AudioSettings { master_volume: 0.5, muted: true },
+            ..GameSave::default()
+        };
+
+        let mut world = World::new();
+        world.spawn(CurrentPuzzle(PuzzleIdentifier::Level(0)));
+        world.insert_resource(PlayStatistics(HashMap::new()));
+        world.insert_resource(Favorites(HashSet::new()));
+        world.insert_resource(LastSeenVersion(Some("1.0.0".to_string())));
+        world.init_resource::<ChosenSolveGesture>();
+        world.init_resource::<GameSettings>();
+        world.init_resource::<ResumablePuzzle>();
+        world.init_resource::<DailyStreak>();
+        world.init_resource::<AudioSettings>();
+        world.init_resource::<Events<ResolveSaveImport>>();
+        world.insert_resource(PendingSaveImport(Some(imported.clone())));
+
+        world.send_event(ResolveSaveImport::Overwrite);
+        world.run_system_once(resolve_import).unwrap();
+
+        let current_puzzle = world.query::<&CurrentPuzzle>().single(&world).0.clone();
+        assert_eq!(current_puzzle, imported.current_index);
+        assert_eq!(world.resource::<PlayStatistics>().0.len(), 1);
+        assert_eq!(world.resource::<Favorites>().0, imported.favorites);
+        assert_eq!(world.resource::<LastSeenVersion>().0, imported.last_seen_version);
+        assert_eq!(world.resource::<ChosenSolveGesture>().0, Some(imported_gesture));
+        assert_eq!(world.resource::<GameSettings>().solve_gesture, imported_gesture);
+        assert_eq!(
+            world.resource::<ResumablePuzzle>().0.as_ref().map(|progress| progress.puzzle.clone()),
+            Some(PuzzleIdentifier::Level(4))
+        );
+        assert_eq!(*world.resource::<DailyStreak>(), imported.daily_streak);
+        assert_eq!(*world.resource::<AudioSettings>(), imported.audio_settings);
+        assert!(world.resource::<PendingSaveImport>().0.is_none());
+    }
+}
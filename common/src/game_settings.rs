@@ -1,12 +1,19 @@
 use bevy::ecs::system::Resource;
 use bevy::prelude::*;
 
+use crate::{
+    assets::{material_handles::MelodyEmphasis, mesh_handles::RoomMarkerStyle}, background_music::BackgroundLoop, camera::VictoryFraming, controller::SolveGesture,
+    effects::EffectsQuality, level_selector::SelectorFraming, levels::CampaignOrder, play_statistics::DailyTimerStart, sound::MelodyVictoryPriority,
+};
+
+#[derive(Clone, PartialEq, Debug)]
 pub struct FaceColorPalette {
     pub colors: [Color; 6],
 }
 
 impl FaceColorPalette {}
 
+#[derive(Clone, PartialEq, Debug)]
 pub struct GameColorPalette {
     pub player_color: Color,
     pub face_colors: FaceColorPalette,
@@ -34,15 +41,134 @@ impl Default for GameColorPalette {
     }
 }
 
-#[derive(Resource)]
+const PALETTE_CYCLE_KEY: KeyCode = KeyCode::F6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PalettePreset {
+    #[default]
+    Standard,
+    // Okabe-Ito colours, distinguishable under the common forms of colour blindness.
+    ColorblindSafe,
+    HighContrast,
+}
+
+impl PalettePreset {
+    pub const ALL: [PalettePreset; 3] = [
+        PalettePreset::Standard,
+        PalettePreset::ColorblindSafe,
+        PalettePreset::HighContrast,
+    ];
+
+    pub fn next(&self) -> PalettePreset {
+        let index = Self::ALL.iter().position(|preset| preset == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    pub fn palette(&self) -> GameColorPalette {
+        match self {
+            PalettePreset::Standard => GameColorPalette::default(),
+            PalettePreset::ColorblindSafe => GameColorPalette {
+                player_color: Color::srgba_u8(240, 228, 66, 254),
+                line_color: Color::linear_rgba(0.95, 0.91, 0.835, 0.99),
+                face_colors: FaceColorPalette {
+                    colors: [
+                        Color::srgba_u8(230, 159, 0, 254),   // orange
+                        Color::srgba_u8(86, 180, 233, 254),  // sky blue
+                        Color::srgba_u8(0, 158, 115, 254),   // bluish green
+                        Color::srgba_u8(204, 121, 167, 254), // reddish purple
+                        Color::srgba_u8(0, 114, 178, 254),   // blue
+                        Color::srgba_u8(255, 255, 255, 254), // white
+                    ],
+                },
+                background_color: Color::srgba_u8(57, 62, 70, 0),
+            },
+            PalettePreset::HighContrast => GameColorPalette {
+                player_color: Color::srgba_u8(255, 40, 40, 254),
+                line_color: Color::linear_rgba(0.0, 0.0, 0.0, 0.99),
+                face_colors: FaceColorPalette {
+                    colors: [
+                        Color::srgba_u8(255, 221, 0, 254),   // yellow
+                        Color::srgba_u8(0, 229, 255, 254),   // cyan
+                        Color::srgba_u8(255, 0, 200, 254),   // magenta
+                        Color::srgba_u8(255, 255, 255, 254), // white
+                        Color::srgba_u8(120, 255, 80, 254),  // lime
+                        Color::srgba_u8(255, 140, 0, 254),   // orange
+                    ],
+                },
+                background_color: Color::srgba_u8(0, 0, 0, 0),
+            },
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
 pub struct GameSettings {
     pub player_elevation: f32,
+    pub player_move_speed: f32,
+    pub maze_surface_offset: f32,
+    pub fold_curvature: f32,
     pub camera_distance: f32,
+    pub field_of_view_degrees: f32,
     pub light_offset: f32,
     pub camera_follow_speed: f32,
     pub camera_zoom_speed: f32,
+    pub dolly_friction: f32,
+    pub dolly_samples: usize,
+    pub dolly_sensitivity: f32,
+    pub invert_dolly: bool,
+    pub selector_tap_threshold: f32,
+    pub dead_zone_factor: f32,
+    pub node_snap_fraction: f32,
+    pub solve_gesture: SolveGesture,
     pub max_player_speed: f32,
+    pub camera_follow: bool,
+    pub idle_recenter: bool,
+    pub idle_recenter_seconds: f32,
+    pub auto_zoom: bool,
+    pub victory_framing: VictoryFraming,
+    pub selector_framing: SelectorFraming,
+    pub campaign_intro_sweep: bool,
+    pub campaign_overview_seconds: f32,
+    pub reduced_motion: bool,
+    pub keep_upright: bool,
+    pub focus_on_player: bool,
+    pub high_visibility_player: bool,
+    pub illegal_move_nudge: bool,
+    pub illegal_move_tick: bool,
+    pub snap_assist_nodes: usize,
+    pub reveal_solution: bool,
+    pub flow_meter: bool,
+    pub edge_counter: bool,
+    pub show_melody_notation: bool,
+    // Debug builds only, so release builds keep every melody behind its puzzle.
+    #[cfg(debug_assertions)]
+    pub reveal_melodies: bool,
+    pub transpose_semitones: i32,
+    pub face_compass: bool,
+    pub show_path_trail: bool,
+    pub vsync: bool,
+    pub max_frame_rate: Option<f32>,
+    pub inactivity_timeout_seconds: Option<f32>,
+    pub campaign_order: CampaignOrder,
+    pub reverse_solve: bool,
+    pub ghost_replay: bool,
+    pub daily_timer_start: DailyTimerStart,
+    pub room_marker_style: RoomMarkerStyle,
+    pub melody_emphasis: MelodyEmphasis,
+    pub effects_quality: EffectsQuality,
+    pub adaptive_quality: bool,
+    pub victory_cue: bool,
+    pub victory_cue_volume: f32,
+    pub background_music: bool,
+    pub background_music_volume: f32,
+    pub selector_background_loop: Option<BackgroundLoop>,
+    pub melody_victory_priority: MelodyVictoryPriority,
+    pub max_note_voices: usize,
+    pub min_note_interval: f32,
+    pub note_dynamics: bool,
     pub palette: GameColorPalette,
+    pub palette_preset: PalettePreset,
+    pub palette_hotkey: bool,
 }
 
 impl GameSettings {}
@@ -51,12 +177,70 @@ impl Default for GameSettings {
     fn default() -> Self {
         Self {
             player_elevation: 0.05,
+            player_move_speed: 20.0,
+            maze_surface_offset: 1.0,
+            fold_curvature: 0.0,
             camera_distance: 3.0,
+            field_of_view_degrees: 45.0,
             light_offset: 3.0,
             camera_follow_speed: 0.1,
             camera_zoom_speed: 0.3,
+            dolly_friction: 0.05,
+            dolly_samples: 5,
+            dolly_sensitivity: 1.0,
+            invert_dolly: false,
+            selector_tap_threshold: 12.0,
+            dead_zone_factor: 0.1,
+            node_snap_fraction: 0.1,
+            solve_gesture: SolveGesture::platform_default(),
             max_player_speed: 4.0,
-            palette: GameColorPalette::default(),
+            camera_follow: true,
+            idle_recenter: false,
+            idle_recenter_seconds: 3.0,
+            auto_zoom: false,
+            victory_framing: VictoryFraming::default(),
+            selector_framing: SelectorFraming::default(),
+            campaign_intro_sweep: true,
+            campaign_overview_seconds: 0.0,
+            reduced_motion: false,
+            keep_upright: false,
+            focus_on_player: false,
+            high_visibility_player: false,
+            illegal_move_nudge: true,
+            illegal_move_tick: false,
+            snap_assist_nodes: 0,
+            reveal_solution: false,
+            flow_meter: false,
+            edge_counter: false,
+            show_melody_notation: false,
+            #[cfg(debug_assertions)]
+            reveal_melodies: false,
+            transpose_semitones: 0,
+            face_compass: false,
+            show_path_trail: false,
+            vsync: true,
+            max_frame_rate: None,
+            inactivity_timeout_seconds: None,
+            campaign_order: CampaignOrder::default(),
+            reverse_solve: false,
+            ghost_replay: true,
+            daily_timer_start: DailyTimerStart::default(),
+            room_marker_style: RoomMarkerStyle::default(),
+            melody_emphasis: MelodyEmphasis::default(),
+            effects_quality: EffectsQuality::default(),
+            adaptive_quality: false,
+            victory_cue: true,
+            victory_cue_volume: 0.6,
+            background_music: false,
+            background_music_volume: 0.3,
+            selector_background_loop: None,
+            melody_victory_priority: MelodyVictoryPriority::default(),
+            max_note_voices: 8,
+            min_note_interval: 0.0,
+            note_dynamics: false,
+            palette: PalettePreset::default().palette(),
+            palette_preset: PalettePreset::default(),
+            palette_hotkey: true,
         }
     }
 }
@@ -66,6 +250,73 @@ pub struct GameSettingsPlugin;
 
 impl Plugin for GameSettingsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<GameSettings>();
+        app.init_resource::<GameSettings>()
+            .add_systems(Update, cycle_palette_preset.run_if(palette_hotkey_enabled));
+    }
+}
+
+fn palette_hotkey_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.palette_hotkey
+}
+
+// The new palette reaches every mesh through `material_handles::update_palette`, which rewrites
+// the shared material handles in place.
+fn cycle_palette_preset(keys: Res<ButtonInput<KeyCode>>, mut game_settings: ResMut<GameSettings>) {
+    if !keys.just_pressed(PALETTE_CYCLE_KEY) {
+        return;
+    }
+
+    let palette_preset = game_settings.palette_preset.next();
+    info!(target: "mazonic::settings", "Switching to palette preset {:?}", palette_preset);
+
+    game_settings.palette_preset = palette_preset;
+    game_settings.palette = palette_preset.palette();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn press_palette_key(world: &mut World) {
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.release(PALETTE_CYCLE_KEY);
+        keys.clear();
+        keys.press(PALETTE_CYCLE_KEY);
+        world.run_system_once(cycle_palette_preset).unwrap();
+    }
+
+    #[test]
+    fn cycling_through_every_preset_returns_to_the_start() {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<GameSettings>();
+        let start = world.resource::<GameSettings>().palette.clone();
+
+        let mut presets = vec![];
+        for _ in 0..PalettePreset::ALL.len() {
+            press_palette_key(&mut world);
+            let game_settings = world.resource::<GameSettings>();
+            assert_eq!(game_settings.palette, game_settings.palette_preset.palette());
+            presets.push(game_settings.palette_preset);
+        }
+
+        assert_eq!(presets, [PalettePreset::ColorblindSafe, PalettePreset::HighContrast, PalettePreset::Standard]);
+        assert_eq!(world.resource::<GameSettings>().palette, start);
+    }
+
+    #[test]
+    fn holding_the_palette_key_switches_once() {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<GameSettings>();
+
+        world.resource_mut::<ButtonInput<KeyCode>>().press(PALETTE_CYCLE_KEY);
+        world.run_system_once(cycle_palette_preset).unwrap();
+        world.resource_mut::<ButtonInput<KeyCode>>().clear();
+        world.run_system_once(cycle_palette_preset).unwrap();
+
+        assert_eq!(world.resource::<GameSettings>().palette_preset, PalettePreset::ColorblindSafe);
     }
 }
@@ -5,15 +5,21 @@ use bevy::{
 };
 
 use crate::{
-    assets::{material_handles::setup_materials, mesh_handles::setup_mesh_handles}, camera, controller::{self, idle, solve, view, ControllerState}, controller_screen_position, effects::{
+    assets::{material_handles::{self, setup_materials}, mesh_handles::setup_mesh_handles, mesh_warmup, symbol_sheet::{self, SymbolSheet}}, background_music, camera, controller::{self, idle, solve, view, ControllerState}, controller_screen_position, effects::{
         self,
         node_arrival::{spawn_node_arrival_particles, update_node_arrival_particles},
-    }, game_save, game_state::{
+    }, edge_coverage, favorites, flow_meter, frame_rate, game_save, game_state::{
         victory_transition,
         GameState, PuzzleState,
-    }, level_selector::{self, SelectorState}, levels, light, load_level_asset, maze::{self, mesh::update_on_melody_discovered}, menu, play_statistics, player, player_path, shape, sound::{self, check_melody_solved, play_note}, ui, victory
+    }, daily_streak, hint, inactivity, level_selector::{self, SelectorState}, levels, light, load_level_asset, maze::{self, mesh::update_on_melody_discovered}, maze_metrics, menu, path_trail, pause, play_statistics, player, player_path, shape, snap_assist, solve_recording, sound::{self, check_melody_solved, play_note}, ui, undo, victory, whats_new
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::state_dump;
+
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+use crate::melody_export;
+
 #[derive(Default)]
 pub struct GameSystemsPlugin;
 
@@ -22,16 +28,74 @@ impl Plugin for GameSystemsPlugin {
         app.init_state::<GameState>()
             .add_sub_state::<PuzzleState>()
             .add_sub_state::<SelectorState>()
-            .add_sub_state::<victory::VictoryState>();
+            .add_sub_state::<victory::VictoryState>()
+            .add_sub_state::<pause::PauseState>();
+
+        app.init_resource::<SystemHandles>()
+            .insert_resource(SymbolSheet::load())
+            .init_resource::<sound::PreviousNoteRoom>()
+            .init_resource::<sound::AudioPaused>()
+            .init_resource::<effects::adaptive_quality::AdaptiveQuality>()
+            .init_resource::<game_save::PendingSaveImport>()
+            .init_resource::<snap_assist::SnapAssist>()
+            .init_resource::<inactivity::Inactivity>()
+            .init_resource::<undo::PlayerMoveHistory>()
+            .init_resource::<solve_recording::SolveRecording>()
+            .init_resource::<level_selector::SelectorPress>()
+            .init_resource::<maze_metrics::MazeMetrics>()
+            .init_resource::<game_save::ResumablePuzzle>()
+            .init_resource::<game_save::PendingResume>()
+            .init_resource::<menu::ResumeOffer>()
+            .init_resource::<daily_streak::DailyStreak>()
+            .init_resource::<sound::AudioSettings>()
+            .init_resource::<sound::SoundClock>()
+            .add_event::<whats_new::OpenWhatsNew>()
+            .add_event::<game_save::ImportSave>()
+            .add_event::<game_save::ResolveSaveImport>()
+            .add_event::<undo::UndoMove>()
+            .add_event::<hint::HintRequest>()
+            .add_event::<pause::TogglePause>();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_systems(Last, frame_rate::limit_frame_rate)
+            .add_systems(
+                Update,
+                (
+                    state_dump::dump.run_if(state_dump::dump_requested),
+                    game_save::read_import_file
+                        .run_if(game_save::import_requested)
+                        .before(game_save::receive_import),
+                ),
+            );
 
-        app.init_resource::<SystemHandles>();
+        #[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+        app.add_systems(
+            Update,
+            melody_export::export
+                .run_if(in_state(GameState::Puzzle))
+                .run_if(melody_export::export_requested),
+        );
 
         let enter_play_systems = (
             shape::spawn,
             maze::mesh::spawn,
             player::spawn,
             camera::update_distance.after(player::spawn),
+            camera::follow_player.after(player::spawn),
+            camera::start_intro_sweep.after(camera::follow_player),
+            camera::start_overview.after(camera::update_distance),
             play_statistics::on_play,
+            maze_metrics::update,
+            ui::planning::spawn.after(play_statistics::on_play),
+            background_music::start.run_if(background_music::background_music_enabled),
+            flow_meter::reset,
+            edge_coverage::reset.run_if(edge_coverage::edge_coverage_enabled),
+            sound::reset_melody_tracking,
+            (snap_assist::reset, undo::reset, ui::undo::spawn, ui::pause::spawn),
+            game_save::apply_resume
+                .after(player::spawn)
+                .after(undo::reset)
+                .before(camera::follow_player),
             camera::reset_dolly_screen_positions,
             ui::navigation::update_previous_level_button_visibility,
             ui::navigation::update_next_level_button_visibility,
@@ -41,45 +105,72 @@ impl Plugin for GameSystemsPlugin {
 
         let exit_puzzle_systems = (
             ui::navigation::despawn_level_navigation_ui,
+            ui::flow_meter::despawn,
+            ui::edge_coverage::despawn,
+            ui::melody_notation::despawn,
+            ui::face_compass::despawn,
             levels::despawn_puzzle_entities,
             ui::message::exit_puzzle_state,
+            background_music::stop,
+            camera::reset_looking_at,
         )
             .into_configs();
 
         let enter_solving_systems = (
             player::turn_off_player_halo,
             effects::player_particles::turn_off_player_particles,
+            camera::hold_current_direction.run_if(not(camera::camera_follow_enabled)),
         );
         let exit_solving_systems = (
             player::turn_on_player_halo,
-            effects::player_particles::turn_on_player_particles,
+            effects::player_particles::turn_on_player_particles.run_if(effects::particles_enabled),
         );
 
         let enter_victory_systems = (
-            camera::follow_player,
+            camera::follow_player.run_if(camera::victory_follows_player),
+            camera::update_distance.run_if(camera::victory_orbits_solid),
             play_statistics::on_victory,
+            flow_meter::on_victory.run_if(flow_meter::flow_meter_enabled),
+            edge_coverage::on_victory.run_if(edge_coverage::edge_coverage_enabled),
             ui::navigation::update_next_level_button_visibility
                 .after(play_statistics::on_victory),
+            player::spawn_ghost.run_if(player::ghost_replay_enabled),
+            game_save::clear_progress,
+            daily_streak::on_victory,
         );
 
         let enter_selector_init_systems = (
             level_selector::load,
+            ui::resume::spawn.run_if(menu::resume_offered),
+            ui::message::show_daily_streak,
+            ui::mute::spawn,
+            ui::favorites_filter::spawn,
             camera::reset_dolly_screen_positions,
             level_selector::set_initial_camera_target.after(level_selector::load),
+            (
+                background_music::spawn_selector_source,
+                background_music::start.run_if(background_music::background_music_enabled),
+            )
+                .chain(),
         )
             .into_configs();
 
         let enter_loading_systems = (
             levels::despawn_puzzle_entities,
+            background_music::stop,
+            sound::reset_melody_tracking,
+            solve_recording::reset,
         )
             .into_configs();
 
         let startup_systems = (
             camera::setup,
+            effects::detect_particle_support,
             light::setup,
             setup_materials,
             game_save::setup,
             setup_mesh_handles,
+            mesh_warmup::setup,
             effects::player_particles::setup,
             effects::musical_notes::setup,
             effects::musical_note_burst::setup,
@@ -88,36 +179,74 @@ impl Plugin for GameSystemsPlugin {
             ui::message::spawn,
             menu::setup.after(game_save::setup),
             play_statistics::setup,
+            flow_meter::setup,
+            edge_coverage::setup,
+            favorites::setup,
+            whats_new::check_version.after(game_save::setup),
         );
 
         let update_systems = get_update_systems();
 
         app.add_systems(Startup, startup_systems)
             .add_systems(Update, update_systems)
+            .add_systems(PreUpdate, sound::tick_sound_clock)
+            .add_systems(PostUpdate, frame_rate::apply_present_mode)
             .add_systems(OnEnter(GameState::Selector), enter_selector_init_systems)
+            .add_systems(
+                OnExit(GameState::Selector),
+                (
+                    ui::favorites_filter::despawn,
+                    background_music::stop,
+                    ui::resume::despawn,
+                    menu::withdraw_resume_offer,
+                    ui::message::clear_lower,
+                    ui::mute::despawn,
+                ),
+            )
             .add_systems(
                 OnExit(PuzzleState::Loading),
                 level_selector::despawn,
             )
             .add_systems(OnEnter(PuzzleState::Loading), enter_loading_systems)
             .add_systems(OnEnter(PuzzleState::Playing), enter_play_systems)
-            .add_systems(OnExit(PuzzleState::Playing), play_statistics::exit_play)
+            .add_systems(
+                OnExit(PuzzleState::Playing),
+                (play_statistics::exit_play, ui::planning::despawn, ui::snap_assist::despawn, ui::undo::despawn, ui::pause::despawn),
+            )
+            .add_systems(OnEnter(pause::PauseState::Paused), pause::on_pause)
+            .add_systems(OnExit(pause::PauseState::Paused), pause::on_resume)
             .add_systems(OnEnter(PuzzleState::Victory), enter_victory_systems)
             .add_systems(OnEnter(victory::VictoryState::Viewing), camera::reset_dolly_screen_positions)
-            .add_systems(OnEnter(GameState::Puzzle), ui::navigation::spawn)
+            .add_systems(OnExit(victory::VictoryState::Viewing), camera::release_dolly)
+            .add_systems(
+                OnEnter(GameState::Puzzle),
+                (
+                    inactivity::reset,
+                    ui::navigation::spawn,
+                    ui::flow_meter::spawn.run_if(flow_meter::flow_meter_enabled),
+                    ui::edge_coverage::spawn.run_if(edge_coverage::edge_coverage_enabled),
+                    ui::melody_notation::spawn.run_if(ui::melody_notation::melody_notation_enabled),
+                    ui::face_compass::spawn.run_if(ui::face_compass::face_compass_enabled),
+                ),
+            )
             .add_systems(OnExit(GameState::Puzzle), exit_puzzle_systems)
             .add_systems(OnEnter(ControllerState::Solving), enter_solving_systems)
             .add_systems(
                 OnEnter(ControllerState::IdlePostSolve),
-                camera::follow_player,
+                (
+                    camera::follow_player.run_if(camera::camera_follow_enabled),
+                    camera::follow_player_if_out_of_view
+                        .run_if(not(camera::camera_follow_enabled)),
+                    camera::auto_zoom_to_player.run_if(camera::auto_zoom_enabled),
+                ),
             )
             .add_systems(
                 OnExit(ControllerState::Viewing),
-                camera::reset_dolly_screen_positions,
+                (camera::release_dolly, camera::reset_dolly_screen_positions).chain(),
             )
             .add_systems(
                 OnExit(SelectorState::Clicked),
-                camera::reset_dolly_screen_positions,
+                (camera::release_dolly, camera::reset_dolly_screen_positions).chain(),
             )
             .add_systems(
                 OnExit(SelectorState::Clicked),
@@ -131,7 +260,17 @@ fn get_update_systems() -> SystemConfigs {
     let selector_systems = (
         level_selector::set_selector_state.run_if(in_state(GameState::Selector)),
         level_selector::update_interactables.run_if(in_state(GameState::Selector)),
-        level_selector::update_selection_overlay.run_if(in_state(GameState::Selector))
+        level_selector::preview_melody
+            .after(level_selector::update_interactables)
+            .run_if(in_state(GameState::Selector)),
+        level_selector::update_selection_overlay.run_if(in_state(GameState::Selector)),
+        level_selector::apply_favorites_filter.run_if(in_state(GameState::Selector)),
+        ui::favorites_filter::toggle.run_if(in_state(GameState::Selector)),
+        ui::favorites_filter::update.run_if(in_state(GameState::Selector)),
+        ui::resume::press.run_if(in_state(GameState::Selector)),
+        (ui::mute::press, ui::mute::update_text)
+            .chain()
+            .run_if(in_state(GameState::Selector)),
     ).into_configs();
 
     let camera_systems = (
@@ -140,15 +279,41 @@ fn get_update_systems() -> SystemConfigs {
                 .or(in_state(victory::VictoryState::Viewing).or(in_state(SelectorState::Clicked))),
         ),
         camera::trigger_camera_resize_on_window_change,
+        (camera::update_clear_color, camera::apply_field_of_view),
         camera::camera_rotate_to_target.run_if(
             in_state(ControllerState::IdlePostSolve)
+            .or(in_state(ControllerState::IdlePostView).and(camera::idle_recentering))
             .or(in_state(SelectorState::Idle))),
+        camera::update_idle_recenter
+            .before(camera::camera_rotate_to_target)
+            .run_if(in_state(PuzzleState::Playing).and(camera::idle_recenter_enabled)),
         camera::camera_zoom_to_target.run_if(
             in_state(ControllerState::IdlePostSolve)
             .or(in_state(ControllerState::IdlePostView))
             .or(in_state(SelectorState::Idle))
             .or(in_state(victory::VictoryState::Idle)),
         ),
+        camera::update_intro_sweep
+            .before(camera::camera_rotate_to_target)
+            .run_if(in_state(PuzzleState::Playing)),
+        camera::update_overview.run_if(in_state(PuzzleState::Playing)),
+        camera::update_looking_at
+            .before(camera::camera_rotate_to_target)
+            .run_if(in_state(GameState::Puzzle)),
+        camera::orbit_solid
+            .run_if(in_state(victory::VictoryState::Idle).and(camera::victory_orbits_solid)),
+        camera::relevel_camera
+            .after(camera::update_dolly)
+            .after(camera::camera_rotate_to_target)
+            .after(camera::orbit_solid)
+            .run_if(camera::keep_upright_enabled),
+        camera::keyboard_dolly
+            .before(camera::update_dolly)
+            .run_if(
+                in_state(ControllerState::IdlePostSolve)
+                    .or(in_state(ControllerState::IdlePostView))
+                    .or(in_state(victory::VictoryState::Idle)),
+            ),
         camera::update_dolly.run_if(
             in_state(ControllerState::Viewing)
                 .or(in_state(ControllerState::IdlePostView))
@@ -161,9 +326,18 @@ fn get_update_systems() -> SystemConfigs {
 
     (
         (
-            player::update,
+            (
+                player::start_nudge.run_if(player::nudge_enabled),
+                player::advance_nudge,
+                player::update,
+            )
+                .chain(),
             game_save::update,
+            game_save::record_progress
+                .after(player_path::update)
+                .run_if(in_state(PuzzleState::Playing)),
             player::update_halo,
+            player::pulse_high_visibility_ring.run_if(player::high_visibility_enabled),
             effects::player_particles::update_player_particles,
         )
             .run_if(in_state(GameState::Puzzle)),
@@ -173,34 +347,136 @@ fn get_update_systems() -> SystemConfigs {
             ui::navigation::replay_level,
             ui::navigation::previous_level,
             ui::navigation::level_selector,
+            ui::navigation::toggle_favorite,
+            ui::navigation::update_favorite_button_text,
+            (ui::navigation::request_hint, hint::revert),
             effects::musical_note_burst::clear_up_effects,
             ui::message::update_lower_during_puzzle_state,
+            ui::message::update_lower_during_victory
+                .after(ui::message::update_lower_during_puzzle_state)
+                .run_if(in_state(PuzzleState::Victory)),
         )
             .run_if(in_state(GameState::Puzzle)),
-        victory_transition.run_if(in_state(PuzzleState::Playing)),
-        player_path::update.run_if(in_state(PuzzleState::Playing)),
-        sound::play_note.run_if(in_state(PuzzleState::Playing)),
-        sound::check_melody_solved.run_if(in_state(PuzzleState::Playing)),
+        // Any route onto the goal room wins; running after solve means a node is never skipped.
+        victory_transition
+            .after(controller::solve)
+            .run_if(in_state(PuzzleState::Playing)),
+        player_path::update
+            .run_if(in_state(PuzzleState::Playing))
+            .run_if(pause::not_paused),
+        sound::play_note
+            .run_if(in_state(PuzzleState::Playing))
+            .run_if(pause::not_paused),
+        sound::check_melody_solved
+            .after(sound::play_note)
+            .run_if(in_state(PuzzleState::Playing)),
+        (
+            sound::play_illegal_move_tick
+                .after(controller::solve)
+                .run_if(sound::illegal_move_tick_enabled),
+            (
+                play_statistics::start_timer_on_first_move
+                    .run_if(in_state(ControllerState::Solving)),
+                ui::planning::dismiss.after(play_statistics::start_timer_on_first_move),
+            ),
+            flow_meter::update
+                .after(player_path::update)
+                .run_if(flow_meter::flow_meter_enabled),
+            path_trail::update
+                .after(player_path::update)
+                .run_if(path_trail::path_trail_enabled),
+            edge_coverage::update
+                .after(player_path::update)
+                .run_if(edge_coverage::edge_coverage_enabled),
+            (
+                ui::snap_assist::press,
+                snap_assist::step
+                    .after(ui::snap_assist::press)
+                    .run_if(pause::not_paused),
+                ui::snap_assist::update.after(snap_assist::step),
+            )
+                .run_if(snap_assist::snap_assist_enabled),
+            (
+                undo::read_keys,
+                ui::undo::press,
+                undo::undo
+                    .run_if(pause::not_paused)
+                    .after(undo::read_keys)
+                    .after(ui::undo::press)
+                    .before(player_path::update)
+                    .before(sound::play_note),
+            ),
+            hint::show.after(ui::navigation::request_hint),
+            maze::mesh::reveal_solution.run_if(maze::mesh::reveal_solution_enabled),
+            solve_recording::record.after(controller::solve),
+            (
+                pause::read_keys,
+                ui::pause::press,
+                pause::toggle.after(pause::read_keys).after(ui::pause::press),
+                ui::pause::update_text,
+            ),
+        )
+            .run_if(in_state(PuzzleState::Playing)),
         load_level_asset::spawn_level_data.run_if(in_state(PuzzleState::Loading)),
         (
-            effects::node_arrival::update_node_arrival_particles,
-            effects::node_arrival::spawn_node_arrival_particles,
+            effects::node_arrival::update_node_arrival_particles.run_if(pause::not_paused),
+            effects::node_arrival::spawn_node_arrival_particles.run_if(pause::not_paused),
+            effects::adaptive_quality::update
+                .run_if(effects::adaptive_quality::adaptive_quality_enabled),
         ),
         (
-            controller::solve.run_if(in_state(ControllerState::Solving)),
+            controller::solve
+                .run_if(in_state(ControllerState::Solving))
+                .run_if(pause::not_paused),
             controller::idle.run_if(
                 in_state(ControllerState::IdlePostSolve).or(in_state(ControllerState::IdlePostView)),
             ),
             controller::view.run_if(in_state(ControllerState::Viewing)),
         ),
-        victory::update_state.run_if(in_state(PuzzleState::Victory)),
+        (victory::update_state, player::update_ghost).run_if(in_state(PuzzleState::Victory)),
         light::follow_camera,
-        play_statistics::during_play.run_if(in_state(PuzzleState::Playing)),
-        effects::musical_notes::spawn,
+        play_statistics::during_play
+            .run_if(in_state(PuzzleState::Playing))
+            .run_if(pause::not_paused),
+        effects::musical_notes::spawn.run_if(effects::particles_enabled),
         selector_systems,
         camera_systems,
-        ui::message::update_upper,
-        ui::message::on_change,
+        (
+            ui::message::update_upper,
+            ui::message::on_change,
+            inactivity::update.run_if(
+                in_state(GameState::Puzzle).and(inactivity::inactivity_timeout_enabled),
+            ),
+        ),
+        (
+            (ui::whats_new::spawn, ui::whats_new::dismiss),
+            (
+                game_save::receive_import,
+                game_save::resolve_import,
+                ui::save_import::spawn.after(game_save::receive_import),
+                ui::save_import::choose,
+            ),
+            (
+                material_handles::update_palette,
+                symbol_sheet::validate_sprite_sheet,
+                symbol_sheet::fallback_missing_sprite_sheet,
+                mesh_warmup::warm_next_shapes.run_if(mesh_warmup::warmup_pending),
+                mesh_warmup::despawn_warmed,
+            ),
+            (
+                sound::pause_on_focus_change,
+                sound::apply_audio_pause::<AudioSink>.after(sound::pause_on_focus_change),
+                background_music::update_volume,
+                sound::apply_audio_settings,
+                sound::advance_melody_playback,
+            ),
+            (
+                ui::flow_meter::update.run_if(flow_meter::flow_meter_enabled),
+                ui::edge_coverage::update.run_if(edge_coverage::edge_coverage_enabled),
+                ui::melody_notation::update.run_if(ui::melody_notation::melody_notation_enabled),
+                ui::face_compass::update.run_if(ui::face_compass::face_compass_enabled),
+            ),
+        ),
         game_save::update_working_level,
         load_level_asset::wait_until_loaded.run_if(in_state(GameState::LoadingRemoteLevel))
     )
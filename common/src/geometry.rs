@@ -0,0 +1,388 @@
+use bevy::math::{primitives::InfinitePlane3d, NormedVectorSpace, Ray3d, Vec3};
+use itertools::Itertools;
+use petgraph::{graphmap::GraphMap, Directed, Direction};
+
+use crate::{
+    maze::border_type::BorderType,
+    player::PlayerMazeState,
+    room::{Edge, Room},
+};
+
+pub fn controller_position(room: &Room, elevation: f32) -> Vec3 {
+    room.position() + elevation * room.face().normal()
+}
+
+pub fn project_ray_to_controller_face(ray: Ray3d, room: &Room, elevation: f32) -> Option<Vec3> {
+    let plane_normal = room.face().normal();
+    let plane_point = controller_position(room, elevation);
+
+    ray.intersect_plane(plane_point, InfinitePlane3d::new(plane_normal))
+        .map(|ray_distance| ray.origin + ray.direction.normalize() * ray_distance)
+}
+
+pub fn project_point_to_plane(point: Vec3, plane_position: Vec3, plane_normal: Vec3) -> Vec3 {
+    point - plane_normal.dot(point - plane_position) * plane_normal
+}
+
+pub fn compute_intersection_point_of_edge(
+    ray: Ray3d,
+    from_room: &Room,
+    elevation: f32,
+    to_room: &Room,
+) -> Option<Vec3> {
+    let from_normal = from_room.face().normal();
+
+    if from_normal.dot(Vec3::from(ray.direction)) > 0.0 {
+        return None;
+    }
+
+    let room_controller_position = controller_position(from_room, elevation);
+    let to_room_controller_position = controller_position(to_room, elevation);
+
+    let other_node_on_from_controller_plane = project_point_to_plane(
+        to_room_controller_position,
+        room_controller_position,
+        from_normal,
+    );
+
+    let from_controller_to = other_node_on_from_controller_plane - room_controller_position;
+    let projected_ray_on_face = project_ray_to_controller_face(ray, from_room, elevation)?;
+
+    let relative_intersection_point = projected_ray_on_face - room_controller_position;
+    let distance_along_node_other_vec = relative_intersection_point.dot(from_controller_to) / from_controller_to.dot(from_controller_to);
+
+    Some(distance_along_node_other_vec.clamp(0.0, 1.0) * from_controller_to + room_controller_position)
+}
+
+// The outcome of pushing away from a node: nothing yet, onto an edge, or a refused move.
+#[derive(Debug)]
+pub enum NodeMove {
+    Stay,
+    Enter(PlayerMazeState),
+    OneWayBlocked(Room),
+    NoEdge(Vec3),
+}
+
+pub fn move_player_on_node(
+    node: &Room,
+    graph: &GraphMap<Room, Edge, Directed>,
+    player_elevation: f32,
+    dead_zone_radius: f32,
+    max_edge_angle: f32,
+    ray: Ray3d,
+) -> NodeMove {
+    let Some(face_intersection_point) = project_ray_to_controller_face(ray, node, player_elevation)
+    else {
+        return NodeMove::Stay;
+    };
+
+    let node_player_position = node.position() + node.face().normal() * player_elevation;
+
+    let face_intersection_from_player = face_intersection_point - node_player_position;
+
+    // Cursor jitter close to the node is ignored until it clears the dead zone.
+    if face_intersection_from_player.norm() <= dead_zone_radius {
+        return NodeMove::Stay;
+    }
+
+    let node_face_normal = node.face().normal();
+    let node_player_plane_position = node.position() + player_elevation * node_face_normal;
+
+    // Incoming neighbors are candidates too, so pointing down a one-way edge from its head is
+    // reported rather than silently snapping to some other outgoing edge.
+    let closest_neighbor = graph
+        .neighbors_directed(*node, Direction::Outgoing)
+        .chain(graph.neighbors_directed(*node, Direction::Incoming))
+        .unique()
+        .map(|to_node| {
+            let to_node_player_plane_position =
+                project_point_to_plane(to_node.position(), node_player_position, node_face_normal);
+
+            let edge_vec = to_node_player_plane_position - node_player_plane_position;
+
+            (to_node, edge_vec.angle_between(face_intersection_from_player))
+        })
+        .filter(|(_, angle)| *angle <= max_edge_angle)
+        .min_by_key(|(_, angle)| (angle * 50.0) as u16)
+        .map(|(to_node, _)| to_node);
+
+    match closest_neighbor {
+        Some(to_node) if graph.contains_edge(*node, to_node) => NodeMove::Enter(
+            PlayerMazeState::Edge(node.clone(), to_node, node_player_plane_position),
+        ),
+        Some(to_node) => NodeMove::OneWayBlocked(to_node),
+        None => NodeMove::NoEdge(face_intersection_from_player.normalize_or_zero()),
+    }
+}
+
+// Within the snap threshold of either end the player lands on that node, otherwise it slides along
+// the edge under the ray.
+pub fn move_player_on_edge(
+    from_node: &Room,
+    to_node: &Room,
+    ray: Ray3d,
+    player_elevation: f32,
+    node_snap_threshold: f32,
+    border_type: &BorderType,
+) -> Option<PlayerMazeState> {
+    let player_plane_edge_intersection =
+        compute_player_plane_edge_intersection(ray, from_node, to_node, player_elevation, border_type)?;
+
+    let to_node_to_intersection = to_node.position() + to_node.face().normal() * player_elevation
+        - player_plane_edge_intersection;
+
+    let from_node_to_intersection = from_node.position()
+        + from_node.face().normal() * player_elevation
+        - player_plane_edge_intersection;
+
+    let new_player_state = if to_node_to_intersection.norm() < node_snap_threshold {
+        PlayerMazeState::Node(to_node.clone())
+    } else if from_node_to_intersection.norm() < node_snap_threshold {
+        PlayerMazeState::Node(from_node.clone())
+    } else {
+        PlayerMazeState::Edge(
+            from_node.clone(),
+            to_node.clone(),
+            player_plane_edge_intersection,
+        )
+    };
+
+    Some(new_player_state)
+}
+
+// A connected edge folds over two faces, so the ray is tried against both and the nearer hit wins.
+pub fn compute_player_plane_edge_intersection(
+    screen_ray: Ray3d,
+    from_node: &Room,
+    to_node: &Room,
+    player_elevation: f32,
+    border_type: &BorderType,
+) -> Option<Vec3> {
+    match border_type {
+        BorderType::SameFace => {
+            compute_intersection_point_of_edge(screen_ray, &from_node, player_elevation, &to_node)
+        }
+        BorderType::Connected => {
+            let to_plane_intersection = compute_intersection_point_of_edge(
+                screen_ray,
+                &to_node,
+                player_elevation,
+                &from_node,
+            );
+
+            let from_plane_intersection = compute_intersection_point_of_edge(
+                screen_ray,
+                &from_node,
+                player_elevation,
+                &to_node,
+            );
+
+            std::cmp::max_by_key(
+                from_plane_intersection,
+                to_plane_intersection,
+                |opt_intersection| opt_intersection.map(|x| (1000.0 / x.norm()) as u16),
+            )
+        }
+    }
+}
+
+// The candidate whose outward normal points most directly back at the camera.
+pub fn closest_facing<T>(
+    camera_forward: Vec3,
+    candidates: impl Iterator<Item = T>,
+    normal: impl Fn(&T) -> Vec3,
+) -> Option<T> {
+    candidates.min_by_key(|candidate| (camera_forward.dot(normal(candidate)) * 100.0) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_4;
+
+    use bevy::math::Dir3;
+
+    use crate::room::{
+        test_support::{room_at, room_on_face},
+        Face,
+    };
+
+    use super::*;
+
+    const ELEVATION: f32 = 0.1;
+    const SIDE: Face = Face { id: 1, normal: Vec3::X };
+
+    fn ray(origin: Vec3, direction: Vec3) -> Ray3d {
+        Ray3d::new(origin, Dir3::new(direction).unwrap())
+    }
+
+    fn looking_down_at(x: f32, y: f32) -> Ray3d {
+        ray(Vec3::new(x, y, 5.0), -Vec3::Z)
+    }
+
+    fn assert_close(actual: Vec3, expected: Vec3) {
+        assert!(actual.distance(expected) < 1e-4, "{actual} is not {expected}");
+    }
+
+    #[test]
+    fn same_face_intersection_projects_onto_the_edge() {
+        let from = room_at(0, Vec3::ZERO);
+        let to = room_at(1, Vec3::X);
+
+        let point = compute_intersection_point_of_edge(looking_down_at(0.3, 0.4), &from, ELEVATION, &to);
+        assert_close(point.unwrap(), Vec3::new(0.3, 0.0, 0.1));
+
+        let point = compute_player_plane_edge_intersection(looking_down_at(0.3, 0.4), &from, &to, ELEVATION, &BorderType::SameFace);
+        assert_close(point.unwrap(), Vec3::new(0.3, 0.0, 0.1));
+    }
+
+    #[test]
+    fn same_face_intersection_clamps_to_the_edge_ends() {
+        let from = room_at(0, Vec3::ZERO);
+        let to = room_at(1, Vec3::X);
+
+        let past_to = compute_intersection_point_of_edge(looking_down_at(1.5, 0.0), &from, ELEVATION, &to);
+        assert_close(past_to.unwrap(), Vec3::new(1.0, 0.0, 0.1));
+
+        let behind_from = compute_intersection_point_of_edge(looking_down_at(-0.5, 0.2), &from, ELEVATION, &to);
+        assert_close(behind_from.unwrap(), Vec3::new(0.0, 0.0, 0.1));
+    }
+
+    #[test]
+    fn intersection_ignores_rays_from_behind_the_face() {
+        let from = room_at(0, Vec3::ZERO);
+        let to = room_at(1, Vec3::X);
+
+        let from_below = ray(Vec3::new(0.5, 0.0, -5.0), Vec3::Z);
+        assert!(compute_intersection_point_of_edge(from_below, &from, ELEVATION, &to).is_none());
+    }
+
+    // A cube corner: `from` sits on the top face and `to` on the +X face, 0.2 from their shared edge.
+    fn connected_rooms() -> (Room, Room) {
+        (room_at(0, Vec3::new(0.8, 0.0, 1.0)), room_on_face(1, Vec3::new(1.0, 0.0, 0.8), SIDE))
+    }
+
+    #[test]
+    fn connected_intersection_uses_the_top_face_for_a_downward_ray() {
+        let (from, to) = connected_rooms();
+
+        // The ray runs parallel to the side face, so only the top face plane is hit.
+        let point = compute_player_plane_edge_intersection(looking_down_at(0.9, 0.0), &from, &to, ELEVATION, &BorderType::Connected);
+        assert_close(point.unwrap(), Vec3::new(0.9, 0.0, 1.1));
+    }
+
+    #[test]
+    fn connected_intersection_uses_the_side_face_for_a_sideways_ray() {
+        let (from, to) = connected_rooms();
+
+        let sideways = ray(Vec3::new(5.0, 0.0, 0.9), -Vec3::X);
+        let point = compute_player_plane_edge_intersection(sideways, &from, &to, ELEVATION, &BorderType::Connected);
+        assert_close(point.unwrap(), Vec3::new(1.1, 0.0, 0.9));
+    }
+
+    #[test]
+    fn connected_intersection_meets_at_the_fold() {
+        let (from, to) = connected_rooms();
+
+        let diagonal = ray(Vec3::new(5.0, 0.0, 5.0), Vec3::new(-1.0, 0.0, -1.0));
+        let point = compute_player_plane_edge_intersection(diagonal, &from, &to, ELEVATION, &BorderType::Connected);
+        assert_close(point.unwrap(), Vec3::new(1.1, 0.0, 1.1));
+    }
+
+    #[test]
+    fn edge_move_snaps_to_the_nearer_node_within_the_threshold() {
+        let from = room_at(0, Vec3::ZERO);
+        let to = room_at(1, Vec3::X);
+        let snap_threshold = 0.1;
+
+        let near_to = move_player_on_edge(&from, &to, looking_down_at(0.95, 0.0), ELEVATION, snap_threshold, &BorderType::SameFace);
+        assert!(matches!(near_to, Some(PlayerMazeState::Node(room)) if room.id == to.id));
+
+        let near_from = move_player_on_edge(&from, &to, looking_down_at(0.04, 0.0), ELEVATION, snap_threshold, &BorderType::SameFace);
+        assert!(matches!(near_from, Some(PlayerMazeState::Node(room)) if room.id == from.id));
+
+        let between = move_player_on_edge(&from, &to, looking_down_at(0.5, 0.0), ELEVATION, snap_threshold, &BorderType::SameFace);
+        let Some(PlayerMazeState::Edge(edge_from, edge_to, position)) = between else {
+            panic!("expected to stay on the edge, got {between:?}");
+        };
+        assert_eq!((edge_from.id, edge_to.id), (from.id, to.id));
+        assert_close(position, Vec3::new(0.5, 0.0, 0.1));
+    }
+
+    // `center` has an outgoing edge to `east` and an incoming one-way edge from `north`.
+    fn junction() -> (Room, Room, Room, GraphMap<Room, Edge, Directed>) {
+        let center = room_at(0, Vec3::ZERO);
+        let east = room_at(1, Vec3::X);
+        let north = room_at(2, Vec3::Y);
+
+        let mut graph = GraphMap::new();
+        graph.add_edge(center, east, Edge);
+        graph.add_edge(east, center, Edge);
+        graph.add_edge(north, center, Edge);
+
+        (center, east, north, graph)
+    }
+
+    fn push_from(center: &Room, graph: &GraphMap<Room, Edge, Directed>, x: f32, y: f32) -> NodeMove {
+        move_player_on_node(center, graph, ELEVATION, 0.05, FRAC_PI_4, looking_down_at(x, y))
+    }
+
+    #[test]
+    fn node_move_stays_inside_the_dead_zone() {
+        let (center, _, _, graph) = junction();
+
+        assert!(matches!(push_from(&center, &graph, 0.02, 0.02), NodeMove::Stay));
+    }
+
+    #[test]
+    fn cursor_jitter_inside_the_dead_zone_never_moves_the_player() {
+        let (center, _, _, graph) = junction();
+        let dead_zone_radius = 0.1;
+
+        // Wobbling around the node toward the open edge, never farther out than the dead zone.
+        for (x, y) in [(0.08, 0.0), (0.06, 0.03), (0.09, -0.02), (0.0, 0.07), (0.07, 0.07)] {
+            let node_move = move_player_on_node(&center, &graph, ELEVATION, dead_zone_radius, FRAC_PI_4, looking_down_at(x, y));
+            assert!(matches!(node_move, NodeMove::Stay), "jitter at ({x}, {y}) gave {node_move:?}");
+        }
+
+        // The same push clears a narrower dead zone.
+        assert!(matches!(push_from(&center, &graph, 0.08, 0.0), NodeMove::Enter(_)));
+    }
+
+    #[test]
+    fn node_move_enters_the_closest_edge_at_the_player_plane() {
+        let (center, east, _, graph) = junction();
+
+        let NodeMove::Enter(PlayerMazeState::Edge(from, to, position)) = push_from(&center, &graph, 0.5, 0.1) else {
+            panic!("expected to enter the east edge");
+        };
+        assert_eq!((from.id, to.id), (center.id, east.id));
+        assert_close(position, Vec3::new(0.0, 0.0, 0.1));
+    }
+
+    #[test]
+    fn node_move_enters_a_one_way_edge_from_its_tail() {
+        let (center, _, north, graph) = junction();
+
+        let NodeMove::Enter(PlayerMazeState::Edge(from, to, _)) = push_from(&north, &graph, 0.0, 0.5) else {
+            panic!("expected to enter the one-way edge towards the center");
+        };
+        assert_eq!((from.id, to.id), (north.id, center.id));
+    }
+
+    #[test]
+    fn node_move_refuses_a_one_way_edge_from_its_head() {
+        let (center, _, north, graph) = junction();
+
+        assert!(matches!(push_from(&center, &graph, 0.0, 0.5), NodeMove::OneWayBlocked(room) if room.id == north.id));
+    }
+
+    #[test]
+    fn node_move_reports_the_direction_when_no_edge_is_close() {
+        let (center, _, _, graph) = junction();
+
+        let NodeMove::NoEdge(direction) = push_from(&center, &graph, -0.5, 0.0) else {
+            panic!("expected no edge to the west");
+        };
+        assert_close(direction, Vec3::NEG_X);
+    }
+}
@@ -0,0 +1,76 @@
+use petgraph::{graphmap::GraphMap, Directed};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    maze::solver::{breadth_first_search, path_to},
+    room::{Edge, Room},
+};
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoalPlacement {
+    #[default]
+    Generated,
+    Farthest,
+}
+
+impl GoalPlacement {
+    pub fn place(&self, graph: &GraphMap<Room, Edge, Directed>, solution: Vec<Room>) -> Vec<Room> {
+        match self {
+            GoalPlacement::Generated => solution,
+            GoalPlacement::Farthest => solution
+                .first()
+                .map(|start| farthest_node_solution(graph, *start))
+                .unwrap_or(solution),
+        }
+    }
+}
+
+pub fn farthest_node_solution(graph: &GraphMap<Room, Edge, Directed>, start: Room) -> Vec<Room> {
+    let (visit_order, predecessors) = breadth_first_search(graph, start);
+    let farthest_node = visit_order.last().copied().unwrap_or(start);
+
+    path_to(farthest_node, &predecessors)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Vec3;
+
+    use crate::{maze::solver::shortest_path, room::test_support::room};
+
+    use super::*;
+
+    // Two branches leave the start; the longer one loops back on itself before its dead end at 6.
+    fn small_maze() -> GraphMap<Room, Edge, Directed> {
+        let mut graph = GraphMap::new();
+        for (from, to) in [(0, 1), (1, 2), (0, 3), (3, 4), (4, 5), (5, 3), (5, 6)] {
+            graph.add_edge(room(from), room(to), Edge);
+            graph.add_edge(room(to), room(from), Edge);
+        }
+        graph
+    }
+
+    #[test]
+    fn farthest_goal_is_at_the_maximal_distance_from_the_start() {
+        let graph = small_maze();
+        let generated_solution = vec![room(0), room(1), room(2)];
+
+        let solution = GoalPlacement::Farthest.place(&graph, generated_solution);
+
+        assert_eq!(solution.first().map(|room| room.id), Some(0));
+        assert_eq!(solution.last().map(|room| room.id), Some(6));
+        for node in graph.nodes() {
+            let distance = shortest_path(&graph, room(0), node).unwrap().len();
+            assert!(distance <= solution.len(), "room {} is farther than the goal", node.id);
+        }
+        assert_eq!(solution, shortest_path(&graph, room(0), room(6)).unwrap());
+    }
+
+    #[test]
+    fn generated_placement_keeps_the_generated_solution() {
+        let graph = small_maze();
+        let generated_solution = vec![room(0), room(1), room(2)];
+
+        assert_eq!(GoalPlacement::Generated.place(&graph, generated_solution.clone()), generated_solution);
+    }
+}
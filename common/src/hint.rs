@@ -0,0 +1,128 @@
+use bevy::{pbr::ExtendedMaterial, prelude::*};
+use petgraph::{graphmap::GraphMap, Directed};
+
+use crate::{
+    assets::{material_handles::MaterialHandles, shaders::DashedArrowShader},
+    game_settings::GameSettings,
+    is_room_junction::is_junction,
+    maze::{mesh::MazeEdgeRooms, solver::shortest_path},
+    player::PlayerMazeState,
+    room::{Edge, Room},
+    shape::loader::{GraphComponent, SolutionComponent},
+};
+
+const HINT_SECONDS: f32 = 1.5;
+
+#[derive(Event)]
+pub struct HintRequest;
+
+enum HintedMaterial {
+    Line(Handle<StandardMaterial>),
+    DashedArrow(Handle<ExtendedMaterial<StandardMaterial, DashedArrowShader>>),
+}
+
+// Marks an edge mesh lit by a hint, holding the material to put back once the timer runs out.
+#[derive(Component)]
+pub struct HintHighlight {
+    timer: Timer,
+    original: HintedMaterial,
+}
+
+// The rooms from `room` up to the next junction on the way to the goal, which is the next choice
+// the player has to get right. There is nothing to hint once that junction is the goal itself.
+pub fn next_move(graph: &GraphMap<Room, Edge, Directed>, room: Room, goal: Room) -> Option<Vec<Room>> {
+    let path = shortest_path(graph, room, goal)?;
+
+    let next_junction_index = path
+        .iter()
+        .skip(1)
+        .position(|path_room| is_junction(path_room, graph))?
+        + 1;
+
+    (path[next_junction_index] != goal).then(|| path[..=next_junction_index].to_vec())
+}
+
+pub fn show(
+    mut commands: Commands,
+    mut hint_reader: EventReader<HintRequest>,
+    player_query: Query<&PlayerMazeState>,
+    maze_query: Query<(&GraphComponent, &SolutionComponent)>,
+    mut edge_query: Query<(
+        Entity,
+        &MazeEdgeRooms,
+        Option<&MeshMaterial3d<StandardMaterial>>,
+        Option<&MeshMaterial3d<ExtendedMaterial<StandardMaterial, DashedArrowShader>>>,
+        Option<&mut HintHighlight>,
+    )>,
+    material_handles: Res<MaterialHandles>,
+    game_settings: Res<GameSettings>,
+) {
+    if hint_reader.read().count() == 0 {
+        return;
+    }
+
+    let Ok(PlayerMazeState::Node(room)) = player_query.get_single() else {
+        return;
+    };
+
+    let Ok((GraphComponent(graph), SolutionComponent(solution))) = maze_query.get_single() else {
+        return;
+    };
+
+    let Some(goal) = solution.last() else {
+        return;
+    };
+
+    let Some(next_move) = next_move(graph, *room, *goal) else {
+        return;
+    };
+
+    debug!(target: "mazonic::hint", "Hinting {} rooms from room {}", next_move.len() - 1, room.id);
+
+    for (entity, edge_rooms, line_material, dashed_arrow_material, hint_highlight) in edge_query.iter_mut() {
+        if !next_move.windows(2).any(|pair| edge_rooms.joins(&pair[0], &pair[1])) {
+            continue;
+        }
+
+        if let Some(mut hint_highlight) = hint_highlight {
+            hint_highlight.timer.reset();
+            continue;
+        }
+
+        let original = match (line_material, dashed_arrow_material) {
+            (Some(MeshMaterial3d(handle)), _) => {
+                commands.entity(entity).insert(MeshMaterial3d(
+                    material_handles.melody_line_handles.get(game_settings.melody_emphasis).clone(),
+                ));
+                HintedMaterial::Line(handle.clone())
+            }
+            (None, Some(MeshMaterial3d(handle))) => {
+                commands.entity(entity).insert(MeshMaterial3d(
+                    material_handles.melody_dashed_arrow_handles.get(game_settings.melody_emphasis).clone(),
+                ));
+                HintedMaterial::DashedArrow(handle.clone())
+            }
+            (None, None) => continue,
+        };
+
+        commands.entity(entity).insert(HintHighlight {
+            timer: Timer::from_seconds(HINT_SECONDS, TimerMode::Once),
+            original,
+        });
+    }
+}
+
+pub fn revert(mut commands: Commands, mut highlight_query: Query<(Entity, &mut HintHighlight)>, time: Res<Time>) {
+    for (entity, mut hint_highlight) in highlight_query.iter_mut() {
+        if !hint_highlight.timer.tick(time.delta()).finished() {
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(entity);
+        match &hint_highlight.original {
+            HintedMaterial::Line(handle) => entity_commands.insert(MeshMaterial3d(handle.clone())),
+            HintedMaterial::DashedArrow(handle) => entity_commands.insert(MeshMaterial3d(handle.clone())),
+        };
+        entity_commands.remove::<HintHighlight>();
+    }
+}
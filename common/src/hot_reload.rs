@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use bevy_common_assets::json::JsonAssetPlugin;
+use serde::Deserialize;
+
+use crate::game_settings::{FaceColorPalette, GameColorPalette, GameSettings};
+
+const PALETTE_PATH: &str = "palettes/default.palette.json";
+
+#[derive(Deserialize, Asset, TypePath)]
+pub struct PaletteAsset {
+    player_color: [u8; 4],
+    line_color: [u8; 4],
+    face_colors: [[u8; 4]; 6],
+    background_color: [u8; 4],
+}
+
+impl From<&PaletteAsset> for GameColorPalette {
+    fn from(palette: &PaletteAsset) -> Self {
+        let to_color = |[r, g, b, a]: [u8; 4]| Color::srgba_u8(r, g, b, a);
+
+        GameColorPalette {
+            player_color: to_color(palette.player_color),
+            line_color: to_color(palette.line_color),
+            face_colors: FaceColorPalette {
+                colors: palette.face_colors.map(to_color),
+            },
+            background_color: to_color(palette.background_color),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PaletteHandle(Handle<PaletteAsset>);
+
+#[derive(Default)]
+pub struct HotReloadPlugin;
+
+impl Plugin for HotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(JsonAssetPlugin::<PaletteAsset>::new(&["palette.json"]))
+            .add_systems(Startup, load_palette)
+            .add_systems(Update, apply_palette);
+    }
+}
+
+fn load_palette(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PaletteHandle(asset_server.load(PALETTE_PATH)));
+}
+
+fn apply_palette(
+    mut palette_events: EventReader<AssetEvent<PaletteAsset>>,
+    palette_handle: Res<PaletteHandle>,
+    palettes: Res<Assets<PaletteAsset>>,
+    mut game_settings: ResMut<GameSettings>,
+) {
+    for event in palette_events.read() {
+        let PaletteHandle(handle) = &*palette_handle;
+
+        if !event.is_loaded_with_dependencies(handle) && !event.is_modified(handle) {
+            continue;
+        }
+
+        if let Some(palette) = palettes.get(handle) {
+            game_settings.palette = palette.into();
+        }
+    }
+}
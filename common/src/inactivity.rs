@@ -0,0 +1,141 @@
+use bevy::{input::mouse::MouseWheel, prelude::*};
+
+use crate::{
+    controller::ControllerState,
+    controller_screen_position::ControllerScreenPosition,
+    game_settings::GameSettings,
+    game_state::GameState,
+    ui::message::{MessagePopup, MessagePopupUpperMarker},
+};
+
+const WARNING_SECONDS: f32 = 5.0;
+
+#[derive(Resource, Default)]
+pub struct Inactivity {
+    idle_seconds: f32,
+    warned: bool,
+}
+
+pub fn inactivity_timeout_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.inactivity_timeout_seconds.is_some()
+}
+
+pub fn reset(mut inactivity: ResMut<Inactivity>) {
+    *inactivity = Inactivity::default();
+}
+
+// Sends an unattended puzzle back to the selector, so a kiosk is ready for the next person.
+// Time spent mid-solve never counts, however long the gesture lasts.
+pub fn update(
+    mut inactivity: ResMut<Inactivity>,
+    controller_screen_position_query: Query<(), Changed<ControllerScreenPosition>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    controller_state: Option<Res<State<ControllerState>>>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    game_settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let Some(timeout_seconds) = game_settings.inactivity_timeout_seconds else {
+        return;
+    };
+
+    let solving = controller_state.is_some_and(|state| *state.get() == ControllerState::Solving);
+    let had_input = !controller_screen_position_query.is_empty()
+        || keys.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || mouse_wheel_reader.read().count() > 0;
+
+    if solving || had_input {
+        *inactivity = Inactivity::default();
+        return;
+    }
+
+    inactivity.idle_seconds += time.delta_secs();
+
+    if !inactivity.warned && inactivity.idle_seconds > timeout_seconds - WARNING_SECONDS {
+        inactivity.warned = true;
+        if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+            message_popup.0 = "returning to level select · tap to stay".to_string();
+        }
+    }
+
+    if inactivity.idle_seconds > timeout_seconds {
+        info!(target: "mazonic::inactivity", "No input for {:.0}s; returning to the selector", timeout_seconds);
+        *inactivity = Inactivity::default();
+        game_state.set(GameState::Selector);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    const TIMEOUT_SECONDS: f32 = 30.0;
+
+    fn idle_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Inactivity>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<ButtonInput<MouseButton>>();
+        world.init_resource::<Events<MouseWheel>>();
+        world.init_resource::<NextState<GameState>>();
+        world.init_resource::<Time>();
+        world.insert_resource(GameSettings {
+            inactivity_timeout_seconds: Some(TIMEOUT_SECONDS),
+            ..default()
+        });
+        world.spawn((MessagePopup::default(), MessagePopupUpperMarker));
+        world
+    }
+
+    fn idle_for(world: &mut World, seconds: f32) {
+        world.resource_mut::<Time>().advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_once(update).unwrap();
+    }
+
+    fn returning_to_selector(world: &World) -> bool {
+        matches!(*world.resource::<NextState<GameState>>(), NextState::Pending(GameState::Selector))
+    }
+
+    fn message(world: &mut World) -> String {
+        world.query::<&MessagePopup>().single(world).0.clone()
+    }
+
+    #[test]
+    fn idling_past_the_timeout_returns_to_the_selector() {
+        let mut world = idle_world();
+
+        idle_for(&mut world, TIMEOUT_SECONDS - WARNING_SECONDS - 1.0);
+        assert!(message(&mut world).is_empty());
+
+        idle_for(&mut world, 2.0);
+        assert!(!message(&mut world).is_empty());
+        assert!(!returning_to_selector(&world));
+
+        idle_for(&mut world, WARNING_SECONDS);
+        assert!(returning_to_selector(&world));
+    }
+
+    #[test]
+    fn input_before_the_timeout_starts_the_wait_again() {
+        let mut world = idle_world();
+
+        idle_for(&mut world, TIMEOUT_SECONDS - 1.0);
+        world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Space);
+        idle_for(&mut world, 0.1);
+        world.resource_mut::<ButtonInput<KeyCode>>().clear();
+
+        idle_for(&mut world, TIMEOUT_SECONDS - 1.0);
+        assert!(!returning_to_selector(&world));
+
+        idle_for(&mut world, 2.0);
+        assert!(returning_to_selector(&world));
+    }
+}
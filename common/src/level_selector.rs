@@ -8,16 +8,18 @@ use bevy::{
 use bevy_hanabi::{EffectMaterial, ParticleEffectBundle};
 use bevy_rapier3d::prelude::*;
 use chrono::Utc;
+use itertools::Itertools;
 
 use crate::{
     assets::{
-        material_handles::MaterialHandles,
+        material_handles::{MaterialHandles, MelodyEmphasis, SelectorHandles},
         mesh_generators::{FaceMeshGenerator, TriangleFaceMeshGenerator},
         mesh_handles::MeshHandles,
         shaders::{MenuSelectionHoverShader, PulsingShader},
-    }, camera::{CameraTarget, MainCamera}, constants::{SQRT_3, SYMBOL_TEXTURE_DIMENSIONS}, controller_screen_position::ControllerScreenPosition, effects::musical_notes::{MusicalNoteEffectColor, MusicalNoteEffectHandle, MusicalNoteImageHandles, MusicalNoteMarker}, game_save::{
+        symbol_sheet::SymbolSheet,
+    }, camera::{CameraTarget, MainCamera}, constants::SQRT_3, controller_screen_position::ControllerScreenPosition, effects::musical_notes::{MusicalNoteEffectColor, MusicalNoteEffectHandle, MusicalNoteImageHandles, MusicalNoteMarker}, game_save::{
         CurrentPuzzle, LevelIndex, PuzzleIdentifier, WorkingLevelIndex
-    }, game_settings::GameSettings, game_state::GameState, levels::{Shape, LEVELS}, maze::{maze_mesh_builder::MazeMeshBuilder, mesh::get_cross_face_edge_transform}, play_statistics::PlayStatistics, shape::{icosahedron, shape_utils::compute_face_normal}, sound::Melody
+    }, favorites::{Favorites, FavoritesFilter}, game_settings::GameSettings, geometry::closest_facing, game_state::GameState, levels::{CampaignOrder, Shape, LEVELS}, maze::{maze_mesh_builder::MazeMeshBuilder, mesh::get_cross_face_edge_transform}, play_statistics::{PlayStatistics, PuzzleStatistics}, shape::{icosahedron, shape_utils::compute_face_normal}, sound::{spawn_melody_playback, Melody, MelodyPlayback}
 };
 
 const FACE_ORDER: [usize; 20] = [
@@ -25,9 +27,37 @@ const FACE_ORDER: [usize; 20] = [
 ];
 
 const EASY_DAILY_POSITION: usize = 7;
+const SELECTOR_EDGE_HEIGHT: f32 = 0.001;
 const HARD_DAILY_POSITION: usize = 15;
+const MELODY_PREVIEW_HOLD_SECONDS: f32 = 0.6;
+// Kept under half the angle between neighbouring face normals, so the closest face to the camera
+// is still the one being framed.
+const SELECTOR_TILT_ANGLE: f32 = 15.0 * std::f32::consts::PI / 180.0;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectorFraming {
+    #[default]
+    FaceOn,
+    Angled,
+}
+
+impl SelectorFraming {
+    // Returns the camera `translation_dir` and `up` used to frame a selector face.
+    pub fn camera_orientation(&self, face_transform: &Transform) -> (Vec3, Vec3) {
+        let face_on_dir = -face_transform.forward().normalize();
+        let up = *face_transform.right();
+
+        match self {
+            SelectorFraming::FaceOn => (face_on_dir, up),
+            SelectorFraming::Angled => {
+                let tilt = Quat::from_axis_angle(up.cross(face_on_dir).normalize(), SELECTOR_TILT_ANGLE);
+                (tilt * face_on_dir, tilt * up)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum SelectorOption {
     Level(LevelIndex),
     EasyDaily,
@@ -39,16 +69,62 @@ impl SelectorOption {
         let date = Utc::now();
         date.format("%Y-%m-%d").to_string()
     }
-}
 
-impl Into<PuzzleIdentifier> for SelectorOption {
-    fn into(self) -> PuzzleIdentifier {
+    pub fn puzzle_identifier(&self, campaign_order: &CampaignOrder) -> PuzzleIdentifier {
         match self {
-            SelectorOption::Level(level_index) => PuzzleIdentifier::Level(level_index),
+            SelectorOption::Level(campaign_position) => {
+                PuzzleIdentifier::Level(campaign_order.level_index(*campaign_position))
+            }
             SelectorOption::EasyDaily => PuzzleIdentifier::EasyDaily(Self::daily_level_filename()),
             SelectorOption::HardDaily => PuzzleIdentifier::HardDaily(Self::daily_level_filename()),
         }
     }
+
+    // The option whose completion opens this one up.
+    fn prerequisite(&self) -> Option<SelectorOption> {
+        match self {
+            SelectorOption::Level(0) => None,
+            SelectorOption::Level(campaign_position) => Some(SelectorOption::Level(campaign_position - 1)),
+            SelectorOption::EasyDaily => Some(SelectorOption::Level(EASY_DAILY_POSITION - 1)),
+            SelectorOption::HardDaily => Some(SelectorOption::Level(HARD_DAILY_POSITION - 1)),
+        }
+    }
+
+    fn is_unlocked(&self, working_level_index: usize) -> bool {
+        match self {
+            SelectorOption::Level(campaign_position) => *campaign_position <= working_level_index,
+            SelectorOption::EasyDaily => working_level_index >= EASY_DAILY_POSITION,
+            SelectorOption::HardDaily => working_level_index >= HARD_DAILY_POSITION,
+        }
+    }
+}
+
+fn selector_index(selector_option: &SelectorOption) -> Option<usize> {
+    SELECTOR_OPTIONS.iter().position(|option| option == selector_option)
+}
+
+// Arrows only join neighbouring faces, so an unlock between distant faces is drawn along the faces
+// laid out between them. Each pair is a (from, to) selector index.
+fn unlock_path_segments(working_level_index: usize) -> Vec<(usize, usize)> {
+    SELECTOR_OPTIONS
+        .iter()
+        .filter(|selector_option| selector_option.is_unlocked(working_level_index))
+        .filter_map(|selector_option| {
+            let prerequisite = selector_option.prerequisite()?;
+            Some((selector_index(&prerequisite)?, selector_index(selector_option)?))
+        })
+        .flat_map(|(from_index, to_index)| {
+            let (low_index, high_index) = (from_index.min(to_index), from_index.max(to_index));
+            (low_index..high_index).map(move |index| {
+                if from_index <= to_index {
+                    (index, index + 1)
+                } else {
+                    (index + 1, index)
+                }
+            })
+        })
+        .unique()
+        .collect()
 }
 
 const SELECTOR_OPTIONS: [SelectorOption; 20] = [
@@ -101,57 +177,73 @@ pub enum SelectorOverlayState {
 #[derive(Component, Clone, Debug)]
 pub struct CameraTargetTransform(Transform);
 
+#[derive(Component, Clone, Debug)]
+pub struct SelectorFaceMaterial(Handle<StandardMaterial>);
+
 #[derive(Component, Clone, Debug)]
 pub struct SelectionOverlay;
 
+// The press currently held on the selector, shared so a long press can preview a melody instead of
+// loading the level.
+#[derive(Resource, Default, Debug)]
+pub struct SelectorPress {
+    dragged: bool,
+    held_seconds: f32,
+    previewed: bool,
+}
+
 pub fn load(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     play_statistics: Res<PlayStatistics>,
     material_handles: Res<MaterialHandles>,
     mesh_handles: Res<MeshHandles>,
+    game_settings: Res<GameSettings>,
+    favorites: Res<Favorites>,
+    favorites_filter: Res<FavoritesFilter>,
+    symbol_sheet: Res<SymbolSheet>,
 ) {
-    let working_level_index = play_statistics.get_working_level();
-    let completed_puzzles = play_statistics.0
-        .iter()
-        .filter(|(_, puzzle_statistics)| puzzle_statistics.completed)
-        .map(|(puzzle_identifier, _)| puzzle_identifier)
-        .cloned()
-        .collect::<HashSet<PuzzleIdentifier>>();
+    let campaign_order = &game_settings.campaign_order;
+    let working_level_index = play_statistics.get_working_level(campaign_order);
 
     let selector_material_handles = &material_handles.selector;
+    let melody_found_symbols_handle = selector_material_handles.melody_found_symbols.get(game_settings.melody_emphasis);
     let faces = icosahedron::faces();
 
-    let tetrahedron_symbol_mesh_handle = meshes.add(coordinate_to_symbol_mesh(4, 1));
-    let cube_symbol_mesh_handle = meshes.add(coordinate_to_symbol_mesh(3, 1));
-    let octahedron_symbol_mesh_handle = meshes.add(coordinate_to_symbol_mesh(2, 1));
-    let dodecahedron_symbol_mesh_handle = meshes.add(coordinate_to_symbol_mesh(1, 1));
-    let icosahedron_symbol_mesh_handle = meshes.add(coordinate_to_symbol_mesh(0, 1));
+    let tetrahedron_symbol_mesh_handle = meshes.add(symbol_sheet.symbol_mesh("tetrahedron"));
+    let cube_symbol_mesh_handle = meshes.add(symbol_sheet.symbol_mesh("cube"));
+    let octahedron_symbol_mesh_handle = meshes.add(symbol_sheet.symbol_mesh("octahedron"));
+    let dodecahedron_symbol_mesh_handle = meshes.add(symbol_sheet.symbol_mesh("dodecahedron"));
+    let icosahedron_symbol_mesh_handle = meshes.add(symbol_sheet.symbol_mesh("icosahedron"));
 
     let number_mesh_handles = (1..10)
         .map(|number| {
-            let mesh = number_symbol_mesh(number);
+            let mesh = symbol_sheet.symbol_mesh(&number.to_string());
             let mesh_handle = meshes.add(mesh);
             (number as u8, mesh_handle)
         })
         .collect::<HashMap<u8, Handle<Mesh>>>();
 
-    let daily_symbol_mesh_handle = meshes.add(coordinate_to_symbol_mesh(0, 4));
+    let daily_symbol_mesh_handle = meshes.add(symbol_sheet.symbol_mesh("daily"));
 
     let face_local_transforms = (0..SELECTOR_OPTIONS.len())
         .map(|level_index| compute_face_transform(level_index, &faces))
         .collect::<Vec<Transform>>();
 
     for (selector_option_index, selector_option) in SELECTOR_OPTIONS.iter().enumerate() {
-        let puzzle_identifier = selector_option.clone().into(); 
-
-        let face_material_handle = match puzzle_identifier {
-            _ if completed_puzzles.contains(&puzzle_identifier) => selector_material_handles.completed.clone(),
-            PuzzleIdentifier::Level(level_index) if level_index == working_level_index => selector_material_handles.incomplete_face_colors[level_index].clone(),
-            PuzzleIdentifier::EasyDaily(_) if working_level_index >= EASY_DAILY_POSITION => selector_material_handles.incomplete_face_colors[EASY_DAILY_POSITION].clone(),
-            PuzzleIdentifier::HardDaily(_) if working_level_index >= HARD_DAILY_POSITION => selector_material_handles.incomplete_face_colors[HARD_DAILY_POSITION].clone(),
-            _ => selector_material_handles.unavailable.clone(),
-        };
+        let puzzle_identifier = selector_option.puzzle_identifier(campaign_order);
+
+        let puzzle_statistics = play_statistics.0.get(&puzzle_identifier);
+        let is_melody_discovered = puzzle_statistics
+            .map_or(false, |puzzle_statistics| puzzle_statistics.discovered_melody.is_some());
+
+        let face_material_handle = face_material(
+            selector_option,
+            puzzle_statistics,
+            working_level_index,
+            game_settings.melody_emphasis,
+            selector_material_handles,
+        );
 
         let face_index = FACE_ORDER[selector_option_index];
         let face_mesh_handle = mesh_handles.shape_mesh_handles.icosahedron[face_index].clone();
@@ -160,7 +252,7 @@ pub fn load(
         
 
         let symbol_mesh_handle = match selector_option {
-            SelectorOption::Level(level_index) => match LEVELS[*level_index].shape {
+            SelectorOption::Level(campaign_position) => match LEVELS[campaign_order.level_index(*campaign_position)].shape {
                 Shape::Tetrahedron(_) => tetrahedron_symbol_mesh_handle.clone(),
                 Shape::Cube(_) => cube_symbol_mesh_handle.clone(),
                 Shape::Octahedron(_) => octahedron_symbol_mesh_handle.clone(),
@@ -177,19 +269,22 @@ pub fn load(
         let triangle_collider =
             Collider::triangle(face_vertices[0], face_vertices[1], face_vertices[2]);
 
+        let displayed_face_material_handle = if favorites_filter.hides(&favorites, &puzzle_identifier) {
+            selector_material_handles.unavailable.clone()
+        } else {
+            face_material_handle.clone()
+        };
+
         let face_object = (
             Mesh3d(face_mesh_handle.clone()),
-            MeshMaterial3d(face_material_handle),
+            MeshMaterial3d(displayed_face_material_handle),
+            SelectorFaceMaterial(face_material_handle),
         );
 
         let selection_overlay_object = (
             Mesh3d(face_mesh_handle.clone()),
             MeshMaterial3d(selector_material_handles.selection_hover.clone()),
         );
-        
-        let is_melody_discovered = play_statistics.0
-            .get(&puzzle_identifier)
-            .map_or(false, |puzzle_statistics| puzzle_statistics.discovered_melody.is_some());
 
         commands
             .spawn(triangle_collider)
@@ -207,7 +302,7 @@ pub fn load(
                         SelectorOption::Level(level_index) => {
                             if is_melody_discovered {
                                 symbol_entity_commands.insert(MeshMaterial3d(
-                                    selector_material_handles.melody_found_selector_face.clone(),
+                                    melody_found_symbols_handle.clone(),
                                 ));
                             } else if *level_index > working_level_index {
                                 symbol_entity_commands.insert(MeshMaterial3d(
@@ -220,13 +315,13 @@ pub fn load(
                             };
 
                             let number_mesh_handle =
-                                number_mesh_handles.get(&LEVELS[*level_index].nodes_per_edge).unwrap();
+                                number_mesh_handles.get(&LEVELS[campaign_order.level_index(*level_index)].nodes_per_edge).unwrap();
                             let mut number_entity_commands =
                                 parent.spawn(Mesh3d(number_mesh_handle.clone()));
 
                             if is_melody_discovered {
                                 number_entity_commands.insert(MeshMaterial3d(
-                                    selector_material_handles.melody_found_selector_face.clone(),
+                                    melody_found_symbols_handle.clone(),
                                 ));
                             } else if *level_index > working_level_index {
                                 number_entity_commands.insert(MeshMaterial3d(
@@ -240,7 +335,7 @@ pub fn load(
                         }
                         SelectorOption::EasyDaily | SelectorOption::HardDaily if is_melody_discovered => {
                             symbol_entity_commands.insert(MeshMaterial3d(
-                                    selector_material_handles.melody_found_selector_face.clone(),
+                                    melody_found_symbols_handle.clone(),
                             ));
                         }
                         SelectorOption::EasyDaily if working_level_index >= EASY_DAILY_POSITION => {
@@ -280,19 +375,11 @@ pub fn load(
     let mesh_builder = MazeMeshBuilder::level_selector();
     let edge_mesh_handle = meshes.add(mesh_builder.one_way_cross_face_edge());
 
-    let total_path_size = working_level_index + if working_level_index >= HARD_DAILY_POSITION { 
-        2
-    } else if working_level_index >= EASY_DAILY_POSITION { 
-        1
-    } else { 
-        0
-    };
+    let path_segments = unlock_path_segments(working_level_index);
 
-    println!("total path size: {}", total_path_size);
+    debug!(target: "mazonic::selector", "unlock path segments: {:?}", path_segments);
 
-    for (from_level_index, to_level_index) in
-        (0..).zip(1..SELECTOR_OPTIONS.len()).take(total_path_size)
-    {
+    for (from_level_index, to_level_index) in path_segments {
         let from_transform = face_local_transforms[from_level_index];
         let to_transform = face_local_transforms[to_level_index];
 
@@ -301,6 +388,7 @@ pub fn load(
             -*from_transform.forward(),
             to_transform.translation,
             -*to_transform.forward(),
+            SELECTOR_EDGE_HEIGHT,
         );
 
         commands
@@ -315,6 +403,32 @@ pub fn load(
     commands.spawn(SelectedLevel(None)).insert(SelectorEntity);
 }
 
+pub fn apply_favorites_filter(
+    mut face_query: Query<(
+        &SelectableLevel,
+        &SelectorFaceMaterial,
+        &mut MeshMaterial3d<StandardMaterial>,
+    )>,
+    favorites: Res<Favorites>,
+    favorites_filter: Res<FavoritesFilter>,
+    material_handles: Res<MaterialHandles>,
+    game_settings: Res<GameSettings>,
+) {
+    if !favorites_filter.is_changed() {
+        return;
+    }
+
+    for (SelectableLevel(selector_option), SelectorFaceMaterial(face_material_handle), mut material) in face_query.iter_mut() {
+        let puzzle_identifier = selector_option.puzzle_identifier(&game_settings.campaign_order);
+
+        material.0 = if favorites_filter.hides(&favorites, &puzzle_identifier) {
+            material_handles.selector.unavailable.clone()
+        } else {
+            face_material_handle.clone()
+        };
+    }
+}
+
 pub fn despawn(
     mut commands: Commands,
     selector_entities: Query<Entity, With<SelectorEntity>>,
@@ -368,28 +482,6 @@ fn compute_face_transform(level_index: usize, faces: &[[Vec3; 3]; 20]) -> Transf
         .with_translation(face_center + face_normal * 0.003)
 }
 
-fn number_symbol_mesh(number: u8) -> Mesh {
-    let y_coord = 2 + (number - 1) / 5;
-    let x_coord = (number - 1) % 5;
-    coordinate_to_symbol_mesh(x_coord, y_coord)
-}
-
-pub fn coordinate_to_symbol_mesh(x_coord: u8, y_coord: u8) -> Mesh {
-    let coordinate = UVec2::new(x_coord.into(), y_coord.into());
-    let max_uv = (coordinate.as_vec2() + Vec2::ONE) / SYMBOL_TEXTURE_DIMENSIONS;
-    let min_uv = coordinate.as_vec2() / SYMBOL_TEXTURE_DIMENSIONS;
-
-    let uvs = vec![
-        min_uv.to_array(),
-        [min_uv.x, max_uv.y],
-        max_uv.to_array(),
-        [max_uv.x, min_uv.y],
-    ];
-
-    let symbol_mesh = Mesh::from(Rectangle::new(1.0, 1.0));
-    symbol_mesh.with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
-}
-
 pub fn set_selector_state(
     controller_screen_position_query: Query<
         &ControllerScreenPosition,
@@ -429,13 +521,26 @@ pub fn update_interactables(
     mut current_level_index_query: Query<&mut CurrentPuzzle>,
     completed_level_index_query: Query<&WorkingLevelIndex>,
     controller_screen_position_query: Query<&ControllerScreenPosition>,
+    game_settings: Res<GameSettings>,
+    (favorites, favorites_filter): (Res<Favorites>, Res<FavoritesFilter>),
     mut start_touch_entity: Local<Option<Entity>>,
     mut previous_controller_screen_position: Local<ControllerScreenPosition>,
+    mut press_start_position: Local<Option<Vec2>>,
+    mut selector_press: ResMut<SelectorPress>,
+    mut last_touch_entity: Local<Option<Entity>>,
 ) {
     let Ok(controller_screen_position) = controller_screen_position_query.get_single() else {
         return;
     };
 
+    track_press(
+        *previous_controller_screen_position,
+        *controller_screen_position,
+        &mut press_start_position,
+        &mut selector_press,
+        game_settings.selector_tap_threshold,
+    );
+
     let Ok(window) = primary_window.get_single() else {
         return;
     };
@@ -500,6 +605,8 @@ pub fn update_interactables(
 
     let selected_face_pressed = touch_intersected_entity == *start_touch_entity && touch_matches_window_center_entity;
 
+    let release_entity = release_entity(*controller_screen_position, touch_intersected_entity, &mut last_touch_entity);
+
     for (entity, mut overlay_state, SelectableLevel(selector_puzzle)) in overlay_states_query.iter_mut()
     {
 
@@ -507,7 +614,7 @@ pub fn update_interactables(
             SelectorOption::Level(level_index) => level_index <= working_level_index,
             SelectorOption::EasyDaily => *working_level_index >= EASY_DAILY_POSITION,
             SelectorOption::HardDaily => *working_level_index >= HARD_DAILY_POSITION,
-        };
+        } && !favorites_filter.hides(&favorites, &selector_puzzle.puzzle_identifier(&game_settings.campaign_order));
 
         let interacted_and_matches_touch = *overlay_state != SelectorOverlayState::None 
             && selected_face_pressed;
@@ -523,12 +630,9 @@ pub fn update_interactables(
             SelectorOverlayState::None 
         };
 
-        if *overlay_state == SelectorOverlayState::Pressed
-            && new_overlay_state == SelectorOverlayState::Hovered
-            && start_touch_entity.is_none()
-        {
+        if release_selects(&overlay_state, &new_overlay_state, *start_touch_entity, release_entity, entity, &selector_press) {
 
-            *current_level_index_query.single_mut() = CurrentPuzzle(selector_puzzle.clone().into());
+            *current_level_index_query.single_mut() = CurrentPuzzle(selector_puzzle.puzzle_identifier(&game_settings.campaign_order));
             let next_game_state = match selector_puzzle {
                 SelectorOption::Level(level_index) => GameState::Puzzle,
                 SelectorOption::EasyDaily | SelectorOption::HardDaily => GameState::LoadingRemoteLevel,
@@ -545,6 +649,133 @@ pub fn update_interactables(
     *previous_controller_screen_position = *controller_screen_position;
 }
 
+// Holding a pressed face whose melody has been discovered plays that melody back in place.
+pub fn preview_melody(
+    mut commands: Commands,
+    overlay_states_query: Query<(&SelectorOverlayState, &SelectableLevel)>,
+    melody_playback_query: Query<(), With<MelodyPlayback>>,
+    mut selector_press: ResMut<SelectorPress>,
+    play_statistics: Res<PlayStatistics>,
+    game_settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let Some((_, SelectableLevel(selector_option))) = overlay_states_query
+        .iter()
+        .find(|(overlay_state, _)| **overlay_state == SelectorOverlayState::Pressed)
+    else {
+        selector_press.held_seconds = 0.0;
+        return;
+    };
+
+    if selector_press.dragged || selector_press.previewed {
+        return;
+    }
+
+    selector_press.held_seconds += time.delta_secs();
+    if selector_press.held_seconds < MELODY_PREVIEW_HOLD_SECONDS {
+        return;
+    }
+
+    let puzzle_identifier = selector_option.puzzle_identifier(&game_settings.campaign_order);
+    let Some(discovered_melody) = play_statistics.0
+        .get(&puzzle_identifier)
+        .and_then(|puzzle_statistics| puzzle_statistics.discovered_melody.as_ref())
+    else {
+        return;
+    };
+
+    // The release that follows belongs to the preview, so it must not load the level.
+    selector_press.previewed = true;
+
+    if !melody_playback_query.is_empty() {
+        return;
+    }
+
+    debug!(target: "mazonic::level_selector", "Previewing the melody of {:?}", puzzle_identifier);
+    spawn_melody_playback(&mut commands, &discovered_melody.melody, game_settings.transpose_semitones);
+}
+
+fn face_material(
+    selector_option: &SelectorOption,
+    puzzle_statistics: Option<&PuzzleStatistics>,
+    working_level_index: usize,
+    melody_emphasis: MelodyEmphasis,
+    selector_material_handles: &SelectorHandles,
+) -> Handle<StandardMaterial> {
+    let is_melody_discovered = puzzle_statistics.is_some_and(|puzzle_statistics| puzzle_statistics.discovered_melody.is_some());
+    let completed = puzzle_statistics.is_some_and(|puzzle_statistics| puzzle_statistics.completed);
+    let perfect = completed && puzzle_statistics.is_some_and(|puzzle_statistics| puzzle_statistics.perfect);
+
+    match selector_option {
+        _ if is_melody_discovered && melody_emphasis == MelodyEmphasis::Bold => selector_material_handles.melody_found.clone(),
+        _ if perfect => selector_material_handles.perfect.clone(),
+        _ if completed => selector_material_handles.completed.clone(),
+        SelectorOption::Level(campaign_position) if *campaign_position == working_level_index => selector_material_handles.incomplete_face_colors[*campaign_position].clone(),
+        SelectorOption::EasyDaily if working_level_index >= EASY_DAILY_POSITION => selector_material_handles.incomplete_face_colors[EASY_DAILY_POSITION].clone(),
+        SelectorOption::HardDaily if working_level_index >= HARD_DAILY_POSITION => selector_material_handles.incomplete_face_colors[HARD_DAILY_POSITION].clone(),
+        _ => selector_material_handles.unavailable.clone(),
+    }
+}
+
+// The release itself has no position, so the face under the last pressed position stands in for it.
+fn release_entity(
+    controller_screen_position: ControllerScreenPosition,
+    touch_intersected_entity: Option<Entity>,
+    last_touch_entity: &mut Option<Entity>,
+) -> Option<Entity> {
+    match controller_screen_position {
+        ControllerScreenPosition::Position(_) => {
+            *last_touch_entity = touch_intersected_entity;
+            None
+        }
+        ControllerScreenPosition::None => last_touch_entity.take(),
+    }
+}
+
+// A face is selected only by releasing over it while it is still the pressed, centered face, after a
+// press that neither dragged nor previewed a melody.
+fn release_selects(
+    overlay_state: &SelectorOverlayState,
+    new_overlay_state: &SelectorOverlayState,
+    start_touch_entity: Option<Entity>,
+    release_entity: Option<Entity>,
+    entity: Entity,
+    selector_press: &SelectorPress,
+) -> bool {
+    *overlay_state == SelectorOverlayState::Pressed
+        && *new_overlay_state == SelectorOverlayState::Hovered
+        && start_touch_entity.is_none()
+        && release_entity == Some(entity)
+        && !selector_press.dragged
+        && !selector_press.previewed
+}
+
+// A new press starts afresh, and a held press becomes a drag once it strays past the threshold.
+fn track_press(
+    previous_controller_screen_position: ControllerScreenPosition,
+    controller_screen_position: ControllerScreenPosition,
+    press_start_position: &mut Option<Vec2>,
+    selector_press: &mut SelectorPress,
+    threshold: f32,
+) {
+    match (previous_controller_screen_position, controller_screen_position) {
+        (ControllerScreenPosition::None, ControllerScreenPosition::Position(position)) => {
+            *press_start_position = Some(position);
+            *selector_press = SelectorPress::default();
+        }
+        (_, ControllerScreenPosition::Position(position)) => {
+            selector_press.dragged |= press_start_position.map_or(false, |start_position| {
+                is_drag(start_position, position, threshold)
+            });
+        }
+        _ => {}
+    }
+}
+
+fn is_drag(start_position: Vec2, position: Vec2, threshold: f32) -> bool {
+    start_position.distance(position) > threshold
+}
+
 pub fn update_selection_overlay(
     changed_overlay_state_query: Query<
         (&SelectorOverlayState, &Children),
@@ -594,7 +825,8 @@ pub fn set_initial_camera_target(
 
     let CurrentPuzzle(puzzle_identifier) = current_level_index_query.single();
 
-    println!(
+    debug!(
+        target: "mazonic::selector",
         "Setting selector look at level index: {:?}",
         puzzle_identifier
     );
@@ -603,7 +835,7 @@ pub fn set_initial_camera_target(
         .iter()
         .filter(|(_, SelectableLevel(selector_level))| {
             match (selector_level, puzzle_identifier) {
-                (SelectorOption::Level(selector_index), PuzzleIdentifier::Level(level_index)) => selector_index == level_index,
+                (SelectorOption::Level(campaign_position), PuzzleIdentifier::Level(level_index)) => game_settings.campaign_order.level_index(*campaign_position) == *level_index,
                 (SelectorOption::EasyDaily, PuzzleIdentifier::EasyDaily(_)) => true,
                 (SelectorOption::HardDaily, PuzzleIdentifier::HardDaily(_)) => true,
                 _ => false,
@@ -614,9 +846,12 @@ pub fn set_initial_camera_target(
         .next()
         .unwrap();
 
-    camera_target.translation_dir = *-face_transform.forward();
+    let (translation_dir, up) = game_settings.selector_framing.camera_orientation(face_transform);
+
+    camera_target.translation_dir = translation_dir;
+    camera_target.reset_zoom_bounds();
     camera_target.translation_norm = game_settings.camera_distance;
-    camera_target.up = *face_transform.right();
+    camera_target.up = up;
 }
 
 pub fn set_camera_target_to_closest_face(
@@ -628,20 +863,290 @@ pub fn set_camera_target_to_closest_face(
 
     let camera_forward = camera_transform.forward();
 
-    let Some(CameraTargetTransform(closest_face_transform)) =
-        selectable
-            .iter()
-            .min_by_key(|CameraTargetTransform(selectable_transform)| {
-                let face_normal = -Vec3::from(selectable_transform.forward());
-                (camera_forward.dot(face_normal) * 100.0) as i32
-            })
-    else {
+    let Some(CameraTargetTransform(closest_face_transform)) = closest_facing(
+        camera_forward.into(),
+        selectable.iter(),
+        |CameraTargetTransform(selectable_transform)| -Vec3::from(selectable_transform.forward()),
+    ) else {
         return;
     };
 
-    println!("Setting selector camera target to closest face");
+    debug!(target: "mazonic::selector", "Setting selector camera target to closest face");
+
+    let (translation_dir, up) = game_settings
+        .selector_framing
+        .camera_orientation(closest_face_transform);
 
-    camera_target.translation_dir = -closest_face_transform.forward().normalize();
+    camera_target.translation_dir = translation_dir;
+    camera_target.reset_zoom_bounds();
     camera_target.translation_norm = game_settings.camera_distance;
-    camera_target.up = *closest_face_transform.right();
+    camera_target.up = up;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{game_save::DiscoveredMelody, sound::Notes};
+
+    use super::*;
+
+    const THRESHOLD: f32 = 12.0;
+
+    // Feeds a press through each position and then releases it, returning the press as it stood.
+    fn press_through(positions: &[Vec2]) -> SelectorPress {
+        let mut previous = ControllerScreenPosition::None;
+        let mut press_start_position = None;
+        let mut selector_press = SelectorPress::default();
+
+        let samples = positions
+            .iter()
+            .map(|position| ControllerScreenPosition::Position(*position))
+            .chain([ControllerScreenPosition::None]);
+        for current in samples {
+            track_press(previous, current, &mut press_start_position, &mut selector_press, THRESHOLD);
+            previous = current;
+        }
+
+        selector_press
+    }
+
+    #[test]
+    fn jitter_within_the_threshold_is_still_a_tap() {
+        let start = Vec2::new(200.0, 300.0);
+        let press = press_through(&[start, start + Vec2::new(3.0, -2.0), start + Vec2::new(-5.0, 4.0), start]);
+
+        assert!(!press.dragged);
+    }
+
+    #[test]
+    fn moving_past_the_threshold_is_a_drag() {
+        let start = Vec2::new(200.0, 300.0);
+        let press = press_through(&[start, start + Vec2::new(8.0, 0.0), start + Vec2::new(40.0, 10.0)]);
+
+        assert!(press.dragged);
+    }
+
+    #[test]
+    fn returning_to_the_start_does_not_undo_a_drag() {
+        let start = Vec2::new(200.0, 300.0);
+        let press = press_through(&[start, start + Vec2::new(0.0, 60.0), start]);
+
+        assert!(press.dragged);
+    }
+
+    #[test]
+    fn a_new_press_forgets_the_previous_drag() {
+        let start = Vec2::new(200.0, 300.0);
+        let mut press_start_position = None;
+        let mut selector_press = SelectorPress { dragged: true, held_seconds: 2.0, previewed: true };
+
+        track_press(
+            ControllerScreenPosition::None,
+            ControllerScreenPosition::Position(start),
+            &mut press_start_position,
+            &mut selector_press,
+            THRESHOLD,
+        );
+
+        assert_eq!(press_start_position, Some(start));
+        assert!(!selector_press.dragged && !selector_press.previewed);
+    }
+
+    #[test]
+    fn drag_threshold_is_exclusive() {
+        assert!(!is_drag(Vec2::ZERO, Vec2::new(THRESHOLD, 0.0), THRESHOLD));
+        assert!(is_drag(Vec2::ZERO, Vec2::new(THRESHOLD + 0.1, 0.0), THRESHOLD));
+    }
+
+    // Presses face `pressed`, moves over each face in `moved_over`, then releases, returning whether
+    // `pressed` is selected.
+    fn release_over(pressed: Entity, moved_over: &[Option<Entity>], selector_press: &SelectorPress) -> bool {
+        let mut last_touch_entity = None;
+        let position = ControllerScreenPosition::Position(Vec2::ZERO);
+
+        release_entity(position, Some(pressed), &mut last_touch_entity);
+        for touch_intersected_entity in moved_over {
+            release_entity(position, *touch_intersected_entity, &mut last_touch_entity);
+        }
+        let released_over = release_entity(ControllerScreenPosition::None, None, &mut last_touch_entity);
+
+        release_selects(
+            &SelectorOverlayState::Pressed,
+            &SelectorOverlayState::Hovered,
+            None,
+            released_over,
+            pressed,
+            selector_press,
+        )
+    }
+
+    #[test]
+    fn releasing_over_the_pressed_face_selects_it() {
+        let face = Entity::from_raw(1);
+
+        assert!(release_over(face, &[], &SelectorPress::default()));
+        assert!(release_over(face, &[Some(face)], &SelectorPress::default()));
+    }
+
+    #[test]
+    fn dragging_away_before_release_does_not_select() {
+        let face = Entity::from_raw(1);
+        let other_face = Entity::from_raw(2);
+
+        assert!(!release_over(face, &[Some(other_face)], &SelectorPress::default()));
+        assert!(!release_over(face, &[None], &SelectorPress::default()));
+    }
+
+    #[test]
+    fn dragging_away_and_back_within_the_tolerance_still_selects() {
+        let face = Entity::from_raw(1);
+        let other_face = Entity::from_raw(2);
+
+        assert!(release_over(face, &[Some(other_face), Some(face)], &SelectorPress::default()));
+        assert!(!release_over(
+            face,
+            &[Some(other_face), Some(face)],
+            &SelectorPress { dragged: true, ..default() }
+        ));
+    }
+
+    #[test]
+    fn releasing_after_a_melody_preview_does_not_select() {
+        let face = Entity::from_raw(1);
+
+        assert!(!release_over(face, &[], &SelectorPress { previewed: true, ..default() }));
+    }
+
+    fn selector_handles() -> SelectorHandles {
+        SelectorHandles::reserve(&Assets::default(), &Assets::default(), &Assets::default())
+    }
+
+    #[test]
+    fn a_perfect_completed_level_resolves_to_the_perfect_material() {
+        let handles = selector_handles();
+        let perfect = PuzzleStatistics { perfect: true, ..PuzzleStatistics::completed() };
+        let completed = PuzzleStatistics::completed();
+        let unfinished = PuzzleStatistics { perfect: true, ..PuzzleStatistics::default() };
+
+        let material = |puzzle_statistics| face_material(&SelectorOption::Level(3), Some(puzzle_statistics), 3, MelodyEmphasis::Standard, &handles);
+
+        assert_eq!(material(&perfect), handles.perfect);
+        assert_eq!(material(&completed), handles.completed);
+        assert_eq!(material(&unfinished), handles.incomplete_face_colors[3]);
+    }
+
+    #[test]
+    fn only_bold_emphasis_recolors_a_face_with_a_discovered_melody() {
+        let handles = selector_handles();
+        let melody_found = PuzzleStatistics {
+            discovered_melody: Some(DiscoveredMelody {
+                melody: Melody { name: "test".to_string(), notes: Notes(vec![]), bpm: 120.0 },
+                room_ids: vec![],
+            }),
+            ..PuzzleStatistics::completed()
+        };
+
+        let material = |melody_emphasis| face_material(&SelectorOption::Level(3), Some(&melody_found), 3, melody_emphasis, &handles);
+
+        assert_eq!(material(MelodyEmphasis::Bold), handles.melody_found);
+        assert_eq!(material(MelodyEmphasis::Standard), handles.completed);
+        assert_eq!(material(MelodyEmphasis::Subtle), handles.completed);
+    }
+
+    #[test]
+    fn unlock_arrows_form_one_path_through_every_unlocked_face() {
+        for working_level_index in 0..=LEVELS.len() {
+            let last_unlocked = SELECTOR_OPTIONS
+                .iter()
+                .rposition(|selector_option| selector_option.is_unlocked(working_level_index))
+                .unwrap();
+
+            let mut segments = unlock_path_segments(working_level_index);
+            segments.sort();
+
+            assert_eq!(
+                segments,
+                (0..last_unlocked).map(|index| (index, index + 1)).collect_vec(),
+                "working level {working_level_index}"
+            );
+        }
+    }
+
+    #[test]
+    fn the_unlock_path_visits_levels_in_campaign_order() {
+        let working_level_index = LEVELS.len() - 1;
+        let path = unlock_path_segments(working_level_index)
+            .into_iter()
+            .sorted()
+            .flat_map(|(from_index, to_index)| [from_index, to_index])
+            .dedup()
+            .collect_vec();
+
+        for campaign_order in [CampaignOrder::Standard, CampaignOrder::Gentle, CampaignOrder::Challenge] {
+            let visited = path
+                .iter()
+                .filter(|index| matches!(SELECTOR_OPTIONS[**index], SelectorOption::Level(_)))
+                .map(|index| SELECTOR_OPTIONS[*index].puzzle_identifier(&campaign_order))
+                .collect_vec();
+
+            let expected = campaign_order
+                .level_indices()
+                .iter()
+                .map(|level_index| PuzzleIdentifier::Level(*level_index))
+                .collect_vec();
+
+            assert_eq!(visited, expected, "{campaign_order:?}");
+        }
+    }
+
+    fn assert_close(actual: Vec3, expected: Vec3) {
+        assert!(actual.distance(expected) < 1e-5, "{actual} is not {expected}");
+    }
+
+    #[test]
+    fn face_on_framing_looks_straight_at_the_face() {
+        let (translation_dir, up) = SelectorFraming::FaceOn.camera_orientation(&Transform::IDENTITY);
+
+        assert_close(translation_dir, Vec3::Z);
+        assert_close(up, Vec3::X);
+    }
+
+    #[test]
+    fn angled_framing_tilts_the_view_towards_the_face_up() {
+        let (translation_dir, up) = SelectorFraming::Angled.camera_orientation(&Transform::IDENTITY);
+        let (sin, cos) = SELECTOR_TILT_ANGLE.sin_cos();
+
+        assert_close(translation_dir, Vec3::new(-sin, 0.0, cos));
+        assert_close(up, Vec3::new(cos, 0.0, sin));
+    }
+
+    #[test]
+    fn angled_framing_stays_within_the_tilt_of_any_face() {
+        let face_transform = Transform::from_xyz(0.3, 0.5, 0.8).looking_to(Vec3::new(-1.0, 2.0, 0.5), Vec3::Y);
+        let (face_on_dir, face_on_up) = SelectorFraming::FaceOn.camera_orientation(&face_transform);
+        let (translation_dir, up) = SelectorFraming::Angled.camera_orientation(&face_transform);
+
+        assert!((translation_dir.angle_between(face_on_dir) - SELECTOR_TILT_ANGLE).abs() < 1e-4);
+        assert!((up.angle_between(face_on_up) - SELECTOR_TILT_ANGLE).abs() < 1e-4);
+        assert!(translation_dir.dot(up).abs() < 1e-5);
+    }
+
+    #[test]
+    fn every_campaign_level_has_a_selector_face() {
+        for campaign_position in 0..LEVELS.len() {
+            assert!(
+                selector_index(&SelectorOption::Level(campaign_position)).is_some(),
+                "campaign position {campaign_position}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_a_pressed_face_can_be_selected() {
+        let face = Entity::from_raw(1);
+        let press = SelectorPress::default();
+
+        assert!(!release_selects(&SelectorOverlayState::Hovered, &SelectorOverlayState::Hovered, None, Some(face), face, &press));
+        assert!(!release_selects(&SelectorOverlayState::Pressed, &SelectorOverlayState::None, None, Some(face), face, &press));
+        assert!(!release_selects(&SelectorOverlayState::Pressed, &SelectorOverlayState::Hovered, Some(face), Some(face), face, &press));
+    }
 }
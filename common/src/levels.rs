@@ -1,13 +1,23 @@
+use std::sync::OnceLock;
+
 use bevy::{prelude::*, utils::HashSet};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    constants::{SQRT_3, TAN_27},
+    constants::{PHI, SQRT_3, TAN_27},
+    game_save::LevelIndex,
     maze::border_type::BorderType,
     room::Face,
     shape::{cube, dodecahedron, icosahedron, octahedron, tetrahedron},
 };
 
+// Difficulty score terms. Shape complexity grows with the face count and with how flat the
+// dihedral angle is, since shallow folds make it harder to tell faces apart. Subdivision is
+// weighted by the approximate room count, face count times nodes per edge squared.
+pub const DIFFICULTY_DIHEDRAL_WEIGHT: f32 = 0.5;
+pub const DIFFICULTY_SHAPE_WEIGHT: f32 = 1.0;
+pub const DIFFICULTY_ROOM_WEIGHT: f32 = 0.25;
+
 #[derive(Component)]
 pub struct PuzzleEntityMarker;
 
@@ -26,6 +36,45 @@ pub enum Shape {
     Icosahedron(icosahedron::Coloring),
 }
 
+impl Shape {
+    pub fn circumradius(&self) -> f32 {
+        let circumradius_factor = match self {
+            Shape::Tetrahedron(_) => 1.5_f32.sqrt(),
+            Shape::Cube(_) => 3.0_f32.sqrt(),
+            Shape::Octahedron(_) => 2.0_f32.sqrt(),
+            Shape::Dodecahedron(_) => 3.0_f32.sqrt() * PHI,
+            Shape::Icosahedron(_) => PHI * (3.0 - PHI).sqrt(),
+        };
+
+        circumradius_factor / 2.0
+    }
+
+    pub fn face_count(&self) -> usize {
+        match self {
+            Shape::Tetrahedron(_) => 4,
+            Shape::Cube(_) => 6,
+            Shape::Octahedron(_) => 8,
+            Shape::Dodecahedron(_) => 12,
+            Shape::Icosahedron(_) => 20,
+        }
+    }
+
+    pub fn dihedral_angle(&self) -> f32 {
+        match self {
+            Shape::Tetrahedron(_) => (1.0_f32 / 3.0).acos(),
+            Shape::Cube(_) => std::f32::consts::FRAC_PI_2,
+            Shape::Octahedron(_) => (-1.0_f32 / 3.0).acos(),
+            Shape::Dodecahedron(_) => (-5.0_f32.sqrt() / 5.0).acos(),
+            Shape::Icosahedron(_) => (-5.0_f32.sqrt() / 3.0).acos(),
+        }
+    }
+
+    pub fn complexity(&self) -> f32 {
+        let flatness = self.dihedral_angle() / std::f32::consts::PI;
+        self.face_count() as f32 * (1.0 + DIFFICULTY_DIHEDRAL_WEIGHT * flatness)
+    }
+}
+
 #[derive(Component, Clone, Debug)]
 pub struct GameLevel {
     pub shape: Shape,
@@ -63,6 +112,16 @@ impl GameLevel {
         indices.into_iter().collect()
     }
 
+    // Only uses what is known before the maze is loaded; see `MazeLevelData::difficulty_score`
+    // for the score including the solution and junctions.
+    pub fn difficulty_score(&self) -> f32 {
+        let approximate_rooms =
+            self.shape.face_count() as f32 * (self.nodes_per_edge as f32).powi(2);
+
+        DIFFICULTY_SHAPE_WEIGHT * self.shape.complexity()
+            + DIFFICULTY_ROOM_WEIGHT * approximate_rooms
+    }
+
     pub fn node_distance(&self) -> f32 {
         match &self.shape {
             Shape::Tetrahedron(_) | Shape::Octahedron(_) | Shape::Icosahedron(_) => {
@@ -108,7 +167,7 @@ pub const LEVELS: [GameLevel; 18] = [
     GameLevel::octahedron(4, octahedron::Coloring::Stripes([0, 1, 2, 4])),
     GameLevel::tetrahedron(6, tetrahedron::Coloring::Full([0, 2, 3, 4])),
     GameLevel::cube(4, cube::Coloring::Full([1, 2, 3])),
-    GameLevel::tetrahedron(7, tetrahedron::Coloring::Full([0, 1, 3, 4])),
+    GameLevel::tetrahedron(7, tetrahedron::Coloring::Stripes([0, 1, 3])),
     GameLevel::octahedron(5, octahedron::Coloring::CrissCross([2, 4])),
     GameLevel::icosahedron(3, icosahedron::Coloring::Tri([0, 1, 2])),
     GameLevel::tetrahedron(8, tetrahedron::Coloring::Dual([1, 2])),
@@ -119,3 +178,117 @@ pub const LEVELS: [GameLevel; 18] = [
     GameLevel::cube(6, cube::Coloring::Mono(2)),
     GameLevel::icosahedron(5, icosahedron::Coloring::Mono(4)),
 ];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CampaignOrder {
+    #[default]
+    Standard,
+    Gentle,
+    Challenge,
+}
+
+impl CampaignOrder {
+    // LEVELS and their difficulty scores are fixed, so each order is sorted once and shared by every
+    // selector and navigation lookup after that.
+    pub fn level_indices(&self) -> &'static [LevelIndex] {
+        static STANDARD: OnceLock<Vec<LevelIndex>> = OnceLock::new();
+        static GENTLE: OnceLock<Vec<LevelIndex>> = OnceLock::new();
+        static CHALLENGE: OnceLock<Vec<LevelIndex>> = OnceLock::new();
+
+        let cache = match self {
+            CampaignOrder::Standard => &STANDARD,
+            CampaignOrder::Gentle => &GENTLE,
+            CampaignOrder::Challenge => &CHALLENGE,
+        };
+
+        cache.get_or_init(|| self.sorted_level_indices())
+    }
+
+    fn sorted_level_indices(&self) -> Vec<LevelIndex> {
+        let mut level_indices = (0..LEVELS.len()).collect::<Vec<LevelIndex>>();
+        let difficulty = |level_index: &LevelIndex| LEVELS[*level_index].difficulty_score();
+
+        match self {
+            CampaignOrder::Standard => {}
+            CampaignOrder::Gentle => {
+                level_indices.sort_by(|a, b| difficulty(a).total_cmp(&difficulty(b)))
+            }
+            CampaignOrder::Challenge => {
+                level_indices.sort_by(|a, b| difficulty(b).total_cmp(&difficulty(a)))
+            }
+        }
+
+        level_indices
+    }
+
+    pub fn level_index(&self, campaign_position: usize) -> LevelIndex {
+        self.level_indices()[campaign_position]
+    }
+
+    // `None` for an index outside LEVELS, such as one kept in a save from a build with more levels.
+    pub fn campaign_position(&self, level_index: LevelIndex) -> Option<usize> {
+        self.level_indices()
+            .iter()
+            .position(|index| *index == level_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_at_equal_subdivision_rank_by_face_count() {
+        let levels = [
+            GameLevel::tetrahedron(3, tetrahedron::Coloring::Mono(0)),
+            GameLevel::cube(3, cube::Coloring::Mono(0)),
+            GameLevel::octahedron(3, octahedron::Coloring::Mono(0)),
+            GameLevel::new(Shape::Dodecahedron(dodecahedron::Coloring::Full([0, 1, 2, 3])), 3),
+            GameLevel::icosahedron(3, icosahedron::Coloring::Mono(0)),
+        ];
+
+        let scores = levels.iter().map(GameLevel::difficulty_score).collect::<Vec<f32>>();
+
+        assert!(scores.windows(2).all(|pair| pair[0] < pair[1]), "{scores:?}");
+    }
+
+    #[test]
+    fn more_subdivision_is_harder() {
+        let coarse = GameLevel::cube(2, cube::Coloring::Mono(0));
+        let fine = GameLevel::cube(5, cube::Coloring::Mono(0));
+
+        assert!(coarse.difficulty_score() < fine.difficulty_score());
+    }
+
+    #[test]
+    fn campaign_orders_rank_levels_by_difficulty() {
+        let difficulties = |order: CampaignOrder| {
+            order
+                .level_indices()
+                .iter()
+                .map(|level_index| LEVELS[*level_index].difficulty_score())
+                .collect::<Vec<f32>>()
+        };
+
+        assert_eq!(CampaignOrder::Standard.level_indices(), (0..LEVELS.len()).collect::<Vec<LevelIndex>>());
+        assert!(difficulties(CampaignOrder::Gentle).windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(difficulties(CampaignOrder::Challenge).windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+
+    #[test]
+    fn campaign_position_and_level_index_are_inverses() {
+        for order in [CampaignOrder::Standard, CampaignOrder::Gentle, CampaignOrder::Challenge] {
+            for level_index in 0..LEVELS.len() {
+                let campaign_position = order.campaign_position(level_index).unwrap();
+                assert_eq!(order.level_index(campaign_position), level_index);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_level_has_no_campaign_position() {
+        for order in [CampaignOrder::Standard, CampaignOrder::Gentle, CampaignOrder::Challenge] {
+            assert_eq!(order.campaign_position(LEVELS.len()), None);
+        }
+    }
+}
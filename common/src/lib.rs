@@ -6,7 +6,7 @@ use assets::shaders::{
 };
 #[cfg(not(target_arch = "wasm32"))]
 use bevy::pbr::wireframe::WireframePlugin;
-use bevy::{pbr::ExtendedMaterial, prelude::*};
+use bevy::{diagnostic::FrameTimeDiagnosticsPlugin, pbr::ExtendedMaterial, prelude::*};
 use bevy_common_assets::json::JsonAssetPlugin;
 use bevy_hanabi::HanabiPlugin;
 use bevy_pkv::PkvStore;
@@ -17,35 +17,76 @@ use game_settings::GameSettingsPlugin;
 use game_systems::GameSystemsPlugin;
 use noisy_bevy::NoisyShaderPlugin;
 use shape::loader::MazeLevelData;
+use soundfont::SoundfontSource;
 
 mod assets;
+mod background_music;
+#[cfg(any(test, feature = "autoplay"))]
+pub mod autoplay;
 pub mod camera;
+#[cfg(feature = "clip_export")]
+mod clip_export;
+pub mod clipboard;
 pub mod constants;
 mod controller;
 pub mod controller_screen_position;
+pub mod custom_maze;
+mod daily_streak;
+mod edge_coverage;
 mod effects;
+pub mod error;
+mod favorites;
+mod flow_meter;
+mod frame_rate;
 pub mod game_save;
 mod game_settings;
 mod game_state;
 mod game_systems;
+mod geometry;
+pub mod goal_placement;
+mod hint;
+#[cfg(feature = "hot_reload")]
+mod hot_reload;
+mod inactivity;
 pub mod is_room_junction;
 mod level_selector;
 pub mod levels;
 mod light;
 pub mod maze;
+pub mod maze_metrics;
+#[cfg(not(any(target_arch = "wasm32", target_os = "android")))]
+mod melody_export;
 mod menu;
+mod path_trail;
+mod pause;
 mod player;
 pub mod room;
+#[cfg(feature = "room_id_labels")]
+mod room_id_labels;
 mod selector;
 pub mod shape;
+mod snap_assist;
+mod solve_recording;
 pub mod sound;
-mod player_path;
+pub mod soundfont;
+#[cfg(not(target_arch = "wasm32"))]
+mod state_dump;
+pub mod player_path;
 mod play_statistics;
 mod ui;
+mod undo;
 mod victory;
+mod whats_new;
 mod load_level_asset;
 
 pub fn add_common_plugins(app: &mut App) {
+    let (soundfont, soundfont_outcome) = app
+        .world()
+        .get_resource::<SoundfontSource>()
+        .cloned()
+        .unwrap_or_default()
+        .load();
+
     app.add_plugins((
         DefaultPlugins,
         #[cfg(not(target_arch = "wasm32"))]
@@ -58,10 +99,24 @@ pub fn add_common_plugins(app: &mut App) {
         NoisyShaderPlugin,
         ShadersPlugin::default(),
         RustySynthPlugin {
-            soundfont: Cursor::new(include_bytes!(
-                "../../desktop/assets/marimba_chiapaneca.sf2"
-            )),
+            soundfont: Cursor::new(soundfont),
         },
         HanabiPlugin,
+        FrameTimeDiagnosticsPlugin,
     ));
+
+    app.insert_resource(soundfont_outcome)
+        .add_systems(Startup, soundfont::log_outcome);
+
+    #[cfg(feature = "hot_reload")]
+    app.add_plugins(hot_reload::HotReloadPlugin::default());
+
+    #[cfg(feature = "room_id_labels")]
+    app.add_plugins(room_id_labels::RoomIdLabelsPlugin::default());
+
+    #[cfg(feature = "autoplay")]
+    app.add_plugins(autoplay::AutoplayPlugin::default());
+
+    #[cfg(feature = "clip_export")]
+    app.add_plugins(clip_export::ClipExportPlugin::default());
 }
@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 use std::time::Duration;
 
+use bevy::asset::LoadState;
 use bevy::prelude::*;
 use bevy::tasks::block_on;
 use bevy::tasks::futures_lite::future;
@@ -9,19 +10,31 @@ use bevy::tasks::Task;
 use bevy::utils::HashMap;
 use bevy_rustysynth::MidiAudio;
 use bevy_rustysynth::MidiNote;
+use itertools::Itertools;
+use petgraph::graphmap::GraphMap;
+use petgraph::Directed;
 
+use crate::background_music::BackgroundLoopSource;
+use crate::custom_maze::default_note;
+use crate::error::MazonicError;
 use crate::game_save::CurrentPuzzle;
 use crate::game_save::DailyLevelId;
 use crate::game_save::LevelIndex;
 use crate::game_save::PuzzleIdentifier;
+use crate::game_settings::GameSettings;
 use crate::game_state::GameState;
 use crate::game_state::PuzzleState;
+use crate::is_room_junction::is_junction;
 use crate::levels::GameLevel;
 use crate::levels::PuzzleEntityMarker;
+use crate::room::Edge;
+use crate::room::Room;
 use crate::shape::loader::EncryptedMelody;
 use crate::shape::loader::GraphComponent;
 use crate::shape::loader::MazeLevelData;
+use crate::shape::loader::ReversedSolve;
 use crate::shape::loader::SolutionComponent;
+use crate::sound::is_well_formed_encrypted_melody;
 use crate::sound::MelodyPuzzleTracker;
 use crate::sound::Note;
 use crate::sound::NoteMapping;
@@ -47,6 +60,14 @@ pub struct LoadingRemoteLevels(pub HashMap<PuzzleIdentifier, Task<Result<MazeLev
 #[derive(Resource, Default)]
 pub struct LoadedLevels(pub HashMap<PuzzleIdentifier, MazeSaveDataHandle>);
 
+const MELODY_UNAVAILABLE_MESSAGE: &str = "melody unavailable for this level";
+
+// What had to be left out for a level to stay playable.
+#[derive(Debug, Default)]
+pub struct LevelFallbacks {
+    pub melody_dropped: bool,
+}
+
 const EASY_DAILY_LEVEL_TAG: &str = "easy";
 const HARD_DAILY_LEVEL_TAG: &str = "hard";
 const DAILY_LEVELS_URL: &str = "https://raw.githubusercontent.com/oscardarwin/mazonic_levels/main";
@@ -87,13 +108,22 @@ pub fn wait_until_loaded(
         return;
     }
 
-    let task = loading_remote_levels.0.entry(puzzle_identifier.clone()).or_insert_with(||
-        match puzzle_identifier {
-            PuzzleIdentifier::EasyDaily(id) => start_remote_daily_level_download(&id, EASY_DAILY_LEVEL_TAG),
-            PuzzleIdentifier::HardDaily(id) => start_remote_daily_level_download(&id, HARD_DAILY_LEVEL_TAG),
-            _ => panic!("Not a remote level")
+    let (daily_level_id, tag) = match puzzle_identifier {
+        PuzzleIdentifier::EasyDaily(id) => (id, EASY_DAILY_LEVEL_TAG),
+        PuzzleIdentifier::HardDaily(id) => (id, HARD_DAILY_LEVEL_TAG),
+        PuzzleIdentifier::Level(_) => {
+            let err = MazonicError::WrongLevelSource(puzzle_identifier.clone());
+            warn!(target: "mazonic::loader", "{}", err);
+            message_popup.single_mut().0 = err.message().to_string();
+            game_state.set(GameState::Selector);
+            return;
         }
-    );
+    };
+
+    let task = loading_remote_levels
+        .0
+        .entry(puzzle_identifier.clone())
+        .or_insert_with(|| start_remote_daily_level_download(daily_level_id, tag));
 
     let Some(load_result) = block_on(future::poll_once(task)) else {
         return;
@@ -101,19 +131,15 @@ pub fn wait_until_loaded(
 
     loading_remote_levels.0.remove(puzzle_identifier);
     
-    let next_game_state = match load_result {
+    let next_game_state = match load_result.map_err(MazonicError::from) {
         Ok(level) => {
+            info!(target: "mazonic::loader", "Loaded remote level {:?} with difficulty {:.1}", puzzle_identifier, level.difficulty_score());
             loaded_levels.0.insert(puzzle_identifier.clone(), MazeSaveDataHandle::LoadedRemoteLevel(level));
             GameState::Puzzle
         },
         Err(err) => {
-            let message = match err {
-                DailyLevelLoadError::JsonParseError(_) => "failed to parse json",
-                DailyLevelLoadError::HttpError(_) => "could not fetch level from web",
-                DailyLevelLoadError::StringParseError(_) => "failed to parse level data",
-            }.to_string();
-
-            message_popup.single_mut().0 = message;
+            warn!(target: "mazonic::loader", "Failed to load remote level {:?}: {}", puzzle_identifier, err);
+            message_popup.single_mut().0 = err.message().to_string();
 
             GameState::Selector
         }
@@ -130,33 +156,173 @@ pub fn spawn_level_data(
     maze_save_data_assets: Res<Assets<MazeLevelData>>,
     mut loaded_levels: ResMut<LoadedLevels>,
     asset_server: Res<AssetServer>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+    game_settings: Res<GameSettings>,
 ) {
     let CurrentPuzzle(puzzle_identifier) = current_level_index_query.single();
     
-    println!("Loaded levels: {:?}, trying with pi: {:?}", loaded_levels.0.keys().collect::<Vec<_>>(), puzzle_identifier);
+    debug!(target: "mazonic::loader", "Loaded levels: {:?}, trying with puzzle: {:?}", loaded_levels.0.keys().collect::<Vec<_>>(), puzzle_identifier);
+
+    if let PuzzleIdentifier::Level(index) = puzzle_identifier {
+        loaded_levels.0.entry(puzzle_identifier.clone()).or_insert_with(||
+            MazeSaveDataHandle::LocalLevel(load_local_level(*index, &asset_server))
+        );
+    }
+
+    // Daily levels are only ever put here by `wait_until_loaded`.
+    let maze_save_data_handle = loaded_levels
+        .0
+        .get(puzzle_identifier)
+        .ok_or_else(|| MazonicError::WrongLevelSource(puzzle_identifier.clone()));
+
+    let level_data = maze_save_data_handle.and_then(|maze_save_data_handle| match maze_save_data_handle {
+        MazeSaveDataHandle::LocalLevel(handle) => match maze_save_data_assets.get(handle) {
+            Some(level) => Ok(Some(level.clone())),
+            None if matches!(asset_server.load_state(handle.id()), LoadState::Failed(_)) => {
+                Err(MazonicError::MissingAsset(handle.path().map_or_else(String::new, |path| path.to_string())))
+            }
+            None => Ok(None),
+        },
+        MazeSaveDataHandle::LoadedRemoteLevel(level) => Ok(Some(level.clone())),
+    });
+
+    let level_data = match level_data {
+        Ok(Some(level_data)) => level_data,
+        Ok(None) => return,
+        Err(err) => {
+            // Waiting on a level file that failed to load would leave the loading screen up forever.
+            leave_failed_level(puzzle_identifier, err, &mut loaded_levels, &mut message_popup_query, &mut game_state);
+            return;
+        }
+    };
+
+    let reversed_level_data = game_settings.reverse_solve.then(|| level_data.reversed()).flatten();
+    if game_settings.reverse_solve && reversed_level_data.is_none() {
+        warn!(target: "mazonic::loader", "Level {:?} cannot be solved in reverse; loading it as usual", puzzle_identifier);
+    }
+
+    let level_data = match reversed_level_data {
+        Some(reversed_level_data) => {
+            commands.spawn((ReversedSolve, PuzzleEntityMarker));
+            reversed_level_data
+        }
+        None => level_data,
+    };
+
+    let fallbacks = match spawn_maze_level_data(&mut commands, &asset_server, level_data) {
+        Ok(fallbacks) => fallbacks,
+        Err(err) => {
+            leave_failed_level(puzzle_identifier, err, &mut loaded_levels, &mut message_popup_query, &mut game_state);
+            return;
+        }
+    };
+
+    if fallbacks.melody_dropped {
+        if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+            message_popup.0 = MELODY_UNAVAILABLE_MESSAGE.to_string();
+        }
+    }
+
+    play_state.set(PuzzleState::Playing);
+}
 
-    let maze_save_data_handle = loaded_levels.0.entry(puzzle_identifier.clone()).or_insert_with(||
-        match puzzle_identifier {
-            PuzzleIdentifier::Level(index) => MazeSaveDataHandle::LocalLevel(load_local_level(*index, &asset_server)),
-            _ => panic!("Not a local level")
+fn leave_failed_level(
+    puzzle_identifier: &PuzzleIdentifier,
+    err: MazonicError,
+    loaded_levels: &mut LoadedLevels,
+    message_popup_query: &mut Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+    game_state: &mut NextState<GameState>,
+) {
+    warn!(target: "mazonic::loader", "Level {:?} failed to load: {}", puzzle_identifier, err);
+    loaded_levels.0.remove(puzzle_identifier);
+    if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+        message_popup.0 = err.message().to_string();
+    }
+    game_state.set(GameState::Selector);
+}
+
+// Checks the parts of level data that the rest of the game relies on without checking again.
+pub fn validate_level_data(level: &GameLevel, graph: &GraphMap<Room, Edge, Directed>, solution: &[Room]) -> Result<(), MazonicError> {
+    if solution.is_empty() {
+        return Err(MazonicError::EmptySolution);
+    }
+
+    // Border lookups index the shape's face table, so out of range faces must be caught first.
+    let face_count = level.shape.face_count();
+    if let Some(room) = graph.nodes().chain(solution.iter().copied()).find(|room| room.face().id() >= face_count) {
+        return Err(MazonicError::InvalidFace { room: room.id });
+    }
+
+    for (from, to, _) in graph.all_edges() {
+        if level.border_type(&from.face(), &to.face()).is_none() {
+            return Err(MazonicError::UnknownBorder { from: from.id, to: to.id });
         }
-    );
+    }
+
+    Ok(())
+}
 
+// Incomplete note or melody data degrades the level to a plain maze rather than failing to load it.
+pub fn spawn_maze_level_data(commands: &mut Commands, asset_server: &AssetServer, level_data: MazeLevelData) -> Result<LevelFallbacks, MazonicError> {
     let MazeLevelData {
         shape,
         nodes_per_edge,
         graph,
         solution,
-        node_id_to_note,
+        goal_placement,
+        mut node_id_to_note,
         encrypted_melody,
-    } = match maze_save_data_handle {
-        MazeSaveDataHandle::LocalLevel(handle) => match maze_save_data_assets.get(handle) {
-            Some(level) => level.clone(),
-            None => return,
-        },
-        MazeSaveDataHandle::LoadedRemoteLevel(level) => level.clone(),
+        background_loop,
+        #[cfg(debug_assertions)]
+        melody,
+    } = level_data;
+
+    let solution = goal_placement.place(&graph, solution);
+
+    let level = GameLevel::new(shape, nodes_per_edge);
+    validate_level_data(&level, &graph, &solution)?;
+
+    // Junctions and the start room are the rooms that sound a note when entered.
+    let unmapped_room_ids = graph
+        .nodes()
+        .filter(|room| is_junction(room, &graph) || solution.first() == Some(room))
+        .map(|room| room.id)
+        .filter(|room_id| !node_id_to_note.contains_key(room_id))
+        .collect_vec();
+
+    if !unmapped_room_ids.is_empty() {
+        warn!(target: "mazonic::loader", "Level has no notes for rooms {:?}; using default notes", unmapped_room_ids);
+        for room_id in unmapped_room_ids.iter() {
+            node_id_to_note.insert(*room_id, default_note(*room_id));
+        }
+    }
+
+    // Substituted notes change the melody hash, so a melody can no longer be discovered with them.
+    let had_melody = encrypted_melody.is_some();
+    let encrypted_melody = encrypted_melody.filter(|encrypted_melody| {
+        let usable = unmapped_room_ids.is_empty()
+            && encrypted_melody.melody_length > 0
+            && is_well_formed_encrypted_melody(&encrypted_melody.encrypted_melody_bytes);
+
+        if !usable {
+            warn!(target: "mazonic::loader", "Level melody is incomplete; loading without the melody puzzle");
+        }
+        usable
+    });
+
+    let fallbacks = LevelFallbacks {
+        melody_dropped: had_melody && encrypted_melody.is_none(),
     };
 
+    // A level with only a plaintext melody still needs a tracker of the melody's length.
+    #[cfg(debug_assertions)]
+    let encrypted_melody = encrypted_melody.or_else(|| {
+        melody.as_ref().map(|melody| EncryptedMelody {
+            encrypted_melody_bytes: vec![],
+            melody_length: melody.notes.0.len(),
+        })
+    });
+
     let note_midi_handle = node_id_to_note
         .into_iter()
         .map(|(node_id, note)| {
@@ -182,20 +348,234 @@ pub fn spawn_level_data(
             MelodyPuzzleTracker {
                 room_ids,
                 encrypted_melody_bytes: encrypted_melody_bytes.clone(),
+                #[cfg(debug_assertions)]
+                plaintext_melody: melody,
             },
             PuzzleEntityMarker,
         ));
     }
 
-    commands.spawn((
+    let mut level_entity_commands = commands.spawn((
         PuzzleEntityMarker,
-        GameLevel {
-            shape,
-            nodes_per_edge,
-        },
+        level,
         GraphComponent(graph),
         SolutionComponent(solution),
         NoteMapping(note_midi_handle),
     ));
-    play_state.set(PuzzleState::Playing);
+
+    if let Some(background_loop) = background_loop {
+        level_entity_commands.insert(BackgroundLoopSource(background_loop));
+    }
+
+    Ok(fallbacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::AssetPlugin;
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::goal_placement::GoalPlacement;
+    use crate::levels::Shape;
+    use crate::room::{test_support::room_on_face, Face};
+    use crate::shape::cube;
+
+    use super::*;
+
+    fn cube_level() -> GameLevel {
+        GameLevel::cube(2, cube::Coloring::Full([0, 1, 2]))
+    }
+
+    fn room(id: u64, face_id: usize) -> Room {
+        room_on_face(id, Vec3::X * id as f32, Face { id: face_id, normal: Vec3::Y })
+    }
+
+    fn graph(edges: &[(Room, Room)]) -> GraphMap<Room, Edge, Directed> {
+        let mut graph = GraphMap::new();
+        for (from, to) in edges {
+            graph.add_edge(*from, *to, Edge);
+        }
+        graph
+    }
+
+    #[test]
+    fn level_data_with_an_empty_solution_is_rejected() {
+        let graph = graph(&[(room(0, 0), room(1, 0))]);
+
+        assert!(matches!(
+            validate_level_data(&cube_level(), &graph, &[]),
+            Err(MazonicError::EmptySolution)
+        ));
+    }
+
+    #[test]
+    fn level_data_with_an_edge_between_opposite_faces_is_rejected() {
+        // Faces 0 and 5 of a cube are opposite, so they share no vertices.
+        let graph = graph(&[(room(0, 0), room(1, 1)), (room(1, 1), room(2, 5))]);
+
+        assert!(matches!(
+            validate_level_data(&cube_level(), &graph, &[room(0, 0), room(1, 1)]),
+            Err(MazonicError::UnknownBorder { from: 1, to: 2 })
+        ));
+    }
+
+    #[test]
+    fn level_data_with_a_room_on_a_face_the_shape_lacks_is_rejected() {
+        // A cube has faces 0 to 5.
+        let graph = graph(&[(room(0, 0), room(1, 0)), (room(1, 0), room(2, 6))]);
+
+        assert!(matches!(
+            validate_level_data(&cube_level(), &graph, &[room(0, 0), room(1, 0)]),
+            Err(MazonicError::InvalidFace { room: 2 })
+        ));
+    }
+
+    #[test]
+    fn level_data_across_bordering_faces_is_accepted() {
+        let graph = graph(&[(room(0, 0), room(1, 0)), (room(1, 0), room(2, 1))]);
+
+        assert!(validate_level_data(&cube_level(), &graph, &[room(0, 0), room(2, 1)]).is_ok());
+    }
+
+    #[test]
+    fn campaign_level_sent_to_the_remote_loader_returns_to_the_selector() {
+        let mut world = World::new();
+        world.init_resource::<LoadedLevels>();
+        world.init_resource::<LoadingRemoteLevels>();
+        world.init_resource::<NextState<GameState>>();
+        world.spawn(CurrentPuzzle(PuzzleIdentifier::Level(3)));
+        let message_popup = world.spawn((MessagePopup::default(), MessagePopupUpperMarker)).id();
+
+        world.run_system_once(wait_until_loaded).unwrap();
+
+        assert!(matches!(
+            *world.resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::Selector)
+        ));
+        assert_eq!(
+            world.get::<MessagePopup>(message_popup).unwrap().0,
+            MazonicError::WrongLevelSource(PuzzleIdentifier::Level(3)).message()
+        );
+        assert!(world.resource::<LoadingRemoteLevels>().0.is_empty());
+    }
+
+    // A straight corridor 0-1-2 on one face, so the ends are its only junctions.
+    fn corridor_level_data(notes: bool, encrypted_melody_bytes: Vec<u8>) -> MazeLevelData {
+        let rooms = [0, 1, 2].map(|id| room(id, 0));
+        let node_id_to_note = match notes {
+            true => rooms.iter().map(|room| (room.id, default_note(room.id))).collect(),
+            false => HashMap::new(),
+        };
+
+        MazeLevelData {
+            shape: Shape::Cube(cube::Coloring::Full([0, 1, 2])),
+            nodes_per_edge: 2,
+            graph: graph(&[(rooms[0], rooms[1]), (rooms[1], rooms[0]), (rooms[1], rooms[2]), (rooms[2], rooms[1])]),
+            solution: rooms.to_vec(),
+            goal_placement: GoalPlacement::Generated,
+            node_id_to_note,
+            encrypted_melody: Some(EncryptedMelody { encrypted_melody_bytes, melody_length: 3 }),
+            background_loop: None,
+            #[cfg(debug_assertions)]
+            melody: None,
+        }
+    }
+
+    fn level_app(puzzle_identifier: PuzzleIdentifier) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<MidiAudio>()
+            .init_asset::<MazeLevelData>()
+            .init_resource::<LoadedLevels>()
+            .init_resource::<NextState<GameState>>()
+            .init_resource::<NextState<PuzzleState>>()
+            .init_resource::<GameSettings>();
+
+        let world = app.world_mut();
+        world.spawn(CurrentPuzzle(puzzle_identifier));
+        world.spawn((MessagePopup::default(), MessagePopupUpperMarker));
+        app
+    }
+
+    fn spawn_remote_level(level_data: MazeLevelData) -> App {
+        let puzzle_identifier = PuzzleIdentifier::EasyDaily("2026-01-01".to_string());
+        let mut app = level_app(puzzle_identifier.clone());
+        app.world_mut()
+            .resource_mut::<LoadedLevels>()
+            .0
+            .insert(puzzle_identifier, MazeSaveDataHandle::LoadedRemoteLevel(level_data));
+
+        app.world_mut().run_system_once(spawn_level_data).unwrap();
+        app
+    }
+
+    fn is_playing(app: &App) -> bool {
+        matches!(*app.world().resource::<NextState<PuzzleState>>(), NextState::Pending(PuzzleState::Playing))
+    }
+
+    fn message(app: &mut App) -> String {
+        let world = app.world_mut();
+        world.query::<&MessagePopup>().single(world).0.clone()
+    }
+
+    fn has<C: Component>(app: &mut App) -> bool {
+        let world = app.world_mut();
+        world.query::<&C>().iter(world).next().is_some()
+    }
+
+    #[test]
+    fn a_complete_level_keeps_its_melody_puzzle() {
+        let mut app = spawn_remote_level(corridor_level_data(true, vec![0; 64]));
+
+        assert!(is_playing(&app));
+        assert!(has::<MelodyPuzzleTracker>(&mut app));
+        assert!(message(&mut app).is_empty());
+    }
+
+    #[test]
+    fn a_level_without_notes_plays_default_notes_without_its_melody() {
+        let mut app = spawn_remote_level(corridor_level_data(false, vec![0; 64]));
+
+        assert!(is_playing(&app));
+        assert!(has::<GraphComponent>(&mut app) && has::<SolutionComponent>(&mut app));
+        assert!(!has::<MelodyPuzzleTracker>(&mut app));
+        assert_eq!(message(&mut app), MELODY_UNAVAILABLE_MESSAGE);
+
+        let world = app.world_mut();
+        let NoteMapping(note_mapping) = world.query::<&NoteMapping>().single(world);
+        assert_eq!(note_mapping.keys().copied().sorted().collect_vec(), vec![0, 2]);
+    }
+
+    #[test]
+    fn a_level_with_a_malformed_melody_plays_without_it() {
+        let mut app = spawn_remote_level(corridor_level_data(true, vec![0; 8]));
+
+        assert!(is_playing(&app));
+        assert!(has::<GraphComponent>(&mut app));
+        assert!(!has::<MelodyPuzzleTracker>(&mut app));
+        assert_eq!(message(&mut app), MELODY_UNAVAILABLE_MESSAGE);
+    }
+
+    #[test]
+    fn a_level_file_that_fails_to_load_returns_to_the_selector() {
+        // No loader is registered for level files here, so the load always fails.
+        let mut app = level_app(PuzzleIdentifier::Level(0));
+
+        for _ in 0..200 {
+            app.world_mut().run_system_once(spawn_level_data).unwrap();
+            if matches!(*app.world().resource::<NextState<GameState>>(), NextState::Pending(_)) {
+                break;
+            }
+            app.update();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        assert!(matches!(
+            *app.world().resource::<NextState<GameState>>(),
+            NextState::Pending(GameState::Selector)
+        ));
+        assert!(!is_playing(&app));
+        assert_eq!(message(&mut app), MazonicError::MissingAsset(String::new()).message());
+        assert!(app.world().resource::<LoadedLevels>().0.is_empty());
+    }
 }
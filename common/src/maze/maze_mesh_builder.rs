@@ -12,6 +12,13 @@ use bevy::render::{
 
 use crate::constants::SQRT_3;
 
+const FOLD_SEGMENTS: usize = 6;
+// How far a rounded fold may sink towards the solid, as a fraction of the node distance. Kept
+// under the edge height so a rounded edge never dips beneath the faces it crosses.
+const MAX_FOLD_DIP: f32 = 0.004;
+
+// `face_angle` is the dihedral angle of the shape: the two halves of a cross-face edge meet at
+// the fold with exactly this angle between them, so each half lies flat on its own face.
 #[derive(Component)]
 pub struct MazeMeshBuilder {
     dash_width: f32,
@@ -19,6 +26,7 @@ pub struct MazeMeshBuilder {
     arrow_head_width: f32,
     face_angle: f32,
     distance_between_nodes: f32,
+    fold_radius: f32,
 }
 
 impl MazeMeshBuilder {
@@ -33,9 +41,20 @@ impl MazeMeshBuilder {
             arrow_head_width,
             face_angle,
             distance_between_nodes: 1.0,
+            fold_radius: 0.0,
         }
     }
 
+    // Rounds the fold of cross-face edges with a radius of `curvature` node distances, limited per
+    // shape so that sharper folds get a smaller radius.
+    pub fn with_fold_curvature(mut self, curvature: f32) -> Self {
+        let max_fold_radius =
+            MAX_FOLD_DIP * self.distance_between_nodes / (1.0 - (self.face_angle / 2.0).sin());
+
+        self.fold_radius = (curvature * self.distance_between_nodes).clamp(0.0, max_fold_radius);
+        self
+    }
+
     pub fn tetrahedron() -> Self {
         Self::new((1.0_f32 / 3.0).acos())
     }
@@ -63,6 +82,7 @@ impl MazeMeshBuilder {
             arrow_head_width: 0.1,
             face_angle: (-5.0_f32.sqrt() / 3.0).acos(),
             distance_between_nodes: 1.0 / SQRT_3 / 3.0,
+            fold_radius: 0.0,
         }
     }
 
@@ -127,6 +147,13 @@ impl MazeMeshBuilder {
         let mut second_line = self.line(second_length, uv_mid_point, 0.5);
 
         let arrow_head_mesh = self.arrow_head().translated_by(Vec3::Z * second_length);
+
+        if self.fold_radius > 0.0 {
+            let mut line = self.rounded_fold(second_length, 0.5, first_length, 0.0, uv_mid_point);
+            line.merge(&arrow_head_mesh);
+            return line;
+        }
+
         second_line.merge(&arrow_head_mesh);
 
         let first_line = self
@@ -141,6 +168,10 @@ impl MazeMeshBuilder {
     pub fn cross_face_edge(&self) -> Mesh {
         let half_length = self.distance_between_nodes / 2.0;
 
+        if self.fold_radius > 0.0 {
+            return self.rounded_fold(half_length, 0.5, half_length, 0.5, 0.0);
+        }
+
         let mut first_line = self.line(half_length, 0.0, 0.5);
         let second_line = self
             .line(half_length, 0.0, 0.5)
@@ -150,4 +181,164 @@ impl MazeMeshBuilder {
         first_line.merge(&second_line);
         first_line
     }
+
+    // A cross-face edge whose fold is a circular arc rather than a sharp corner. The straight half
+    // runs along +Z from the fold, like `line`, and the folded half leaves the fold at `face_angle`
+    // to it. Texture coordinates follow the path length so dashes stay evenly spaced.
+    fn rounded_fold(
+        &self,
+        straight_length: f32,
+        straight_uv_end: f32,
+        folded_length: f32,
+        folded_uv_end: f32,
+        uv_fold: f32,
+    ) -> Mesh {
+        let straight_dir = Vec3::Z;
+        let folded_dir = Quat::from_rotation_x(self.face_angle) * Vec3::Z;
+        let half_angle = self.face_angle / 2.0;
+
+        let max_tangent_distance = 0.5 * straight_length.min(folded_length);
+        let tangent_distance = (self.fold_radius / half_angle.tan()).min(max_tangent_distance);
+        let radius = tangent_distance * half_angle.tan();
+        let center = (straight_dir + folded_dir).normalize() * radius / half_angle.sin();
+
+        let folded_tangent = folded_dir * tangent_distance;
+        let straight_tangent = straight_dir * tangent_distance;
+
+        let arc_start = folded_tangent - center;
+        let arc = Quat::from_rotation_arc(arc_start.normalize(), (straight_tangent - center).normalize());
+
+        let mut points = vec![folded_dir * folded_length];
+        points.extend((0..=FOLD_SEGMENTS).map(|segment| {
+            let t = segment as f32 / FOLD_SEGMENTS as f32;
+            center + Quat::IDENTITY.slerp(arc, t) * arc_start
+        }));
+        points.push(straight_dir * straight_length);
+
+        let mut distances = vec![0.0];
+        for (from, to) in points.iter().zip(points.iter().skip(1)) {
+            distances.push(distances.last().unwrap() + from.distance(*to));
+        }
+        let total_distance = *distances.last().unwrap();
+        let fold_distance = distances[1 + FOLD_SEGMENTS / 2];
+
+        let uv = |distance: f32| {
+            if distance <= fold_distance {
+                FloatExt::lerp(folded_uv_end, uv_fold, distance / fold_distance)
+            } else {
+                FloatExt::lerp(uv_fold, straight_uv_end, (distance - fold_distance) / (total_distance - fold_distance))
+            }
+        };
+
+        let half_width = Vec3::X * self.dash_width / 2.0;
+
+        let mut positions = Vec::with_capacity(points.len() * 2);
+        let mut normals = Vec::with_capacity(points.len() * 2);
+        let mut uvs = Vec::with_capacity(points.len() * 2);
+
+        for (index, point) in points.iter().enumerate() {
+            let previous = points[index.saturating_sub(1)];
+            let next = points[(index + 1).min(points.len() - 1)];
+            let normal = (next - previous).normalize().cross(Vec3::X);
+            let v = uv(distances[index]);
+
+            positions.extend([(*point - half_width).to_array(), (*point + half_width).to_array()]);
+            normals.extend([normal.to_array(), normal.to_array()]);
+            uvs.extend([[0.0, v], [1.0, v]]);
+        }
+
+        let indices = (0..points.len() as u32 - 1)
+            .flat_map(|index| {
+                let (left, right) = (index * 2, index * 2 + 1);
+                let (next_left, next_right) = (left + 2, right + 2);
+                [left, next_left, right, right, next_left, next_right]
+            })
+            .collect::<Vec<u32>>();
+
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shape::{cube, dodecahedron, icosahedron, octahedron, shape_utils::compute_face_normal, tetrahedron};
+
+    use super::*;
+
+    // The angle between two faces that share an edge, measured from the shape's own vertices.
+    fn measured_dihedral<const N: usize>(faces: &[[Vec3; N]]) -> f32 {
+        let outward_normal = |face: &[Vec3; N]| {
+            let normal = compute_face_normal(face);
+            let centroid = face.iter().sum::<Vec3>() / N as f32;
+            if normal.dot(centroid) < 0.0 { -normal } else { normal }
+        };
+
+        let shared_vertices = |first: &[Vec3; N], second: &[Vec3; N]| {
+            first
+                .iter()
+                .filter(|vertex| second.iter().any(|other| vertex.distance(*other) < 1e-4))
+                .count()
+        };
+
+        let neighbor = faces[1..]
+            .iter()
+            .find(|face| shared_vertices(&faces[0], face) == 2)
+            .unwrap();
+
+        PI - outward_normal(&faces[0]).angle_between(outward_normal(neighbor))
+    }
+
+    fn positions(mesh: &Mesh) -> Vec<Vec3> {
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            .unwrap()
+            .as_float3()
+            .unwrap()
+            .iter()
+            .map(|position| Vec3::from_array(*position))
+            .collect()
+    }
+
+    // The centre of the far end of each half, seen from the fold at the origin.
+    fn far_end(half: &[Vec3]) -> Vec3 {
+        let mut by_distance = half.to_vec();
+        by_distance.sort_by(|a, b| b.length().total_cmp(&a.length()));
+        (by_distance[0] + by_distance[1]) / 2.0
+    }
+
+    fn shapes() -> [(&'static str, MazeMeshBuilder, f32); 5] {
+        [
+            ("tetrahedron", MazeMeshBuilder::tetrahedron(), measured_dihedral(&tetrahedron::faces())),
+            ("cube", MazeMeshBuilder::cube(), measured_dihedral(&cube::faces())),
+            ("octahedron", MazeMeshBuilder::octahedron(), measured_dihedral(&octahedron::faces())),
+            ("dodecahedron", MazeMeshBuilder::dodecahedron(), measured_dihedral(&dodecahedron::faces())),
+            ("icosahedron", MazeMeshBuilder::icosahedron(), measured_dihedral(&icosahedron::faces())),
+        ]
+    }
+
+    #[test]
+    fn cross_face_edges_fold_at_the_dihedral_angle_of_each_shape() {
+        for (name, builder, dihedral) in shapes() {
+            let positions = positions(&builder.cross_face_edge());
+            let (first_half, second_half) = positions.split_at(positions.len() / 2);
+            let fold_angle = far_end(first_half).angle_between(far_end(second_half));
+
+            assert!((fold_angle - dihedral).abs() < 1e-3, "{name}: folds at {fold_angle}, dihedral is {dihedral}");
+        }
+    }
+
+    #[test]
+    fn rounded_cross_face_edges_keep_the_dihedral_angle_of_each_shape() {
+        for (name, builder, dihedral) in shapes() {
+            let positions = positions(&builder.with_fold_curvature(1.0).cross_face_edge());
+            let folded_end = (positions[0] + positions[1]) / 2.0;
+            let straight_end = (positions[positions.len() - 2] + positions[positions.len() - 1]) / 2.0;
+            let fold_angle = folded_end.angle_between(straight_end);
+
+            assert!((fold_angle - dihedral).abs() < 1e-3, "{name}: folds at {fold_angle}, dihedral is {dihedral}");
+        }
+    }
 }
@@ -13,18 +13,47 @@ use crate::{
         material_handles::MaterialHandles,
         mesh_handles::MeshHandles,
         shaders::{DashedArrowShader, PulsingShader},
-    }, effects::musical_notes::{MusicalNoteEffectColor, MusicalNoteEffectHandle, MusicalNoteImageHandles, MusicalNoteMarker}, game_save::{CurrentPuzzle, DiscoveredMelody, PuzzleIdentifier}, game_systems::SystemHandles, is_room_junction::is_junction, levels::{GameLevel, PuzzleEntityMarker, Shape}, maze::maze_mesh_builder::MazeMeshBuilder, play_statistics::PlayStatistics, room::Room, shape::loader::{GraphComponent, SolutionComponent}
+    }, effects::musical_notes::{MusicalNoteEffectColor, MusicalNoteEffectHandle, MusicalNoteImageHandles, MusicalNoteMarker}, game_save::{CurrentPuzzle, DiscoveredMelody, PuzzleIdentifier}, game_settings::GameSettings, game_systems::SystemHandles, is_room_junction::is_junction, levels::{GameLevel, PuzzleEntityMarker, Shape}, maze::maze_mesh_builder::MazeMeshBuilder, play_statistics::PlayStatistics, room::Room, shape::loader::{GraphComponent, SolutionComponent}
 };
 
-use super::border_type::BorderType;
+use super::{border_type::BorderType, solution_edges};
 
-const ROOM_HEIGHT: f32 = 0.002;
-const SAME_FACE_EDGE_HEIGHT: f32 = 0.001;
-const CROSS_FACE_EDGE_HEIGHT: f32 = 0.001;
+// Heights above the face as fractions of the distance between nodes, so the separation keeps pace
+// with the size the maze is drawn at. At a node distance of 0.2 these are 0.002 for rooms and
+// 0.001 for edges.
+const ROOM_HEIGHT_FACTOR: f32 = 0.01;
+const EDGE_HEIGHT_FACTOR: f32 = 0.005;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SurfaceOffsets {
+    pub room: f32,
+    pub edge: f32,
+}
+
+impl SurfaceOffsets {
+    pub fn new(distance_between_nodes: f32, multiplier: f32) -> Self {
+        let scale = distance_between_nodes * multiplier.max(0.0);
+
+        SurfaceOffsets {
+            room: ROOM_HEIGHT_FACTOR * scale,
+            edge: EDGE_HEIGHT_FACTOR * scale,
+        }
+    }
+}
 
 #[derive(Component, Debug, Clone)]
 pub struct MazeMarker;
 
+// The room ids an edge mesh joins, in the direction the edge was spawned.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MazeEdgeRooms(pub u64, pub u64);
+
+impl MazeEdgeRooms {
+    pub fn joins(&self, from: &Room, to: &Room) -> bool {
+        (self.0, self.1) == (from.id, to.id) || (self.0, self.1) == (to.id, from.id)
+    }
+}
+
 pub fn spawn(
     mut commands: Commands,
     level_query: Query<&GameLevel>,
@@ -35,6 +64,7 @@ pub fn spawn(
     current_puzzle_query: Query<&CurrentPuzzle>,
     musical_note_effect_handle: Query<&MusicalNoteEffectHandle>,
     musical_note_image_handle_query: Query<&MusicalNoteImageHandles>,
+    game_settings: Res<GameSettings>,
 ) {
     let Ok(level) = level_query.get_single() else {
         return;
@@ -57,8 +87,11 @@ pub fn spawn(
         .collect::<HashMap<_, _>>();
 
     let distance_between_nodes = level.node_distance();
+    let surface_offsets = SurfaceOffsets::new(distance_between_nodes, game_settings.maze_surface_offset);
 
-    let goal_node = solution.last().unwrap();
+    let Some(goal_node) = solution.last() else {
+        return;
+    };
     for room in graph.nodes().filter(|room| is_junction(room, &graph)) {
         let is_goal_node = room == *goal_node;
 
@@ -67,7 +100,7 @@ pub fn spawn(
                 -room.face().normal(),
                 room.face().normal().any_orthogonal_vector(),
             )
-            .with_translation(room.position() + room.face().normal() * ROOM_HEIGHT);
+            .with_translation(room.position() + room.face().normal() * surface_offsets.room);
 
         let mut entity_commands =
             commands.spawn((transform, PuzzleEntityMarker, room, Visibility::default()));
@@ -77,7 +110,7 @@ pub fn spawn(
         let mesh_handle = if room == *goal_node {
             mesh_handles.goal_room.clone()
         } else {
-            mesh_handles.junction_room.clone()
+            mesh_handles.junction_room.get(game_settings.room_marker_style).clone()
         };
 
         entity_commands.with_children(|parent| {
@@ -91,7 +124,7 @@ pub fn spawn(
                 (true, _) => child_entity_commands
                     .insert(MeshMaterial3d(material_handles.goal_handle.clone())),
                 (false, Some(melody_index)) => child_entity_commands.insert((MeshMaterial3d(
-                    material_handles.bright_line_handle.clone(),
+                    material_handles.melody_line_handles.get(game_settings.melody_emphasis).clone(),
                 ), MusicalNoteMarker(*melody_index, MusicalNoteEffectColor::Line))),
                 (false, None) => child_entity_commands
                     .insert(MeshMaterial3d(material_handles.line_handle.clone())),
@@ -117,8 +150,10 @@ pub fn spawn(
             continue;
         }
 
+        // Level data is validated on load, so this only skips an edge rather than failing the level.
         let Some(border_type) = level.border_type(&source_node.face(), &target_node.face()) else {
-            panic!["unknown edge type"];
+            warn!(target: "mazonic::maze", "Skipping edge {} -> {} between faces with no shared border", source_node.id, target_node.id);
+            continue;
         };
 
         let mesh_handle = match (&border_type, bidirectional) {
@@ -128,7 +163,7 @@ pub fn spawn(
             (BorderType::Connected, false) => maze_mesh_handles.one_way_cross_face_edge.clone(),
         };
 
-        let transform = get_connection_transform(source_node, target_node, &border_type);
+        let transform = get_connection_transform(source_node, target_node, &border_type, surface_offsets.edge);
 
         let is_discovered = discovered_melody_room_pairs
             .contains(&(source_node.id, target_node.id))
@@ -141,16 +176,17 @@ pub fn spawn(
                     Mesh3d(mesh_handle),
                     Transform::IDENTITY.with_scale(Vec3::splat(distance_between_nodes)),
                     MazeMarker,
+                    MazeEdgeRooms(source_node.id, target_node.id),
                 ));
 
                 match (bidirectional, is_discovered) {
                     (false, true) => entity_commands.insert(MeshMaterial3d(
-                        material_handles.bright_dashed_arrow_handle.clone(),
+                        material_handles.melody_dashed_arrow_handles.get(game_settings.melody_emphasis).clone(),
                     )),
                     (false, false) => entity_commands
                         .insert(MeshMaterial3d(material_handles.dashed_arrow_handle.clone())),
                     (true, true) => entity_commands.insert(MeshMaterial3d(
-                        material_handles.bright_line_handle.clone(),
+                        material_handles.melody_line_handles.get(game_settings.melody_emphasis).clone(),
                     )),
                     (true, false) => {
                         entity_commands.insert(MeshMaterial3d(material_handles.line_handle.clone()))
@@ -160,19 +196,65 @@ pub fn spawn(
     }
 }
 
-fn get_connection_transform(from: Room, to: Room, border_type: &BorderType) -> Transform {
+// Marks an edge mesh lit up by the solution reveal.
+#[derive(Component, Debug, Clone)]
+pub struct RevealedSolutionEdge;
+
+pub fn reveal_solution_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.reveal_solution
+}
+
+// Lights every edge along the solution as the maze meshes appear, including when they are rebuilt
+// after a melody is discovered.
+pub fn reveal_solution(
+    mut commands: Commands,
+    edge_query: Query<(Entity, &MazeEdgeRooms, Has<MeshMaterial3d<StandardMaterial>>), Added<MazeEdgeRooms>>,
+    solution_query: Query<&SolutionComponent>,
+    material_handles: Res<MaterialHandles>,
+    game_settings: Res<GameSettings>,
+) {
+    if edge_query.is_empty() {
+        return;
+    }
+
+    let Ok(SolutionComponent(solution)) = solution_query.get_single() else {
+        return;
+    };
+
+    for (entity, edge_rooms, is_line) in edge_query.iter() {
+        if !solution_edges(solution).any(|(from, to)| edge_rooms.joins(&from, &to)) {
+            continue;
+        }
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(RevealedSolutionEdge);
+
+        if is_line {
+            entity_commands.insert(MeshMaterial3d(
+                material_handles.melody_line_handles.get(game_settings.melody_emphasis).clone(),
+            ));
+        } else {
+            entity_commands.insert(MeshMaterial3d(
+                material_handles.melody_dashed_arrow_handles.get(game_settings.melody_emphasis).clone(),
+            ));
+        }
+    }
+}
+
+pub fn get_connection_transform(from: Room, to: Room, border_type: &BorderType, edge_height: f32) -> Transform {
     match border_type {
         BorderType::SameFace => {
             let forward = from.position() - to.position();
             Transform::IDENTITY
                 .looking_to(forward, from.face().normal())
-                .with_translation(from.position() + from.face().normal() * SAME_FACE_EDGE_HEIGHT)
+                .with_translation(from.position() + from.face().normal() * edge_height)
         }
         BorderType::Connected => get_cross_face_edge_transform(
             from.position(),
             from.face().normal(),
             to.position(),
             to.face().normal(),
+            edge_height,
         ),
     }
 }
@@ -182,6 +264,7 @@ pub fn get_cross_face_edge_transform(
     from_normal: Vec3,
     to_position: Vec3,
     to_normal: Vec3,
+    edge_height: f32,
 ) -> Transform {
     let half_angle = from_normal.angle_between(to_normal) / 2.0;
 
@@ -194,7 +277,7 @@ pub fn get_cross_face_edge_transform(
 
     Transform::IDENTITY
         .looking_to(intersection_point - to_position, to_normal)
-        .with_translation(intersection_point + average_normal * CROSS_FACE_EDGE_HEIGHT)
+        .with_translation(intersection_point + average_normal * edge_height)
 }
 
 pub fn make_room_pairs_from_discovered_melodies(
@@ -220,3 +303,24 @@ pub fn update_on_melody_discovered(
         commands.entity(maze_entity).despawn_recursive();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surface_offsets_scale_with_the_distance_between_nodes() {
+        let near = SurfaceOffsets::new(0.1, 1.0);
+        let far = SurfaceOffsets::new(0.4, 1.0);
+
+        assert!((far.room - 4.0 * near.room).abs() < 1e-6);
+        assert!((far.edge - 4.0 * near.edge).abs() < 1e-6);
+        assert!(near.edge < near.room);
+
+        let raised = SurfaceOffsets::new(0.1, 3.0);
+        assert!((raised.room - 3.0 * near.room).abs() < 1e-6);
+
+        let flattened = SurfaceOffsets::new(0.1, -1.0);
+        assert_eq!((flattened.room, flattened.edge), (0.0, 0.0));
+    }
+}
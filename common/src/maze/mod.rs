@@ -1,3 +1,11 @@
 pub mod border_type;
 pub mod maze_mesh_builder;
 pub mod mesh;
+pub mod solver;
+
+use crate::room::Room;
+
+// Each step along a solution as a (from, to) pair of consecutive rooms.
+pub fn solution_edges(solution: &[Room]) -> impl Iterator<Item = (Room, Room)> + '_ {
+    solution.windows(2).map(|pair| (pair[0], pair[1]))
+}
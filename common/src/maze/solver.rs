@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+
+use bevy::utils::HashMap;
+use petgraph::{graphmap::GraphMap, Directed};
+
+use crate::room::{Edge, Room};
+
+// The fewest moves from `from` to `to` along the maze's directed edges, including both ends.
+// Returns `None` when either room is missing from the graph or `to` cannot be reached.
+pub fn shortest_path(graph: &GraphMap<Room, Edge, Directed>, from: Room, to: Room) -> Option<Vec<Room>> {
+    if !graph.contains_node(from) || !graph.contains_node(to) {
+        return None;
+    }
+
+    let (visit_order, predecessors) = breadth_first_search(graph, from);
+
+    visit_order
+        .contains(&to)
+        .then(|| path_to(to, &predecessors))
+}
+
+// Every edge costs one move, so a breadth first search finds shortest paths without weights.
+pub(crate) fn breadth_first_search(
+    graph: &GraphMap<Room, Edge, Directed>,
+    start: Room,
+) -> (Vec<Room>, HashMap<Room, Room>) {
+    let mut predecessors = HashMap::<Room, Room>::new();
+    let mut visit_order = vec![];
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(room) = queue.pop_front() {
+        visit_order.push(room);
+
+        for neighbor in graph.neighbors(room) {
+            if neighbor != start && !predecessors.contains_key(&neighbor) {
+                predecessors.insert(neighbor, room);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    (visit_order, predecessors)
+}
+
+pub(crate) fn path_to(goal: Room, predecessors: &HashMap<Room, Room>) -> Vec<Room> {
+    let mut path = vec![goal];
+    while let Some(predecessor) = predecessors.get(path.last().unwrap()) {
+        path.push(*predecessor);
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::Vec3;
+
+    use crate::room::{test_support::room_on_face, Face};
+
+    use super::*;
+
+    const TOP: Face = Face { id: 0, normal: Vec3::Y };
+    const FRONT: Face = Face { id: 1, normal: Vec3::Z };
+
+    // Two rooms on the top face, a third around the edge on the front face, and a fourth
+    // on the front face with no doors.
+    fn rooms() -> [Room; 4] {
+        [
+            room_on_face(0, Vec3::new(-0.5, 1.0, 0.0), TOP),
+            room_on_face(1, Vec3::new(0.5, 1.0, 0.0), TOP),
+            room_on_face(2, Vec3::new(0.5, 0.0, 1.0), FRONT),
+            room_on_face(3, Vec3::new(-0.5, 0.0, 1.0), FRONT),
+        ]
+    }
+
+    fn graph(rooms: &[Room; 4]) -> GraphMap<Room, Edge, Directed> {
+        let mut graph = GraphMap::new();
+        for room in rooms {
+            graph.add_node(room.clone());
+        }
+        for (from, to) in [(0, 1), (1, 0), (1, 2), (2, 1)] {
+            graph.add_edge(rooms[from].clone(), rooms[to].clone(), Edge);
+        }
+        graph
+    }
+
+    fn ids(path: Option<Vec<Room>>) -> Option<Vec<u64>> {
+        path.map(|path| path.iter().map(|room| room.id).collect())
+    }
+
+    #[test]
+    fn shortest_path_follows_an_edge_on_the_same_face() {
+        let rooms = rooms();
+
+        let path = shortest_path(&graph(&rooms), rooms[0].clone(), rooms[1].clone());
+
+        assert_eq!(ids(path), Some(vec![0, 1]));
+    }
+
+    #[test]
+    fn shortest_path_crosses_onto_another_face() {
+        let rooms = rooms();
+
+        let path = shortest_path(&graph(&rooms), rooms[0].clone(), rooms[2].clone());
+
+        assert_eq!(ids(path), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn shortest_path_is_none_between_disconnected_rooms() {
+        let rooms = rooms();
+        let graph = graph(&rooms);
+
+        assert_eq!(ids(shortest_path(&graph, rooms[0].clone(), rooms[3].clone())), None);
+        assert_eq!(ids(shortest_path(&graph, rooms[3].clone(), rooms[0].clone())), None);
+    }
+}
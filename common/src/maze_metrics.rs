@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use itertools::Itertools;
+use petgraph::{graphmap::GraphMap, Directed, Direction};
+
+use crate::{
+    is_room_junction::is_junction,
+    maze::solver::breadth_first_search,
+    room::{Edge, Room},
+    shape::loader::{GraphComponent, SolutionComponent},
+};
+
+// Shape of the current maze, for balancing and difficulty display. Lengths count moves between
+// rooms, not rooms.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct MazeMetrics {
+    pub solution_length: usize,
+    pub junction_count: usize,
+    pub dead_end_count: usize,
+    pub diameter: usize,
+}
+
+impl MazeMetrics {
+    pub fn new(graph: &GraphMap<Room, Edge, Directed>, solution: &[Room]) -> Self {
+        MazeMetrics {
+            solution_length: solution.len().saturating_sub(1),
+            junction_count: graph.nodes().filter(|room| is_junction(room, graph)).count(),
+            dead_end_count: graph.nodes().filter(|room| is_dead_end(room, graph)).count(),
+            diameter: diameter(graph),
+        }
+    }
+}
+
+fn is_dead_end(room: &Room, graph: &GraphMap<Room, Edge, Directed>) -> bool {
+    graph
+        .neighbors_directed(*room, Direction::Incoming)
+        .chain(graph.neighbors_directed(*room, Direction::Outgoing))
+        .unique()
+        .count()
+        == 1
+}
+
+// The longest of the shortest paths between any two rooms, following one-way edges.
+fn diameter(graph: &GraphMap<Room, Edge, Directed>) -> usize {
+    graph
+        .nodes()
+        .map(|start| {
+            let (visit_order, predecessors) = breadth_first_search(graph, start);
+
+            // The last room visited is always one of the farthest.
+            visit_order.last().map_or(0, |farthest| {
+                std::iter::successors(Some(farthest), |room| predecessors.get(*room)).count() - 1
+            })
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn update(
+    mut maze_metrics: ResMut<MazeMetrics>,
+    maze_query: Query<(&GraphComponent, &SolutionComponent)>,
+) {
+    let Ok((GraphComponent(graph), SolutionComponent(solution))) = maze_query.get_single() else {
+        return;
+    };
+
+    *maze_metrics = MazeMetrics::new(graph, solution);
+
+    info!(target: "mazonic::maze_metrics", "{:?}", *maze_metrics);
+}
@@ -0,0 +1,70 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    game_save::{save_directory, CurrentPuzzle, SaveLocation},
+    play_statistics::PlayStatistics,
+    sound::export_melody_midi,
+    ui::message::{MessagePopup, MessagePopupUpperMarker},
+};
+
+const EXPORT_KEY: KeyCode = KeyCode::KeyM;
+const EXPORT_DIRECTORY: &str = "melodies";
+
+pub fn export_requested(keys: Res<ButtonInput<KeyCode>>) -> bool {
+    let control = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    control && shift && keys.just_pressed(EXPORT_KEY)
+}
+
+// Writes the current puzzle's discovered melody next to the save, named after the melody.
+pub fn export(
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    play_statistics: Res<PlayStatistics>,
+    save_location: Option<Res<SaveLocation>>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+) {
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let Some(discovered_melody) = play_statistics
+        .0
+        .get(puzzle_identifier)
+        .and_then(|statistics| statistics.discovered_melody.as_ref())
+    else {
+        if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+            message_popup.0 = "no melody to export".to_string();
+        }
+        return;
+    };
+
+    let directory = save_directory(save_location.as_deref()).join(EXPORT_DIRECTORY);
+    let file_name = discovered_melody
+        .melody
+        .name
+        .chars()
+        .map(|character| if character.is_alphanumeric() { character } else { '_' })
+        .collect::<String>();
+    let path = directory.join(format!("{file_name}.mid"));
+
+    let result = fs::create_dir_all(&directory)
+        .and_then(|_| fs::write(&path, export_melody_midi(&discovered_melody.melody)));
+
+    let message = match result {
+        Ok(()) => {
+            info!(target: "mazonic::melody_export", "Wrote melody to {:?}", path);
+            "melody saved"
+        }
+        Err(err) => {
+            warn!(target: "mazonic::melody_export", "Failed to write melody to {:?}: {}", path, err);
+            "could not save melody"
+        }
+    };
+
+    if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+        message_popup.0 = message.to_string();
+    }
+}
@@ -1,14 +1,47 @@
 use bevy::prelude::*;
+use chrono::Utc;
 
-use crate::{game_save::WorkingLevelIndex, game_state::GameState};
+use crate::{
+    game_save::{CurrentPuzzle, PendingResume, PuzzleProgress, ResumablePuzzle, WorkingLevelIndex},
+    game_state::GameState,
+};
 
-pub fn setup(mut next_game_state: ResMut<NextState<GameState>>, working_level_index_query: Query<&WorkingLevelIndex>) {
+// Unfinished progress offered once at launch, cleared as soon as the selector is left.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ResumeOffer(pub Option<PuzzleProgress>);
+
+pub fn setup(
+    mut commands: Commands,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    working_level_index_query: Query<&WorkingLevelIndex>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    resumable_puzzle: Res<ResumablePuzzle>,
+) {
     let WorkingLevelIndex(index) = working_level_index_query.single();
 
+    let today = Utc::now().date_naive();
+    let resumable_progress = resumable_puzzle
+        .0
+        .clone()
+        .filter(|progress| !progress.is_stale(today));
+
     if *index > 0 {
+        commands.insert_resource(ResumeOffer(resumable_progress));
         next_game_state.set(GameState::Selector)
     } else {
+        // The first puzzle opens straight away, so there is nothing to choose; just carry on.
+        let CurrentPuzzle(current_puzzle) = current_puzzle_query.single();
+        let pending_resume = resumable_progress.filter(|progress| progress.puzzle == *current_puzzle);
+        commands.insert_resource(PendingResume(pending_resume));
         next_game_state.set(GameState::Puzzle)
     }
 
 }
+
+pub fn resume_offered(resume_offer: Option<Res<ResumeOffer>>) -> bool {
+    resume_offer.is_some_and(|resume_offer| resume_offer.0.is_some())
+}
+
+pub fn withdraw_resume_offer(mut resume_offer: ResMut<ResumeOffer>) {
+    resume_offer.0 = None;
+}
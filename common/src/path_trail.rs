@@ -0,0 +1,106 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    assets::{material_handles::MaterialHandles, mesh_handles::MeshHandles},
+    game_settings::GameSettings,
+    levels::{GameLevel, PuzzleEntityMarker, Shape},
+    maze::{border_type::BorderType, mesh::{get_connection_transform, SurfaceOffsets}},
+    player_path::{undirected_edge_key, PlayerPath},
+};
+
+
+#[derive(Component, Debug, Clone)]
+pub struct PathTrailSegment(pub (u64, u64));
+
+pub fn path_trail_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.show_path_trail
+}
+
+pub fn update(
+    mut commands: Commands,
+    player_path_query: Query<&PlayerPath, Changed<PlayerPath>>,
+    trail_segment_query: Query<(Entity, &PathTrailSegment)>,
+    level_query: Query<&GameLevel>,
+    mesh_handles: Res<MeshHandles>,
+    material_handles: Res<MaterialHandles>,
+    game_settings: Res<GameSettings>,
+) {
+    let Ok(PlayerPath(path)) = player_path_query.get_single() else {
+        return;
+    };
+
+    // Undo truncates the path, so any segment whose edge the path no longer crosses is removed.
+    let path_edges = path
+        .iter()
+        .zip(path.iter().skip(1))
+        .filter(|(from, to)| from != to)
+        .map(|(from, to)| undirected_edge_key(from, to))
+        .collect::<HashSet<_>>();
+
+    let mut trailed_edges = HashSet::new();
+    for (entity, PathTrailSegment(edge)) in trail_segment_query.iter() {
+        if path_edges.contains(edge) {
+            trailed_edges.insert(*edge);
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    if path_edges.is_empty() {
+        return;
+    }
+
+    let Ok(level) = level_query.get_single() else {
+        return;
+    };
+
+    let maze_mesh_handles = match &level.shape {
+        Shape::Tetrahedron(_) => &mesh_handles.shape_maze_edge_mesh_handles.tetrahedron,
+        Shape::Cube(_) => &mesh_handles.shape_maze_edge_mesh_handles.cube,
+        Shape::Octahedron(_) => &mesh_handles.shape_maze_edge_mesh_handles.octahedron,
+        Shape::Dodecahedron(_) => &mesh_handles.shape_maze_edge_mesh_handles.dodecahedron,
+        Shape::Icosahedron(_) => &mesh_handles.shape_maze_edge_mesh_handles.icosahedron,
+    };
+
+    let distance_between_nodes = level.node_distance();
+    let surface_offsets = SurfaceOffsets::new(distance_between_nodes, game_settings.maze_surface_offset);
+
+    for (from, to) in path.iter().zip(path.iter().skip(1)) {
+        if from == to {
+            continue;
+        }
+
+        // Walking back along an edge keeps its first highlight.
+        let edge = undirected_edge_key(from, to);
+        if !trailed_edges.insert(edge) {
+            continue;
+        }
+
+        let Some(border_type) = level.border_type(&from.face(), &to.face()) else {
+            continue;
+        };
+
+        let mesh_handle = match border_type {
+            BorderType::SameFace => maze_mesh_handles.one_way_same_face_edge.clone(),
+            BorderType::Connected => maze_mesh_handles.one_way_cross_face_edge.clone(),
+        };
+
+        // Sits one edge offset above the maze edge it highlights.
+        let elevation = from.face().normal().lerp(to.face().normal(), 0.5).normalize() * surface_offsets.edge;
+        let mut transform = get_connection_transform(*from, *to, &border_type, surface_offsets.edge);
+        transform.translation += elevation;
+
+        commands
+            .spawn((
+                transform,
+                PathTrailSegment(edge),
+                PuzzleEntityMarker,
+                Visibility::default(),
+            ))
+            .with_child((
+                Mesh3d(mesh_handle),
+                MeshMaterial3d(material_handles.path_trail_handle.clone()),
+                Transform::IDENTITY.with_scale(Vec3::splat(distance_between_nodes)),
+            ));
+    }
+}
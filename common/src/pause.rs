@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_state::PuzzleState,
+    player::PlayerMazeState,
+    sound::{AudioPaused, PreviousNoteRoom},
+    ui::message::{MessagePopup, MessagePopupUpperMarker},
+};
+
+#[derive(SubStates, Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[source(PuzzleState = PuzzleState::Playing)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+#[derive(Event)]
+pub struct TogglePause;
+
+// Also true outside of play, where there is no pause state, so shared systems keep running.
+pub fn not_paused(pause_state: Option<Res<State<PauseState>>>) -> bool {
+    !pause_state.is_some_and(|pause_state| *pause_state.get() == PauseState::Paused)
+}
+
+pub fn read_keys(keys: Res<ButtonInput<KeyCode>>, mut toggle_pause_writer: EventWriter<TogglePause>) {
+    if keys.just_pressed(KeyCode::Escape) {
+        toggle_pause_writer.send(TogglePause);
+    }
+}
+
+pub fn toggle(
+    mut toggle_pause_reader: EventReader<TogglePause>,
+    pause_state: Res<State<PauseState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+) {
+    // A key press and a button press in the same frame should not cancel each other out.
+    if toggle_pause_reader.read().count() == 0 {
+        return;
+    }
+
+    next_pause_state.set(match pause_state.get() {
+        PauseState::Running => PauseState::Paused,
+        PauseState::Paused => PauseState::Running,
+    });
+}
+
+pub fn on_pause(
+    mut audio_paused: ResMut<AudioPaused>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+) {
+    audio_paused.menu = true;
+
+    if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+        message_popup.0 = "paused".to_string();
+    }
+}
+
+// Whatever room the player rests on was already sounded before pausing, so it must not play again
+// when note playback picks up.
+pub fn on_resume(
+    mut audio_paused: ResMut<AudioPaused>,
+    mut previous_note_room: ResMut<PreviousNoteRoom>,
+    player_query: Query<&PlayerMazeState>,
+) {
+    audio_paused.menu = false;
+
+    if let Ok(PlayerMazeState::Node(room)) = player_query.get_single() {
+        previous_note_room.0 = Some(*room);
+    }
+}
@@ -3,39 +3,124 @@ use std::time::Duration;
 use bevy::{prelude::*, time::Stopwatch, utils::{HashMap, HashSet}};
 use serde::{Deserialize, Serialize};
 
-use crate::game_save::{CurrentPuzzle, DiscoveredMelody, LevelIndex, PuzzleIdentifier};
+use crate::{
+    game_save::{CurrentPuzzle, DiscoveredMelody, PuzzleIdentifier},
+    game_settings::GameSettings,
+    levels::CampaignOrder,
+    maze::mesh::RevealedSolutionEdge,
+    player::PlayerMazeState,
+    player_path::PlayerPath,
+    room::Room,
+    shape::loader::{ReversedSolve, SolutionComponent},
+    snap_assist::SnapAssist,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PuzzleStatistics {
     pub completed: bool,
     pub time_spent: Duration,
     pub discovered_melody: Option<DiscoveredMelody>,
+    #[serde(default)]
+    pub peak_flow: Option<f32>,
+    #[serde(default)]
+    pub perfect: bool,
+    #[serde(default)]
+    pub edges_crossed: Option<usize>,
+    #[serde(default)]
+    pub followed_solution: bool,
+    #[serde(default)]
+    pub completed_reversed: bool,
+    #[serde(default)]
+    pub assisted: bool,
+    // The fewest moves any unassisted, forward completion has taken.
+    #[serde(default)]
+    pub best_score: Option<usize>,
+    // The most stars any unassisted, forward completion has earned.
+    #[serde(default)]
+    pub best_stars: Option<u8>,
 }
 
+// A perfect solve earns three stars, and one within this many times the solution's length earns two.
+pub const TWO_STAR_SOLUTION_RATIO: f32 = 1.5;
+pub const MAX_STARS: u8 = 3;
+
 impl PuzzleStatistics {
+    // Combines two records of the same puzzle, keeping the better value of each field.
+    pub fn merge(self, other: Self) -> Self {
+        let time_spent = match (self.completed, other.completed) {
+            (true, true) => self.time_spent.min(other.time_spent),
+            (true, false) => self.time_spent,
+            (false, true) => other.time_spent,
+            (false, false) => self.time_spent.max(other.time_spent),
+        };
+
+        Self {
+            completed: self.completed || other.completed,
+            time_spent,
+            discovered_melody: self.discovered_melody.or(other.discovered_melody),
+            peak_flow: match (self.peak_flow, other.peak_flow) {
+                (Some(peak), Some(other_peak)) => Some(peak.max(other_peak)),
+                (peak, other_peak) => peak.or(other_peak),
+            },
+            perfect: self.perfect || other.perfect,
+            edges_crossed: self.edges_crossed.max(other.edges_crossed),
+            followed_solution: self.followed_solution || other.followed_solution,
+            completed_reversed: self.completed_reversed || other.completed_reversed,
+            assisted: match (self.completed, other.completed) {
+                (true, true) => self.assisted && other.assisted,
+                (true, false) => self.assisted,
+                (false, true) => other.assisted,
+                (false, false) => false,
+            },
+            best_score: match (self.best_score, other.best_score) {
+                (Some(best), Some(other_best)) => Some(best.min(other_best)),
+                (best, other_best) => best.or(other_best),
+            },
+            best_stars: self.best_stars.max(other.best_stars),
+        }
+    }
+
     pub fn completed() -> Self {
         Self {
             completed: true,
             time_spent: Duration::from_secs(0),
             discovered_melody: None,
+            peak_flow: None,
+            perfect: false,
+            edges_crossed: None,
+            followed_solution: false,
+            completed_reversed: false,
+            assisted: false,
+            best_score: None,
+            best_stars: None,
         }
     }
 }
 
+pub fn star_rating(score: usize, solution_length: usize, perfect: bool) -> u8 {
+    if perfect {
+        MAX_STARS
+    } else if score as f32 <= solution_length as f32 * TWO_STAR_SOLUTION_RATIO {
+        2
+    } else {
+        1
+    }
+}
+
 #[derive(Resource, Debug, Clone)]
 pub struct PlayStatistics(pub HashMap<PuzzleIdentifier, PuzzleStatistics>);
 
 impl PlayStatistics {
-    pub fn get_working_level(&self) -> LevelIndex {
-        let highest_completed_level_index = self.0.iter()
+    pub fn get_working_level(&self, campaign_order: &CampaignOrder) -> usize {
+        let highest_completed_campaign_position = self.0.iter()
             .filter_map(|(puzzle_identifier, puzzle_statistics)| match puzzle_identifier { 
-                    PuzzleIdentifier::Level(level) if puzzle_statistics.completed => Some(level), 
+                    PuzzleIdentifier::Level(level) if puzzle_statistics.completed => campaign_order.campaign_position(*level), 
                     _ => None
             })
             .max();
 
-        match highest_completed_level_index {
-            Some(level) => level + 1,
+        match highest_completed_campaign_position {
+            Some(campaign_position) => campaign_position + 1,
             None => 0,
         }
     }
@@ -53,6 +138,25 @@ impl PlayStatistics {
 pub struct SolveTime {
     pub stopwatch: Stopwatch,
     pub running: bool, 
+    pub awaiting_start: bool,
+}
+
+impl SolveTime {
+    pub fn start(&mut self) {
+        if self.awaiting_start {
+            self.awaiting_start = false;
+            self.running = true;
+        }
+    }
+}
+
+// When the clock of an unfinished daily starts; campaign levels always time from entry.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DailyTimerStart {
+    #[default]
+    Immediately,
+    FirstMove,
+    AfterPlanning,
 }
 
 pub fn setup(mut commands: Commands) {
@@ -63,6 +167,7 @@ pub fn on_play(
     mut solve_time: ResMut<SolveTime>,
     current_puzzle_query: Query<&CurrentPuzzle>,
     mut play_statistics: ResMut<PlayStatistics>,
+    game_settings: Res<GameSettings>,
 ) {
     let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
         return;
@@ -70,23 +175,100 @@ pub fn on_play(
 
     let statistics = play_statistics.0.entry(puzzle_identifier.clone()).or_insert(PuzzleStatistics::default());
 
+    let is_daily = !matches!(puzzle_identifier, PuzzleIdentifier::Level(_));
+    let awaiting_start = !statistics.completed
+        && is_daily
+        && game_settings.daily_timer_start != DailyTimerStart::Immediately;
+
     solve_time.stopwatch.set_elapsed(statistics.time_spent);
-    solve_time.running = !statistics.completed;
+    solve_time.running = !statistics.completed && !awaiting_start;
+    solve_time.awaiting_start = awaiting_start;
+}
+
+// Only leaving the start node counts; presses inside the dead zone and orbiting the camera never
+// change the player's maze state, so neither starts the clock.
+pub fn start_timer_on_first_move(
+    player_query: Query<&PlayerMazeState, Changed<PlayerMazeState>>,
+    mut solve_time: ResMut<SolveTime>,
+) {
+    if !solve_time.awaiting_start {
+        return;
+    }
+
+    if let Ok(PlayerMazeState::Edge(..)) = player_query.get_single() {
+        solve_time.start();
+    }
 }
 
 pub fn on_victory(
     current_puzzle_query: Query<&CurrentPuzzle>,
+    player_path_query: Query<&PlayerPath>,
+    solution_query: Query<&SolutionComponent>,
+    reversed_solve_query: Query<(), With<ReversedSolve>>,
+    snap_assist: Res<SnapAssist>,
+    revealed_edge_query: Query<(), With<RevealedSolutionEdge>>,
     mut play_statistics: ResMut<PlayStatistics>,
 ) {
     let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
         return;
     };
 
+    // A reversed solve is recorded on its own so it neither unlocks levels nor counts as perfect.
+    if !reversed_solve_query.is_empty() {
+        play_statistics.0.entry(puzzle_identifier.clone()).and_modify(|puzzle_statistics| {
+            puzzle_statistics.completed_reversed = true;
+        });
+        return;
+    }
+
+    // An assisted finish still completes the level but is never perfect. The flag only stays set
+    // while every completion so far has been assisted.
+    if snap_assist.assisted {
+        play_statistics.0.entry(puzzle_identifier.clone()).and_modify(|puzzle_statistics| {
+            puzzle_statistics.assisted = !puzzle_statistics.completed || puzzle_statistics.assisted;
+            puzzle_statistics.completed = true;
+        });
+        return;
+    }
+
+    // The goal can be reached by routes other than the stored solution, which still count as a
+    // completion and can still be perfect if they are no longer than the solution.
+    let (perfect, followed_solution) = match (player_path_query.get_single(), solution_query.get_single()) {
+        (Ok(PlayerPath(path)), Ok(SolutionComponent(solution))) => {
+            (is_perfect_solve(path, solution), path == solution)
+        }
+        _ => (false, false),
+    };
+
+    // Following a revealed solution still completes the level, but can never be perfect.
+    let perfect = perfect && revealed_edge_query.is_empty();
+
+    let score = player_path_query
+        .get_single()
+        .ok()
+        .map(|PlayerPath(path)| path.len().saturating_sub(1));
+
+    let stars = score.zip(solution_query.get_single().ok()).map(|(score, SolutionComponent(solution))| {
+        star_rating(score, solution.len().saturating_sub(1), perfect)
+    });
+
     play_statistics.0.entry(puzzle_identifier.clone()).and_modify(|puzzle_statistics| {
-        puzzle_statistics.completed = true
+        puzzle_statistics.completed = true;
+        puzzle_statistics.assisted = false;
+        puzzle_statistics.perfect |= perfect;
+        puzzle_statistics.followed_solution |= followed_solution;
+        puzzle_statistics.best_score = match (puzzle_statistics.best_score, score) {
+            (Some(best), Some(score)) => Some(best.min(score)),
+            (best, score) => best.or(score),
+        };
+        puzzle_statistics.best_stars = puzzle_statistics.best_stars.max(stars);
     });
 }
 
+fn is_perfect_solve(path: &Vec<Room>, solution: &Vec<Room>) -> bool {
+    !path.is_empty() && path.len() <= solution.len()
+}
+
 pub fn during_play(time: Res<Time>, mut solve_time: ResMut<SolveTime>) {
     if solve_time.running {
         solve_time.stopwatch.tick(time.delta());
@@ -109,3 +291,267 @@ pub fn exit_play(
     });
 }
 
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::{
+        controller::{self, ControllerState},
+        controller_screen_position::ControllerScreenPosition,
+        levels::LEVELS,
+        room::test_support::room,
+        sound::Melody,
+    };
+
+    use super::*;
+
+    fn rooms(ids: &[u64]) -> Vec<Room> {
+        ids.iter().map(|id| room(*id)).collect()
+    }
+
+    fn puzzle_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(PlayStatistics(HashMap::from([(
+            PuzzleIdentifier::Level(0),
+            PuzzleStatistics::default(),
+        )])));
+        world.init_resource::<SnapAssist>();
+        world.spawn(CurrentPuzzle(PuzzleIdentifier::Level(0)));
+        world.spawn(SolutionComponent(rooms(&[0, 1, 2, 3])));
+        world
+    }
+
+    fn finish_with_path(world: &mut World, path: &[u64]) -> PuzzleStatistics {
+        let player = world.spawn(PlayerPath(rooms(path))).id();
+        world.run_system_once(on_victory).unwrap();
+        world.despawn(player);
+
+        world.resource::<PlayStatistics>().0[&PuzzleIdentifier::Level(0)].clone()
+    }
+
+    #[test]
+    fn a_worse_replay_keeps_the_better_score() {
+        let mut world = puzzle_world();
+
+        let best = finish_with_path(&mut world, &[0, 1, 2, 3]);
+        assert_eq!(best.best_score, Some(3));
+        assert_eq!(best.best_stars, Some(MAX_STARS));
+
+        let replay = finish_with_path(&mut world, &[0, 1, 2, 1, 2, 3]);
+        assert_eq!(replay.best_score, Some(3));
+        assert_eq!(replay.best_stars, Some(MAX_STARS));
+    }
+
+    #[test]
+    fn a_better_replay_improves_the_score() {
+        let mut world = puzzle_world();
+
+        let first = finish_with_path(&mut world, &[0, 1, 2, 1, 2, 1, 2, 3]);
+        assert_eq!(first.best_score, Some(7));
+        assert_eq!(first.best_stars, Some(1));
+
+        let replay = finish_with_path(&mut world, &[0, 1, 2, 3]);
+        assert_eq!(replay.best_score, Some(3));
+        assert_eq!(replay.best_stars, Some(MAX_STARS));
+    }
+
+    #[test]
+    fn a_route_off_the_solution_still_completes_the_puzzle() {
+        let mut world = puzzle_world();
+
+        let detour = finish_with_path(&mut world, &[0, 1, 4, 5, 3]);
+        assert!(detour.completed);
+        assert!(!detour.followed_solution);
+
+        let solution = finish_with_path(&mut world, &[0, 1, 2, 3]);
+        assert!(solution.followed_solution);
+    }
+
+    fn start_puzzle(puzzle_identifier: PuzzleIdentifier, daily_timer_start: DailyTimerStart) -> (World, Entity) {
+        let mut world = World::new();
+        world.insert_resource(GameSettings { daily_timer_start, ..default() });
+        world.insert_resource(PlayStatistics(HashMap::new()));
+        world.init_resource::<SolveTime>();
+        world.init_resource::<Time>();
+        world.spawn(CurrentPuzzle(puzzle_identifier));
+        let player = world.spawn(PlayerMazeState::Node(room(0))).id();
+
+        world.run_system_once(on_play).unwrap();
+        (world, player)
+    }
+
+    fn play_for(world: &mut World, seconds: u64) {
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(seconds));
+        world.run_system_once(start_timer_on_first_move).unwrap();
+        world.run_system_once(during_play).unwrap();
+    }
+
+    // The controller and clock systems with the run conditions they are scheduled under, with any
+    // requested controller state applied before the next frame.
+    fn controller_frame(world: &mut World, controller_schedule: &mut Schedule, seconds: u64) {
+        world.resource_mut::<Time>().advance_by(Duration::from_secs(seconds));
+        controller_schedule.run(world);
+
+        if let NextState::Pending(controller_state) = world.resource::<NextState<ControllerState>>().clone() {
+            world.insert_resource(State::new(controller_state));
+            world.insert_resource(NextState::<ControllerState>::Unchanged);
+        }
+    }
+
+    #[test]
+    fn viewing_and_orbiting_a_daily_does_not_start_its_clock() {
+        let daily = PuzzleIdentifier::EasyDaily("2026-01-01".to_string());
+        let (mut world, player) = start_puzzle(daily, DailyTimerStart::FirstMove);
+        world.insert_resource(State::new(ControllerState::Viewing));
+        world.init_resource::<NextState<ControllerState>>();
+        let cursor = world.spawn(ControllerScreenPosition::Position(Vec2::ZERO)).id();
+
+        let mut controller_schedule = Schedule::default();
+        controller_schedule.add_systems(
+            (
+                controller::view.run_if(in_state(ControllerState::Viewing)),
+                start_timer_on_first_move.run_if(in_state(ControllerState::Solving)),
+                during_play,
+            )
+                .chain(),
+        );
+
+        // Dragging across the screen orbits the camera for as long as the pointer is held.
+        for step in 1..=5 {
+            *world.get_mut::<ControllerScreenPosition>(cursor).unwrap() =
+                ControllerScreenPosition::Position(Vec2::new(40.0 * step as f32, 10.0 * step as f32));
+            controller_frame(&mut world, &mut controller_schedule, 1);
+            assert_eq!(*world.resource::<State<ControllerState>>().get(), ControllerState::Viewing);
+        }
+
+        *world.get_mut::<ControllerScreenPosition>(cursor).unwrap() = ControllerScreenPosition::None;
+        controller_frame(&mut world, &mut controller_schedule, 1);
+        assert_eq!(*world.resource::<State<ControllerState>>().get(), ControllerState::IdlePostView);
+        assert!(matches!(world.get::<PlayerMazeState>(player), Some(PlayerMazeState::Node(room)) if room.id == 0));
+        assert!(!world.resource::<SolveTime>().running);
+        assert_eq!(world.resource::<SolveTime>().stopwatch.elapsed(), Duration::ZERO);
+
+        world.insert_resource(State::new(ControllerState::Solving));
+        *world.get_mut::<ControllerScreenPosition>(cursor).unwrap() = ControllerScreenPosition::Position(Vec2::ZERO);
+        *world.get_mut::<PlayerMazeState>(player).unwrap() = PlayerMazeState::Edge(room(0), room(1), Vec3::X * 0.5);
+        controller_frame(&mut world, &mut controller_schedule, 2);
+        assert!(world.resource::<SolveTime>().running);
+        assert_eq!(world.resource::<SolveTime>().stopwatch.elapsed(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn campaign_levels_time_from_entry_whatever_the_daily_setting() {
+        let (mut world, _) = start_puzzle(PuzzleIdentifier::Level(0), DailyTimerStart::FirstMove);
+
+        play_for(&mut world, 3);
+        assert_eq!(world.resource::<SolveTime>().stopwatch.elapsed(), Duration::from_secs(3));
+    }
+
+    fn record(completed: bool, seconds: u64, assisted: bool, best_score: Option<usize>) -> PuzzleStatistics {
+        PuzzleStatistics {
+            completed,
+            time_spent: Duration::from_secs(seconds),
+            assisted,
+            best_score,
+            ..PuzzleStatistics::default()
+        }
+    }
+
+    fn discovered(name: &str, room_ids: &[u64]) -> Option<DiscoveredMelody> {
+        Some(DiscoveredMelody {
+            melody: Melody { name: name.to_string(), ..Melody::default() },
+            room_ids: room_ids.to_vec(),
+        })
+    }
+
+    #[test]
+    fn merge_keeps_the_current_melody_and_takes_an_imported_one_only_when_missing() {
+        let current = PuzzleStatistics { discovered_melody: discovered("current", &[0, 1]), ..PuzzleStatistics::default() };
+        let imported = PuzzleStatistics { discovered_melody: discovered("imported", &[2, 3]), ..PuzzleStatistics::default() };
+
+        let both = current.clone().merge(imported.clone());
+        assert_eq!(both.discovered_melody.map(|discovered| discovered.room_ids), Some(vec![0, 1]));
+
+        let only_imported = PuzzleStatistics::default().merge(imported);
+        assert_eq!(only_imported.discovered_melody.map(|discovered| discovered.melody.name), Some("imported".to_string()));
+
+        let only_current = current.merge(PuzzleStatistics::default());
+        assert_eq!(only_current.discovered_melody.map(|discovered| discovered.melody.name), Some("current".to_string()));
+    }
+
+    #[test]
+    fn merge_keeps_the_faster_of_two_completions() {
+        let merged = record(true, 90, false, None).merge(record(true, 60, false, None));
+
+        assert!(merged.completed);
+        assert_eq!(merged.time_spent, Duration::from_secs(60));
+    }
+
+    #[test]
+    fn merge_keeps_the_completed_time_over_an_unfinished_one() {
+        let current_completed = record(true, 90, false, None).merge(record(false, 30, false, None));
+        let imported_completed = record(false, 30, false, None).merge(record(true, 90, false, None));
+
+        assert!(current_completed.completed && imported_completed.completed);
+        assert_eq!(current_completed.time_spent, Duration::from_secs(90));
+        assert_eq!(imported_completed.time_spent, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn merge_keeps_the_longer_time_of_two_unfinished_attempts() {
+        let merged = record(false, 30, false, None).merge(record(false, 45, false, None));
+
+        assert!(!merged.completed);
+        assert_eq!(merged.time_spent, Duration::from_secs(45));
+    }
+
+    #[test]
+    fn merge_is_assisted_only_while_every_completion_was() {
+        let assisted = |current: (bool, bool), imported: (bool, bool)| {
+            record(current.0, 0, current.1, None)
+                .merge(record(imported.0, 0, imported.1, None))
+                .assisted
+        };
+
+        // (completed, assisted) for the current and the imported record.
+        assert!(assisted((true, true), (true, true)));
+        assert!(!assisted((true, true), (true, false)));
+        assert!(!assisted((true, false), (true, true)));
+        assert!(assisted((true, true), (false, false)));
+        assert!(!assisted((true, false), (false, true)));
+        assert!(assisted((false, false), (true, true)));
+        assert!(!assisted((false, true), (true, false)));
+        assert!(!assisted((false, true), (false, true)));
+    }
+
+    #[test]
+    fn merge_keeps_the_lowest_best_score() {
+        let both = record(true, 0, false, Some(9)).merge(record(true, 0, false, Some(6)));
+        let current_only = record(true, 0, false, Some(9)).merge(record(true, 0, false, None));
+        let imported_only = record(true, 0, false, None).merge(record(true, 0, false, Some(6)));
+
+        assert_eq!(both.best_score, Some(6));
+        assert_eq!(current_only.best_score, Some(9));
+        assert_eq!(imported_only.best_score, Some(6));
+    }
+
+    #[test]
+    fn working_level_skips_levels_outside_the_campaign() {
+        let play_statistics = PlayStatistics(HashMap::from([
+            (PuzzleIdentifier::Level(0), PuzzleStatistics::completed()),
+            (PuzzleIdentifier::Level(LEVELS.len() + 3), PuzzleStatistics::completed()),
+        ]));
+
+        assert_eq!(play_statistics.get_working_level(&CampaignOrder::Standard), 1);
+    }
+
+    #[test]
+    fn working_level_follows_the_campaign_order() {
+        let hardest = *CampaignOrder::Gentle.level_indices().last().unwrap();
+        let play_statistics = PlayStatistics(HashMap::from([(PuzzleIdentifier::Level(hardest), PuzzleStatistics::completed())]));
+
+        assert_eq!(play_statistics.get_working_level(&CampaignOrder::Gentle), LEVELS.len());
+        assert_eq!(play_statistics.get_working_level(&CampaignOrder::Challenge), 1);
+    }
+}
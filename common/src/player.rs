@@ -4,53 +4,227 @@ use crate::{
     assets::{
         material_handles::MaterialHandles, mesh_handles::MeshHandles, shaders::PlayerHaloShader,
     },
-    effects::player_particles::{PlayerParticleEffect, PlayerParticlesHandle},
+    controller::IllegalMove,
+    effects::{
+        player_particles::{PlayerParticleEffect, PlayerParticlesHandle},
+        ParticlesAvailable,
+    },
     game_settings::GameSettings,
     levels::{GameLevel, PuzzleEntityMarker},
     room::Room,
     shape::loader::SolutionComponent,
     player_path::PlayerPath,
+    solve_recording::SolveRecording,
 };
 use bevy::{math::NormedVectorSpace, pbr::ExtendedMaterial, prelude::*};
 
 use bevy_hanabi::prelude::*;
 use bevy_rapier3d::geometry::Collider;
+use serde::{Deserialize, Serialize};
 
 #[derive(Component)]
 pub struct Player {
     pub radius: f32,
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Clone, Serialize, Deserialize)]
 pub enum PlayerMazeState {
     Node(Room),
     Edge(Room, Room, Vec3),
 }
 
+const NUDGE_SECONDS: f32 = 0.18;
+const NUDGE_DISTANCE_FACTOR: f32 = 0.6;
+
+#[derive(Component)]
+pub struct PlayerNudge {
+    direction: Vec3,
+    elapsed: f32,
+}
+
+impl PlayerNudge {
+    // Out towards the attempted direction and back again over the nudge.
+    fn offset(&self, radius: f32) -> Vec3 {
+        let progress = (self.elapsed / NUDGE_SECONDS).clamp(0.0, 1.0);
+        self.direction * radius * NUDGE_DISTANCE_FACTOR * (progress * std::f32::consts::PI).sin()
+    }
+}
+
+pub fn nudge_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.illegal_move_nudge && !game_settings.reduced_motion
+}
+
+pub fn start_nudge(
+    mut commands: Commands,
+    mut illegal_move_reader: EventReader<IllegalMove>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    let Some(IllegalMove { direction, .. }) = illegal_move_reader.read().last() else {
+        return;
+    };
+
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    commands.entity(player_entity).insert(PlayerNudge {
+        direction: *direction,
+        elapsed: 0.0,
+    });
+}
+
+pub fn advance_nudge(
+    mut commands: Commands,
+    mut nudge_query: Query<(Entity, &mut PlayerNudge)>,
+    time: Res<Time>,
+) {
+    for (entity, mut nudge) in nudge_query.iter_mut() {
+        nudge.elapsed += time.delta_secs();
+        if nudge.elapsed >= NUDGE_SECONDS {
+            commands.entity(entity).remove::<PlayerNudge>();
+        }
+    }
+}
+
 pub fn update(
-    mut player_query: Query<(&mut Transform, &PlayerMazeState, &Player)>,
+    mut player_query: Query<(&mut Transform, &PlayerMazeState, &Player, Option<&PlayerNudge>)>,
     time: Res<Time>,
     settings: Res<GameSettings>,
 ) {
-    let Ok((mut player_transform, player_maze_state, Player { radius: size })) =
+    let Ok((mut player_transform, player_maze_state, Player { radius: size }, nudge)) =
         player_query.get_single_mut()
     else {
         return;
     };
 
-    let target_position = match player_maze_state {
+    let target_position = maze_state_position(player_maze_state, *size, settings.player_elevation)
+        + nudge.map_or(Vec3::ZERO, |nudge| nudge.offset(*size));
+
+    if player_transform.translation.distance(target_position) < 0.001 {
+        return;
+    }
+
+    // Eases the rendered player towards its logical maze state at the same rate whatever the
+    // frame rate. Sounds and the camera follow the maze state, so they never wait on the easing.
+    let ease = 1.0 - (-settings.player_move_speed.max(0.0) * time.delta_secs()).exp();
+    player_transform.translation = player_transform.translation.lerp(target_position, ease);
+}
+
+fn maze_state_position(player_maze_state: &PlayerMazeState, radius: f32, player_elevation: f32) -> Vec3 {
+    match player_maze_state {
         PlayerMazeState::Node(node) => {
-            let height_above_node = settings.player_elevation + size;
+            let height_above_node = player_elevation + radius;
             node.position() + height_above_node * node.face().normal()
         }
         PlayerMazeState::Edge(_, _, edge_position) => edge_position.clone(),
+    }
+}
+
+// Idle time before the first move is skipped, keeping only this much of it.
+const GHOST_LEAD_IN_SECONDS: f32 = 0.5;
+const GHOST_LOOP_PAUSE_SECONDS: f32 = 1.5;
+const GHOST_SCALE: f32 = 0.8;
+
+// Replays a recorded solve, looping, while the puzzle is complete.
+#[derive(Component)]
+pub struct Ghost {
+    radius: f32,
+    elapsed: f32,
+}
+
+pub fn ghost_replay_enabled(settings: Res<GameSettings>) -> bool {
+    settings.ghost_replay
+}
+
+pub fn spawn_ghost(
+    mut commands: Commands,
+    mut solve_recording: ResMut<SolveRecording>,
+    mesh_handles: Res<MeshHandles>,
+    material_handles: Res<MaterialHandles>,
+    settings: Res<GameSettings>,
+    level_query: Query<&GameLevel>,
+) {
+    let Ok(level) = level_query.get_single() else {
+        return;
     };
 
-    if player_transform.translation.distance(target_position) < 0.001 {
+    if solve_recording.0.len() < 2 {
         return;
     }
 
-    player_transform.translation = player_transform.translation.lerp(target_position, 0.3);
+    let first_move_time = solve_recording.0[1].0;
+    let start_time = solve_recording.0[0].0.max(first_move_time - GHOST_LEAD_IN_SECONDS);
+    for (time, _) in solve_recording.0.iter_mut() {
+        *time = (*time - start_time).max(0.0);
+    }
+
+    let radius = get_player_radius(level.node_distance());
+    let start_position = maze_state_position(&solve_recording.0[0].1, radius, settings.player_elevation);
+
+    commands
+        .spawn((
+            Transform::from_translation(start_position),
+            Visibility::default(),
+            Ghost { radius, elapsed: 0.0 },
+            PuzzleEntityMarker,
+        ))
+        .with_child((
+            Mesh3d(mesh_handles.player.clone()),
+            MeshMaterial3d(material_handles.player_handle.clone()),
+            Transform::from_scale(Vec3::ONE * radius * GHOST_SCALE),
+        ));
+}
+
+pub fn update_ghost(
+    mut ghost_query: Query<(&mut Transform, &mut Ghost)>,
+    solve_recording: Res<SolveRecording>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let Ok((mut ghost_transform, mut ghost)) = ghost_query.get_single_mut() else {
+        return;
+    };
+
+    let Some((end_time, _)) = solve_recording.0.last() else {
+        return;
+    };
+
+    ghost.elapsed += time.delta_secs();
+    if ghost.elapsed > end_time + GHOST_LOOP_PAUSE_SECONDS {
+        ghost.elapsed = 0.0;
+    }
+
+    let state_index = solve_recording
+        .0
+        .partition_point(|(state_time, _)| *state_time <= ghost.elapsed)
+        .saturating_sub(1);
+    let (_, player_maze_state) = &solve_recording.0[state_index];
+
+    ghost_transform.translation = maze_state_position(player_maze_state, ghost.radius, settings.player_elevation);
+}
+
+const HIGH_VISIBILITY_RING_SCALE: f32 = 1.8;
+const HIGH_VISIBILITY_PULSE_AMPLITUDE: f32 = 0.25;
+const HIGH_VISIBILITY_PULSE_SPEED: f32 = 4.0;
+
+#[derive(Component)]
+pub struct HighVisibilityRing;
+
+pub fn high_visibility_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.high_visibility_player
+}
+
+// Kept separate from the halo, which solving turns off, so the marker stays easy to find throughout.
+pub fn pulse_high_visibility_ring(
+    mut ring_query: Query<&mut Transform, With<HighVisibilityRing>>,
+    time: Res<Time>,
+) {
+    let pulse = 1.0
+        + HIGH_VISIBILITY_PULSE_AMPLITUDE * (time.elapsed_secs() * HIGH_VISIBILITY_PULSE_SPEED).sin();
+
+    for mut transform in ring_query.iter_mut() {
+        transform.scale = Vec3::splat(HIGH_VISIBILITY_RING_SCALE * pulse);
+    }
 }
 
 #[derive(Component)]
@@ -134,6 +308,7 @@ pub fn spawn(
     level_query: Query<&GameLevel>,
     material_handles: Res<MaterialHandles>,
     player_particle_handle_query: Query<&PlayerParticlesHandle>,
+    particles_available: Res<ParticlesAvailable>,
 ) {
     let Ok(level) = level_query.get_single() else {
         return;
@@ -146,7 +321,9 @@ pub fn spawn(
         return;
     };
 
-    let initial_node = solution.first().unwrap().clone();
+    let Some(initial_node) = solution.first().cloned() else {
+        return;
+    };
 
     let node_distance = level.node_distance();
     let radius = get_player_radius(node_distance);
@@ -177,12 +354,23 @@ pub fn spawn(
                         PlayerHalo { visible: true },
                     ));
 
-                    parent
-                        .spawn(ParticleEffectBundle {
-                            effect: ParticleEffect::new(effect_handle.clone()),
-                            ..Default::default()
-                        })
-                        .insert(PlayerParticleEffect);
+                    if settings.high_visibility_player {
+                        parent.spawn((
+                            Mesh3d(mesh_handles.player_halo.clone()),
+                            MeshMaterial3d(material_handles.high_visibility_ring_handle.clone()),
+                            Transform::from_scale(Vec3::splat(HIGH_VISIBILITY_RING_SCALE)),
+                            HighVisibilityRing,
+                        ));
+                    }
+
+                    if particles_available.enabled(&settings) {
+                        parent
+                            .spawn(ParticleEffectBundle {
+                                effect: ParticleEffect::new(effect_handle.clone()),
+                                ..Default::default()
+                            })
+                            .insert(PlayerParticleEffect);
+                    }
                 });
         });
 }
@@ -199,3 +387,45 @@ fn compute_initial_player_transform(
         .looking_at(face_normal.any_orthogonal_vector(), face_normal)
         .with_translation(start_node.position() + height_above_node * face_normal)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::room::test_support::room_at;
+
+    use super::*;
+
+    const RADIUS: f32 = 0.1;
+
+    fn advance(world: &mut World, seconds: f32) {
+        world.resource_mut::<Time>().advance_by(Duration::from_secs_f32(seconds));
+        world.run_system_once(advance_nudge).unwrap();
+    }
+
+    #[test]
+    fn an_illegal_move_nudges_the_marker_out_and_back() {
+        let node = room_at(0, Vec3::ZERO);
+
+        let mut world = World::new();
+        world.init_resource::<Events<IllegalMove>>();
+        world.init_resource::<Time>();
+        let player = world
+            .spawn((Player { radius: RADIUS }, PlayerMazeState::Node(node), Transform::default()))
+            .id();
+
+        world.send_event(IllegalMove { node, direction: Vec3::X });
+        world.run_system_once(start_nudge).unwrap();
+        assert!(world.get::<PlayerNudge>(player).is_some());
+
+        // Half way through, the marker is at its furthest towards the attempted direction.
+        advance(&mut world, NUDGE_SECONDS / 2.0);
+        let offset = world.get::<PlayerNudge>(player).unwrap().offset(RADIUS);
+        assert!((offset - Vec3::X * RADIUS * NUDGE_DISTANCE_FACTOR).length() < 1e-4, "{offset}");
+
+        advance(&mut world, NUDGE_SECONDS);
+        assert!(world.get::<PlayerNudge>(player).is_none());
+    }
+}
@@ -1,7 +1,8 @@
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
-use crate::{player::PlayerMazeState, room::Room};
+use crate::{game_state::PuzzleState, player::PlayerMazeState, room::Room};
 
+// Every node arrival in order, including revisits.
 #[derive(Component, Debug, Clone)]
 pub struct PlayerPath(pub Vec<Room>);
 
@@ -11,6 +12,98 @@ impl Default for PlayerPath {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepKind {
+    Start,
+    Forward,
+    // Returning to the node the current route came from, undoing the previous step.
+    Backtrack,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathStep {
+    pub room: Room,
+    pub kind: StepKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PathSnapshot {
+    pub steps: Vec<PathStep>,
+    pub completed: bool,
+}
+
+impl PathSnapshot {
+    pub fn rooms(&self) -> impl Iterator<Item = &Room> {
+        self.steps.iter().map(|step| &step.room)
+    }
+
+    pub fn move_count(&self) -> usize {
+        self.steps.len().saturating_sub(1)
+    }
+
+    pub fn backtrack_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.kind == StepKind::Backtrack)
+            .count()
+    }
+}
+
+impl PlayerPath {
+    pub fn snapshot(&self, completed: bool) -> PathSnapshot {
+        let mut route: Vec<Room> = vec![];
+
+        let steps = self
+            .0
+            .iter()
+            .map(|room| {
+                let kind = if route.is_empty() {
+                    StepKind::Start
+                } else if route.len() >= 2 && route[route.len() - 2] == *room {
+                    StepKind::Backtrack
+                } else {
+                    StepKind::Forward
+                };
+
+                match kind {
+                    StepKind::Backtrack => {
+                        route.pop();
+                    }
+                    _ => route.push(*room),
+                }
+
+                PathStep { room: *room, kind }
+            })
+            .collect();
+
+        PathSnapshot { steps, completed }
+    }
+}
+
+// Read-only access to the path through the puzzle being played, completed once victory is reached.
+#[derive(SystemParam)]
+pub struct CurrentPlayerPath<'w, 's> {
+    player_path_query: Query<'w, 's, &'static PlayerPath>,
+    puzzle_state: Option<Res<'w, State<PuzzleState>>>,
+}
+
+impl CurrentPlayerPath<'_, '_> {
+    pub fn snapshot(&self) -> Option<PathSnapshot> {
+        let player_path = self.player_path_query.get_single().ok()?;
+        let completed = self
+            .puzzle_state
+            .as_ref()
+            .is_some_and(|puzzle_state| *puzzle_state.get() == PuzzleState::Victory);
+
+        Some(player_path.snapshot(completed))
+    }
+}
+
+// Edges are keyed without direction so walking back along an edge counts as the same edge.
+pub fn undirected_edge_key(from: &Room, to: &Room) -> (u64, u64) {
+    (from.id.min(to.id), from.id.max(to.id))
+}
+
 pub fn update(
     mut player_path_query: Query<&mut PlayerPath>,
     player_query: Query<&PlayerMazeState, Changed<PlayerMazeState>>,
@@ -29,3 +122,60 @@ pub fn update(
         (*path).0.push(current_node.clone());
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use crate::room::test_support::room;
+
+    use super::*;
+
+    fn move_to(world: &mut World, player: Entity, player_maze_state: PlayerMazeState) {
+        *world.get_mut::<PlayerMazeState>(player).unwrap() = player_maze_state;
+        world.run_system_once(update).unwrap();
+    }
+
+    fn snapshot(world: &mut World) -> PathSnapshot {
+        world
+            .run_system_once(|current_player_path: CurrentPlayerPath| current_player_path.snapshot())
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn the_snapshot_follows_every_node_arrival() {
+        let mut world = World::new();
+        world.spawn(PlayerPath::default());
+        let player = world.spawn(PlayerMazeState::Node(room(0))).id();
+        world.run_system_once(update).unwrap();
+
+        for (from, to) in [(0, 1), (1, 0), (0, 1), (1, 2)] {
+            move_to(&mut world, player, PlayerMazeState::Edge(room(from), room(to), Vec3::ZERO));
+            move_to(&mut world, player, PlayerMazeState::Node(room(to)));
+            // Staying on a node is not a new arrival.
+            move_to(&mut world, player, PlayerMazeState::Node(room(to)));
+        }
+
+        let snapshot = snapshot(&mut world);
+        assert_eq!(snapshot.rooms().map(|room| room.id).collect::<Vec<_>>(), vec![0, 1, 0, 1, 2]);
+        assert_eq!(
+            snapshot.steps.iter().map(|step| step.kind).collect::<Vec<_>>(),
+            vec![StepKind::Start, StepKind::Forward, StepKind::Backtrack, StepKind::Forward, StepKind::Forward]
+        );
+        assert_eq!(snapshot.move_count(), 4);
+        assert_eq!(snapshot.backtrack_count(), 1);
+        assert!(!snapshot.completed);
+    }
+
+    #[test]
+    fn the_snapshot_is_completed_once_the_puzzle_is_won() {
+        let mut world = World::new();
+        world.spawn(PlayerPath(vec![room(0), room(1)]));
+        world.insert_resource(State::new(PuzzleState::Playing));
+        assert!(!snapshot(&mut world).completed);
+
+        world.insert_resource(State::new(PuzzleState::Victory));
+        assert!(snapshot(&mut world).completed);
+    }
+}
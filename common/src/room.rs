@@ -72,3 +72,23 @@ impl Eq for Room {}
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
 pub struct Edge;
+
+#[cfg(test)]
+pub mod test_support {
+    use bevy::prelude::*;
+
+    use super::{Face, Room};
+
+    // Rooms compare by position, so every fixture room sits at its own position.
+    pub fn room(id: u64) -> Room {
+        room_at(id, Vec3::X * id as f32)
+    }
+
+    pub fn room_at(id: u64, position: Vec3) -> Room {
+        room_on_face(id, position, Face { id: 0, normal: Vec3::Z })
+    }
+
+    pub fn room_on_face(id: u64, position: Vec3, face: Face) -> Room {
+        Room { position, face, id }
+    }
+}
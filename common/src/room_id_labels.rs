@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use itertools::Itertools;
+use petgraph::{graphmap::GraphMap, Directed, Direction};
+
+use crate::{
+    camera::MainCamera,
+    constants::{FONT_PATH, TEXT_COLOR},
+    game_state::{GameState, PuzzleState},
+    levels::PuzzleEntityMarker,
+    room::{Edge, Room},
+    shape::loader::GraphComponent,
+};
+
+const FONT_SIZE: f32 = 12.0;
+const LABEL_ELEVATION: f32 = 0.02;
+const MAX_VISIBLE_LABELS: usize = 128;
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+const DEGREE_TOGGLE_KEY: KeyCode = KeyCode::F4;
+
+// From linear corridors through to the busiest crossings.
+const DEGREE_COLORS: [Color; 3] = [
+    Color::srgb(0.4, 0.7, 1.0),
+    Color::srgb(1.0, 0.85, 0.3),
+    Color::srgb(1.0, 0.35, 0.3),
+];
+
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoomLabelMode {
+    #[default]
+    Hidden,
+    Id,
+    Degree,
+}
+
+#[derive(Component)]
+pub struct RoomIdLabel(pub Room);
+
+#[derive(Default)]
+pub struct RoomIdLabelsPlugin;
+
+impl Plugin for RoomIdLabelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoomLabelMode>()
+            .add_systems(OnEnter(PuzzleState::Playing), spawn)
+            .add_systems(
+                Update,
+                (toggle, update.after(toggle)).run_if(in_state(GameState::Puzzle)),
+            );
+    }
+}
+
+pub fn spawn(
+    mut commands: Commands,
+    graph_query: Query<&GraphComponent>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(GraphComponent(graph)) = graph_query.get_single() else {
+        return;
+    };
+
+    let font = asset_server.load(FONT_PATH);
+
+    for room in graph.nodes() {
+        commands.spawn((
+            Text::new(room.id.to_string()),
+            TextFont {
+                font: font.clone(),
+                font_size: FONT_SIZE,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Visibility::Hidden,
+            PickingBehavior::IGNORE,
+            RoomIdLabel(room),
+            PuzzleEntityMarker,
+        ));
+    }
+}
+
+pub fn toggle(keys: Res<ButtonInput<KeyCode>>, mut label_mode: ResMut<RoomLabelMode>) {
+    let pressed_mode = if keys.just_pressed(TOGGLE_KEY) {
+        RoomLabelMode::Id
+    } else if keys.just_pressed(DEGREE_TOGGLE_KEY) {
+        RoomLabelMode::Degree
+    } else {
+        return;
+    };
+
+    *label_mode = if *label_mode == pressed_mode {
+        RoomLabelMode::Hidden
+    } else {
+        pressed_mode
+    };
+}
+
+// Counts each neighbour once, whichever way the edges between them run.
+fn degree(room: Room, graph: &GraphMap<Room, Edge, Directed>) -> usize {
+    graph
+        .neighbors_directed(room, Direction::Outgoing)
+        .chain(graph.neighbors_directed(room, Direction::Incoming))
+        .unique()
+        .count()
+}
+
+fn degree_color(degree: usize) -> Color {
+    DEGREE_COLORS[degree.saturating_sub(2).min(DEGREE_COLORS.len() - 1)]
+}
+
+pub fn update(
+    label_mode: Res<RoomLabelMode>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    graph_query: Query<Ref<GraphComponent>>,
+    mut label_query: Query<(&RoomIdLabel, &mut Node, &mut Visibility, &mut Text, &mut TextColor)>,
+) {
+    if *label_mode == RoomLabelMode::Hidden {
+        if label_mode.is_changed() {
+            for (_, _, mut visibility, _, _) in label_query.iter_mut() {
+                *visibility = Visibility::Hidden;
+            }
+        }
+        return;
+    }
+
+    let Ok(graph_component) = graph_query.get_single() else {
+        return;
+    };
+
+    // Degrees are read from the live graph, so a rebuilt maze is reflected straight away.
+    if label_mode.is_changed() || graph_component.is_changed() {
+        let GraphComponent(graph) = graph_component.as_ref();
+
+        for (RoomIdLabel(room), _, _, mut text, mut text_color) in label_query.iter_mut() {
+            let (label, color) = match *label_mode {
+                RoomLabelMode::Degree => {
+                    let degree = degree(*room, graph);
+                    (degree.to_string(), degree_color(degree))
+                }
+                _ => (room.id.to_string(), TEXT_COLOR),
+            };
+
+            text.0 = label;
+            text_color.0 = color;
+        }
+    }
+
+    let Ok((camera, camera_global_transform)) = camera_query.get_single() else {
+        return;
+    };
+
+    let camera_position = camera_global_transform.translation();
+    let viewport_rect = camera.logical_viewport_rect();
+    let mut num_visible_labels = 0;
+
+    for (RoomIdLabel(room), mut node, mut visibility, _, _) in label_query.iter_mut() {
+        let normal = room.face().normal();
+        let label_position = room.position() + normal * LABEL_ELEVATION;
+        let faces_camera = normal.dot(camera_position - label_position) > 0.0;
+
+        let viewport_position = camera
+            .world_to_viewport(camera_global_transform, label_position)
+            .ok()
+            .filter(|position| viewport_rect.is_some_and(|rect| rect.contains(*position)))
+            .filter(|_| faces_camera && num_visible_labels < MAX_VISIBLE_LABELS);
+
+        let Some(viewport_position) = viewport_position else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        num_visible_labels += 1;
+        node.left = Val::Px(viewport_position.x);
+        node.top = Val::Px(viewport_position.y);
+        *visibility = Visibility::Visible;
+    }
+}
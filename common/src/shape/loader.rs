@@ -18,20 +18,31 @@ use crate::{
         },
         mesh_handles::MeshHandles,
         shaders::GlobalShader,
-    }, constants::{SQRT_3, TAN_27}, game_save::CurrentPuzzle, game_settings::{FaceColorPalette, GameSettings}, game_state::{GameState, PuzzleState}, is_room_junction::is_junction, levels::{GameLevel, PuzzleEntityMarker, Shape}, load_level_asset::{DailyLevelLoadError, LoadedLevels, MazeSaveDataHandle}, maze::{border_type::BorderType, mesh}, player::{Player, PlayerMazeState}, room::{Edge, Face, Room}, sound::{MelodyPuzzleTracker, Note, NoteMapping}, ui::message::MessagePopup
+    }, constants::{SQRT_3, TAN_27}, game_save::CurrentPuzzle, game_settings::{FaceColorPalette, GameSettings}, game_state::{GameState, PuzzleState}, is_room_junction::is_junction, levels::{GameLevel, PuzzleEntityMarker, Shape}, load_level_asset::{DailyLevelLoadError, LoadedLevels, MazeSaveDataHandle}, maze::{border_type::BorderType, mesh}, player::{Player, PlayerMazeState}, room::{Edge, Face, Room}, sound::{Melody, MelodyPuzzleTracker, Note, NoteMapping}, ui::message::MessagePopup
 };
 
+use crate::background_music::BackgroundLoop;
+use crate::goal_placement::GoalPlacement;
+use crate::maze::solver::shortest_path;
+
 use super::{cube, dodecahedron, icosahedron, octahedron, tetrahedron};
 use crate::assets::material_handles::MaterialHandles;
 
 use serde::{Deserialize, Serialize};
 
+const DIFFICULTY_SOLUTION_WEIGHT: f32 = 0.5;
+const DIFFICULTY_JUNCTION_WEIGHT: f32 = 0.2;
+
 #[derive(Component)]
 pub struct GraphComponent(pub GraphMap<Room, Edge, Directed>);
 
 #[derive(Component)]
 pub struct SolutionComponent(pub Vec<Room>);
 
+// Marks a level that was loaded with its start and goal swapped.
+#[derive(Component)]
+pub struct ReversedSolve;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct EncryptedMelody {
     pub encrypted_melody_bytes: Vec<u8>,
@@ -44,6 +55,145 @@ pub struct MazeLevelData {
     pub nodes_per_edge: u8,
     pub graph: GraphMap<Room, Edge, Directed>,
     pub solution: Vec<Room>,
+    #[serde(default)]
+    pub goal_placement: GoalPlacement,
     pub node_id_to_note: HashMap<u64, Note>,
     pub encrypted_melody: Option<EncryptedMelody>,
+    #[serde(default)]
+    pub background_loop: Option<BackgroundLoop>,
+    // The unencrypted melody, for authoring levels with `GameSettings::reveal_melodies`. Release
+    // builds never read it, so shipped levels keep only the encrypted melody.
+    #[cfg(debug_assertions)]
+    #[serde(default)]
+    pub melody: Option<Melody>,
+}
+
+impl MazeLevelData {
+    // Swaps the start and goal by flipping every edge, so one-way edges stay one-way against the
+    // reversed solution. The melody is dropped since its notes would be heard backwards.
+    pub fn reversed(&self) -> Option<Self> {
+        let solution = self
+            .goal_placement
+            .place(&self.graph, self.solution.clone())
+            .into_iter()
+            .rev()
+            .collect::<Vec<Room>>();
+
+        let mut graph = GraphMap::<Room, Edge, Directed>::new();
+        for room in self.graph.nodes() {
+            graph.add_node(room);
+        }
+        for (from, to, edge) in self.graph.all_edges() {
+            graph.add_edge(to, from, edge.clone());
+        }
+
+        let (Some(start), Some(goal)) = (solution.first(), solution.last()) else {
+            return None;
+        };
+        shortest_path(&graph, *start, *goal)?;
+
+        Some(MazeLevelData {
+            shape: self.shape.clone(),
+            nodes_per_edge: self.nodes_per_edge,
+            graph,
+            solution,
+            goal_placement: GoalPlacement::Generated,
+            node_id_to_note: self.node_id_to_note.clone(),
+            encrypted_melody: None,
+            background_loop: self.background_loop.clone(),
+            #[cfg(debug_assertions)]
+            melody: None,
+        })
+    }
+
+    pub fn difficulty_score(&self) -> f32 {
+        let level = GameLevel::new(self.shape.clone(), self.nodes_per_edge);
+        let junction_count = self
+            .graph
+            .nodes()
+            .filter(|room| is_junction(room, &self.graph))
+            .count();
+
+        level.difficulty_score()
+            + DIFFICULTY_SOLUTION_WEIGHT * self.solution.len() as f32
+            + DIFFICULTY_JUNCTION_WEIGHT * junction_count as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use itertools::Itertools;
+
+    use crate::levels::LEVELS;
+
+    use super::*;
+
+    fn level_file(level_index: usize) -> MazeLevelData {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../desktop/assets/levels")
+            .join(format!("{level_index}.json"));
+        let json = fs::read_to_string(&path).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn every_level_file_matches_its_campaign_entry() {
+        for (level_index, level) in LEVELS.iter().enumerate() {
+            let level_data = level_file(level_index);
+
+            assert_eq!(
+                serde_json::to_value(&level_data.shape).unwrap(),
+                serde_json::to_value(&level.shape).unwrap(),
+                "level {level_index}"
+            );
+            assert_eq!(level_data.nodes_per_edge, level.nodes_per_edge, "level {level_index}");
+
+            let (Some(start), Some(goal)) = (level_data.solution.first(), level_data.solution.last()) else {
+                panic!("level {level_index} has an empty solution");
+            };
+            assert!(shortest_path(&level_data.graph, *start, *goal).is_some(), "level {level_index}");
+        }
+    }
+
+    #[test]
+    fn tetrahedron_stripes_round_trips_through_level_json() {
+        let level_index = LEVELS
+            .iter()
+            .position(|level| matches!(level.shape, Shape::Tetrahedron(tetrahedron::Coloring::Stripes(_))))
+            .unwrap();
+
+        let json = serde_json::to_string(&level_file(level_index)).unwrap();
+        let level_data = serde_json::from_str::<MazeLevelData>(&json).unwrap();
+
+        assert!(matches!(
+            level_data.shape,
+            Shape::Tetrahedron(tetrahedron::Coloring::Stripes([0, 1, 3]))
+        ));
+    }
+
+    #[test]
+    fn reversed_levels_are_solvable_along_the_reversed_solution() {
+        for level_index in 0..LEVELS.len() {
+            let level_data = level_file(level_index);
+            let original_solution = level_data.goal_placement.place(&level_data.graph, level_data.solution.clone());
+
+            let reversed = level_data.reversed().unwrap();
+
+            let expected_solution = original_solution.iter().rev().copied().collect::<Vec<Room>>();
+            assert_eq!(reversed.solution, expected_solution, "level {level_index}");
+
+            let (start, goal) = (reversed.solution[0], *reversed.solution.last().unwrap());
+            assert!(shortest_path(&reversed.graph, start, goal).is_some(), "level {level_index}");
+            assert!(
+                reversed
+                    .solution
+                    .iter()
+                    .tuple_windows()
+                    .all(|(from, to)| reversed.graph.contains_edge(*from, *to)),
+                "level {level_index}"
+            );
+        }
+    }
 }
@@ -36,6 +36,9 @@ pub fn faces() -> [[Vec3; 3]; 4] {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Coloring {
     Full([u8; 4]),
+    // A band of the two faces sharing the edge from vertex 0 to 3, between two differently coloured
+    // end faces that share the opposite edge.
+    Stripes([u8; 3]),
     Dual([u8; 2]),
     Mono(u8),
 }
@@ -0,0 +1,171 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    game_settings::GameSettings,
+    player::PlayerMazeState,
+    room::Room,
+};
+
+const STEP_SECONDS: f32 = 0.25;
+
+#[derive(Resource, Default)]
+pub struct SnapAssist {
+    remaining: VecDeque<Room>,
+    since_last_step: f32,
+    pub assisted: bool,
+}
+
+impl SnapAssist {
+    pub fn is_running(&self) -> bool {
+        !self.remaining.is_empty()
+    }
+
+    pub fn start(&mut self, remaining: &[Room]) {
+        self.remaining = remaining.iter().copied().collect();
+        self.since_last_step = 0.0;
+        self.assisted = true;
+    }
+}
+
+pub fn snap_assist_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.snap_assist_nodes > 0
+}
+
+// The rooms left to walk along the solution from `room`, if it lies on the solution and is at
+// most `max_nodes` rooms from the goal.
+pub fn remaining_solution<'a>(solution: &'a [Room], room: &Room, max_nodes: usize) -> Option<&'a [Room]> {
+    let index = solution.iter().position(|solution_room| solution_room == room)?;
+    let remaining = &solution[index + 1..];
+
+    (!remaining.is_empty() && remaining.len() <= max_nodes).then_some(remaining)
+}
+
+// The remaining rooms for the player's current node, when the assist may be offered.
+pub fn available_remaining_solution<'a>(
+    player_maze_state: &PlayerMazeState,
+    solution: &'a [Room],
+    game_settings: &GameSettings,
+) -> Option<&'a [Room]> {
+    let PlayerMazeState::Node(room) = player_maze_state else {
+        return None;
+    };
+
+    remaining_solution(solution, room, game_settings.snap_assist_nodes)
+}
+
+pub fn reset(mut snap_assist: ResMut<SnapAssist>) {
+    *snap_assist = SnapAssist::default();
+}
+
+// Walks the player one room at a time so the player mesh animates along the path to the goal.
+pub fn step(
+    mut snap_assist: ResMut<SnapAssist>,
+    mut player_query: Query<&mut PlayerMazeState>,
+    time: Res<Time>,
+) {
+    if !snap_assist.is_running() {
+        return;
+    }
+
+    let Ok(mut player_maze_state) = player_query.get_single_mut() else {
+        return;
+    };
+
+    snap_assist.since_last_step += time.delta_secs();
+    if snap_assist.since_last_step < STEP_SECONDS {
+        return;
+    }
+    snap_assist.since_last_step = 0.0;
+
+    if let Some(room) = snap_assist.remaining.pop_front() {
+        *player_maze_state = PlayerMazeState::Node(room);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::{ecs::system::RunSystemOnce, utils::HashMap};
+
+    use crate::{
+        game_save::{CurrentPuzzle, PuzzleIdentifier},
+        game_state::{victory_transition, PuzzleState},
+        play_statistics::{self, PlayStatistics, PuzzleStatistics},
+        player_path::{self, PlayerPath},
+        room::test_support::room,
+        shape::loader::SolutionComponent,
+    };
+
+    use super::*;
+
+    fn solution() -> Vec<Room> {
+        (0..5).map(room).collect()
+    }
+
+    fn ids(rooms: Option<&[Room]>) -> Option<Vec<u64>> {
+        rooms.map(|rooms| rooms.iter().map(|room| room.id).collect())
+    }
+
+    #[test]
+    fn remaining_solution_is_the_rest_of_the_solution_within_reach_of_the_goal() {
+        let solution = solution();
+
+        assert_eq!(ids(remaining_solution(&solution, &room(2), 2)), Some(vec![3, 4]));
+        assert_eq!(ids(remaining_solution(&solution, &room(3), 2)), Some(vec![4]));
+    }
+
+    #[test]
+    fn remaining_solution_is_not_offered_off_the_solution() {
+        assert_eq!(ids(remaining_solution(&solution(), &room(7), 10)), None);
+    }
+
+    #[test]
+    fn remaining_solution_is_not_offered_too_far_from_the_goal() {
+        assert_eq!(ids(remaining_solution(&solution(), &room(1), 2)), None);
+    }
+
+    #[test]
+    fn remaining_solution_is_not_offered_at_the_goal() {
+        assert_eq!(ids(remaining_solution(&solution(), &room(4), 10)), None);
+    }
+
+    #[test]
+    fn an_assisted_walk_to_the_goal_wins_and_is_recorded_as_assisted() {
+        let solution = solution();
+        let puzzle_identifier = PuzzleIdentifier::Level(0);
+
+        let mut world = World::new();
+        world.init_resource::<SnapAssist>();
+        world.init_resource::<Time>();
+        world.init_resource::<NextState<PuzzleState>>();
+        world.insert_resource(PlayStatistics(HashMap::from([(puzzle_identifier.clone(), PuzzleStatistics::default())])));
+        world.spawn(CurrentPuzzle(puzzle_identifier.clone()));
+        world.spawn(SolutionComponent(solution.clone()));
+        world.spawn(PlayerPath(solution[..3].to_vec()));
+        let player = world.spawn(PlayerMazeState::Node(solution[2])).id();
+
+        let remaining = remaining_solution(&solution, &solution[2], 2).unwrap().to_vec();
+        world.resource_mut::<SnapAssist>().start(&remaining);
+
+        for _ in 0..10 {
+            world.resource_mut::<Time>().advance_by(Duration::from_secs_f32(STEP_SECONDS));
+            world.run_system_once(step).unwrap();
+            world.run_system_once(player_path::update).unwrap();
+        }
+
+        assert!(!world.resource::<SnapAssist>().is_running());
+        assert!(matches!(world.get::<PlayerMazeState>(player), Some(PlayerMazeState::Node(goal)) if goal.id == 4));
+
+        world.run_system_once(victory_transition).unwrap();
+        assert!(matches!(*world.resource::<NextState<PuzzleState>>(), NextState::Pending(PuzzleState::Victory)));
+
+        world.run_system_once(play_statistics::on_victory).unwrap();
+        let puzzle_statistics = &world.resource::<PlayStatistics>().0[&puzzle_identifier];
+        assert!(puzzle_statistics.completed);
+        assert!(puzzle_statistics.assisted);
+        assert!(!puzzle_statistics.perfect);
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::player::{Player, PlayerMazeState};
+
+// Every state the player passed through this attempt, with the time it was entered.
+#[derive(Resource, Default)]
+pub struct SolveRecording(pub Vec<(f32, PlayerMazeState)>);
+
+pub fn reset(mut solve_recording: ResMut<SolveRecording>) {
+    solve_recording.0.clear();
+}
+
+pub fn record(
+    mut solve_recording: ResMut<SolveRecording>,
+    player_query: Query<&PlayerMazeState, (With<Player>, Changed<PlayerMazeState>)>,
+    time: Res<Time>,
+) {
+    let Ok(player_maze_state) = player_query.get_single() else {
+        return;
+    };
+
+    solve_recording.0.push((time.elapsed_secs(), player_maze_state.clone()));
+}
@@ -1,9 +1,9 @@
 use std::collections::VecDeque;
 use std::time::Duration;
 
-use bevy::audio::PlaybackMode;
+use bevy::audio::{PlaybackMode, Volume};
 use bevy::reflect::List;
-use bevy::{audio::AddAudioSource, prelude::*, utils::HashMap};
+use bevy::{audio::AddAudioSource, prelude::*, utils::HashMap, window::WindowFocused};
 use bevy_rustysynth::{MidiAudio, MidiNote};
 use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, KeyInit};
 
@@ -18,7 +18,9 @@ use serde::{Deserialize, Serialize};
 use sha2::digest::typenum::Pow;
 use sha2::{Digest, Sha256};
 
+use crate::controller::IllegalMove;
 use crate::game_save::{CurrentPuzzle, DiscoveredMelody};
+use crate::game_settings::GameSettings;
 use crate::game_systems::SystemHandles;
 use crate::maze::mesh::MazeMarker;
 use crate::play_statistics::PlayStatistics;
@@ -55,6 +57,28 @@ impl NoteValue {
         }
 
     }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NoteValue::Semiquaver => "semiquaver",
+            NoteValue::Quaver => "quaver",
+            NoteValue::Crotchet => "crotchet",
+            NoteValue::DottedCrotchet => "dotted crotchet",
+            NoteValue::Minim => "minim",
+            NoteValue::DottedMinim => "dotted minim",
+            NoteValue::Semibreve => "semibreve",
+            NoteValue::SemibreveAndCrotchet => "semibreve + crotchet",
+        }
+    }
+}
+
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+// Scientific pitch notation, where MIDI key 60 is middle C (C4).
+pub fn note_name(key: i32) -> String {
+    let name = NOTE_NAMES[key.rem_euclid(12) as usize];
+    let octave = key.div_euclid(12) - 1;
+    format!("{name}{octave}")
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Hash)]
@@ -91,6 +115,17 @@ impl Default for Melody {
     }
 }
 
+impl Melody {
+    pub fn notation(&self) -> String {
+        let Notes(notes) = &self.notes;
+
+        notes
+            .iter()
+            .map(|note| format!("{} {}", note_name(note.key), note.value.name()))
+            .join(", ")
+    }
+}
+
 impl Note {
     pub fn new(key: i32, value: NoteValue) -> Self {
         Note {
@@ -133,6 +168,14 @@ impl Note {
     }
 }
 
+const VICTORY_CHORD_KEYS: [i32; 4] = [60, 64, 67, 72];
+const VICTORY_CHORD_SECONDS: f32 = 1.2;
+
+#[derive(Component)]
+pub struct NoteVoice {
+    started: f32,
+}
+
 #[derive(Component)]
 pub struct NoteMapping(pub HashMap<u64, (Handle<MidiAudio>, Note)>);
 
@@ -140,17 +183,240 @@ pub struct NoteMapping(pub HashMap<u64, (Handle<MidiAudio>, Note)>);
 pub struct MelodyPuzzleTracker {
     pub room_ids: VecDeque<u64>,
     pub encrypted_melody_bytes: Vec<u8>,
+    #[cfg(debug_assertions)]
+    pub plaintext_melody: Option<Melody>,
+}
+
+// Arrivals this close together play loudest and this far apart play quietest, scaling the note's
+// own velocity so the level's dynamics are kept relative to each other.
+const FAST_ARRIVAL_SECONDS: f32 = 0.2;
+const SLOW_ARRIVAL_SECONDS: f32 = 1.5;
+const FAST_VELOCITY_SCALE: f32 = 1.3;
+const SLOW_VELOCITY_SCALE: f32 = 0.6;
+const MIN_DYNAMIC_VELOCITY: i32 = 40;
+const MAX_DYNAMIC_VELOCITY: i32 = 127;
+
+const ILLEGAL_MOVE_TICK_KEY: i32 = 84;
+const ILLEGAL_MOVE_TICK_VELOCITY: i32 = 40;
+const ILLEGAL_MOVE_TICK_MILLIS: u64 = 60;
+
+// A discovered melody can still be playing when the goal is reached a move or two later; this picks
+// which of the two is heard.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MelodyVictoryPriority {
+    #[default]
+    MelodyFirst,
+    VictoryFirst,
+}
+
+// Plays a melody one scheduled note at a time against the SoundClock, and lives until its last
+// note has finished so background music stays ducked for the whole melody.
+#[derive(Component, Debug)]
+pub struct MelodyPlayback {
+    schedule: Vec<ScheduledNote>,
+    transpose_semitones: i32,
+    elapsed: Duration,
+    next_note: usize,
+}
+
+impl MelodyPlayback {
+    fn new(schedule: Vec<ScheduledNote>, transpose_semitones: i32) -> Self {
+        Self {
+            schedule,
+            transpose_semitones,
+            elapsed: Duration::ZERO,
+            next_note: 0,
+        }
+    }
+
+    // Notes whose start has been reached since the last advance, in order.
+    fn advance(&mut self, delta: Duration) -> &[ScheduledNote] {
+        self.elapsed += delta;
+
+        let first_due = self.next_note;
+        while self
+            .schedule
+            .get(self.next_note)
+            .is_some_and(|note| note.start <= self.elapsed)
+        {
+            self.next_note += 1;
+        }
+
+        &self.schedule[first_due..self.next_note]
+    }
+
+    fn is_finished(&self) -> bool {
+        self.schedule
+            .last()
+            .map_or(true, |note| self.elapsed >= note.start + note.duration)
+    }
+}
+
+// A note spawned by a MelodyPlayback, so cutting the melody short can silence what is sounding.
+#[derive(Component)]
+pub struct MelodyNote;
+
+// The time sound scheduling runs on. It follows the game clock every frame; tests insert a manual
+// clock instead and step it themselves.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SoundClock {
+    elapsed: Duration,
+    delta: Duration,
+    manual: bool,
+}
+
+impl SoundClock {
+    pub fn manual() -> Self {
+        Self {
+            manual: true,
+            ..default()
+        }
+    }
+
+    pub fn advance(&mut self, delta: Duration) {
+        self.delta = delta;
+        self.elapsed += delta;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+}
+
+pub fn tick_sound_clock(time: Res<Time>, mut sound_clock: ResMut<SoundClock>) {
+    if sound_clock.manual {
+        return;
+    }
+
+    sound_clock.advance(time.delta());
+}
+
+#[derive(Resource, Default)]
+pub struct PreviousNoteRoom(pub Option<Room>);
+
+// The player's own volume, applied on top of every sound's volume and persisted in the save.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AudioSettings {
+    pub master_volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            self.master_volume.clamp(0.0, 1.0)
+        }
+    }
+}
+
+// A sound's own volume before the player's audio settings are applied. Every note, chord and
+// melody carries one so live sinks can be rescaled when those settings change.
+#[derive(Component, Clone, Copy)]
+pub struct SoundVolume(pub f32);
+
+// Every note and melody plays once and despawns, so only sinks already sounding need adjusting.
+pub fn apply_audio_settings(
+    audio_settings: Res<AudioSettings>,
+    sink_query: Query<(&AudioSink, &SoundVolume)>,
+) {
+    if !audio_settings.is_changed() {
+        return;
+    }
+
+    for (sink, SoundVolume(volume)) in sink_query.iter() {
+        sink.set_volume(volume * audio_settings.volume());
+    }
+}
+
+// Unlike skipping playback, pausing holds sinks that are already playing so they resume in place.
+// Losing focus and the pause menu are tracked apart, so neither can resume audio the other paused.
+#[derive(Resource, Default)]
+pub struct AudioPaused {
+    pub focus: bool,
+    pub menu: bool,
+}
+
+impl AudioPaused {
+    pub fn paused(&self) -> bool {
+        self.focus || self.menu
+    }
+}
+
+pub fn pause_on_focus_change(
+    mut window_focused_events: EventReader<WindowFocused>,
+    mut audio_paused: ResMut<AudioPaused>,
+) {
+    let Some(WindowFocused { focused, .. }) = window_focused_events.read().last() else {
+        return;
+    };
+
+    if audio_paused.focus == !focused {
+        return;
+    }
+
+    audio_paused.focus = !focused;
+}
+
+// Generic over the sink so it can drive sinks that don't need an audio device.
+pub fn apply_audio_pause<S: Component + AudioSinkPlayback>(
+    audio_paused: Res<AudioPaused>,
+    audio_sink_query: Query<Ref<S>>,
+) {
+    for audio_sink in audio_sink_query.iter() {
+        if !audio_paused.is_changed() && !audio_sink.is_added() {
+            continue;
+        }
+
+        if audio_paused.paused() {
+            audio_sink.pause();
+        } else {
+            audio_sink.play();
+        }
+    }
+}
+
+pub fn reset_melody_tracking(
+    mut previous_note_room: ResMut<PreviousNoteRoom>,
+    mut melody_tracker_query: Query<&mut MelodyPuzzleTracker>,
+) {
+    previous_note_room.0 = None;
+
+    for mut melody_tracker in melody_tracker_query.iter_mut() {
+        melody_tracker.room_ids.clear();
+    }
 }
 
 pub fn play_note(
     mut commands: Commands,
-    mut previous_room_local: Local<Option<Room>>,
+    mut previous_note_room: ResMut<PreviousNoteRoom>,
     mut melody_tracker_query: Query<&mut MelodyPuzzleTracker>,
     graph_component: Query<&GraphComponent>,
     solution_query: Query<&SolutionComponent>,
     player_query: Query<&PlayerMazeState>,
     note_mapping: Query<&NoteMapping>,
+    note_voice_query: Query<(Entity, &NoteVoice)>,
+    melody_playback_query: Query<Entity, Or<(With<MelodyPlayback>, With<MelodyNote>)>>,
+    game_settings: Res<GameSettings>,
+    audio_settings: Res<AudioSettings>,
     asset_server: Res<AssetServer>,
+    sound_clock: Res<SoundClock>,
+    mut last_note_time: Local<Option<f32>>,
+    mut last_junction_time: Local<Option<f32>>,
 ) {
     let Ok(GraphComponent(graph)) = graph_component.get_single() else {
         return;
@@ -160,29 +426,43 @@ pub fn play_note(
         return;
     };
 
-    let play_sound = match *previous_room_local {
+    let play_sound = match previous_note_room.0 {
         Some(previous_room) => previous_room != *room && is_junction(room, graph),
         None => true,
     };
 
-    *previous_room_local = Some(*room);
-
     if !play_sound {
+        previous_note_room.0 = Some(*room);
         return;
     }
 
+    let now = sound_clock.elapsed().as_secs_f32();
+    // The first note of a level has no previous arrival to be timed against.
+    let seconds_since_last_junction = previous_note_room
+        .0
+        .and(*last_junction_time)
+        .map(|last_junction_time| now - last_junction_time);
+    *last_junction_time = Some(now);
+
+    previous_note_room.0 = Some(*room);
+
     let Ok(NoteMapping(note_mapping)) = note_mapping.get_single() else {
         return;
     };
 
-    let final_room = solution_query
+    let Some(final_room) = solution_query
         .get_single()
         .ok()
         .and_then(|SolutionComponent(solution)| solution.last())
-        .unwrap();
+    else {
+        return;
+    };
 
     if room != final_room {
-        let (note_handle, note) = note_mapping.get(&room.id).unwrap().clone();
+        let Some((note_handle, note)) = note_mapping.get(&room.id).cloned() else {
+            warn!(target: "mazonic::sound", "Room {} has no note", room.id);
+            return;
+        };
 
         if let Ok(mut melody_tracker) = melody_tracker_query.get_single_mut() {
             if melody_tracker.room_ids.len() == melody_tracker.room_ids.capacity() {
@@ -192,23 +472,153 @@ pub fn play_note(
             melody_tracker.room_ids.push_back(room.id);
         }
 
-        commands.spawn(AudioSourceBundle {
-            source: AudioPlayer(note_handle),
-            settings: get_playback_settings(1.0)
-        });
+        if last_note_time.map_or(false, |last_note_time| now - last_note_time < game_settings.min_note_interval) {
+            return;
+        }
+        *last_note_time = Some(now);
+
+        for voice_entity in voices_to_cut(&note_voice_query, game_settings.max_note_voices) {
+            commands.entity(voice_entity).despawn();
+        }
+
+        // Only playback is affected; the tracker above keeps the canonical notes for decryption.
+        let note_handle = if game_settings.note_dynamics || game_settings.transpose_semitones != 0 {
+            let velocity = if game_settings.note_dynamics {
+                dynamic_velocity(note.velocity, seconds_since_last_junction)
+            } else {
+                note.velocity
+            };
+            let midi_note = MidiNote {
+                key: transpose_key(note.key, game_settings.transpose_semitones),
+                velocity,
+                duration: Duration::from_secs_f32(note.value.as_f32()),
+                ..Default::default()
+            };
+            asset_server.add::<MidiAudio>(MidiAudio::Sequence(vec![midi_note]))
+        } else {
+            note_handle
+        };
+
+        commands.spawn((
+            AudioSourceBundle {
+                source: AudioPlayer(note_handle),
+                settings: get_playback_settings(1.0, &audio_settings)
+            },
+            NoteVoice { started: now },
+            SoundVolume(1.0),
+        ));
+        return;
+    }
+
+    // The goal room is never tracked as a melody note, so a melody is always discovered before
+    // the victory flourish and only its playback can still overlap.
+    if !melody_playback_query.is_empty() {
+        match game_settings.melody_victory_priority {
+            MelodyVictoryPriority::MelodyFirst => return,
+            MelodyVictoryPriority::VictoryFirst => {
+                for melody_playback_entity in melody_playback_query.iter() {
+                    commands.entity(melody_playback_entity).despawn();
+                }
+            }
+        }
+    }
+
+    if melody_tracker_query.is_empty() {
+        if game_settings.victory_cue {
+            play_victory_chord(commands, game_settings.victory_cue_volume, asset_server, &audio_settings);
+        }
     } else {
         play_winning_melody(
             commands,
             note_mapping.values().map(|(_, note)| note).collect(),
             asset_server,
+            game_settings.transpose_semitones,
+            &audio_settings,
         );
     }
 }
 
+pub fn illegal_move_tick_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.illegal_move_tick
+}
+
+pub fn play_illegal_move_tick(
+    mut commands: Commands,
+    mut illegal_move_reader: EventReader<IllegalMove>,
+    asset_server: Res<AssetServer>,
+    audio_settings: Res<AudioSettings>,
+) {
+    if illegal_move_reader.read().count() == 0 {
+        return;
+    }
+
+    let midi_note = MidiNote {
+        key: ILLEGAL_MOVE_TICK_KEY,
+        velocity: ILLEGAL_MOVE_TICK_VELOCITY,
+        duration: Duration::from_millis(ILLEGAL_MOVE_TICK_MILLIS),
+        ..Default::default()
+    };
+
+    let audio_handle = asset_server.add::<MidiAudio>(MidiAudio::Sequence(vec![midi_note]));
+    commands.spawn((
+        AudioSourceBundle {
+            source: AudioPlayer(audio_handle),
+            settings: get_playback_settings(1.0, &audio_settings),
+        },
+        SoundVolume(1.0),
+    ));
+}
+
+// Scales a note's velocity by how quickly the player reached this junction from the last one.
+pub fn dynamic_velocity(velocity: i32, seconds_since_last_junction: Option<f32>) -> i32 {
+    let Some(seconds) = seconds_since_last_junction else {
+        return velocity;
+    };
+
+    let slowness = ((seconds - FAST_ARRIVAL_SECONDS) / (SLOW_ARRIVAL_SECONDS - FAST_ARRIVAL_SECONDS)).clamp(0.0, 1.0);
+    let scale = FloatExt::lerp(FAST_VELOCITY_SCALE, SLOW_VELOCITY_SCALE, slowness);
+
+    ((velocity as f32 * scale).round() as i32).clamp(MIN_DYNAMIC_VELOCITY, MAX_DYNAMIC_VELOCITY)
+}
+
+// Oldest voices are cut first so there is room for one more within the cap.
+fn voices_to_cut(note_voice_query: &Query<(Entity, &NoteVoice)>, max_note_voices: usize) -> Vec<Entity> {
+    let num_voices_to_cut = (note_voice_query.iter().count() + 1).saturating_sub(max_note_voices.max(1));
+
+    note_voice_query
+        .iter()
+        .sorted_by(|(_, a), (_, b)| a.started.total_cmp(&b.started))
+        .take(num_voices_to_cut)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn play_victory_chord(mut commands: Commands, volume: f32, asset_server: Res<AssetServer>, audio_settings: &AudioSettings) {
+    for key in VICTORY_CHORD_KEYS {
+        let midi_note = MidiNote {
+            key,
+            velocity: 90,
+            duration: Duration::from_secs_f32(VICTORY_CHORD_SECONDS),
+            ..Default::default()
+        };
+
+        let audio_handle = asset_server.add::<MidiAudio>(MidiAudio::Sequence(vec![midi_note]));
+        commands.spawn((
+            AudioSourceBundle {
+                source: AudioPlayer(audio_handle),
+                settings: get_playback_settings(1.0, audio_settings).with_volume(Volume::new(volume * audio_settings.volume())),
+            },
+            SoundVolume(volume),
+        ));
+    }
+}
+
 fn play_winning_melody(
     mut commands: Commands,
     level_notes: Vec<&Note>,
     asset_server: Res<AssetServer>,
+    transpose_semitones: i32,
+    audio_settings: &AudioSettings,
 ) {
     let mut rng = ChaCha20Rng::from_entropy();
 
@@ -230,7 +640,7 @@ fn play_winning_melody(
             let fast_note_duration = Duration::from_secs_f32(note.value.as_f32() / speedup);
 
             MidiNote {
-                key: note.key,
+                key: transpose_key(note.key, transpose_semitones),
                 velocity: note.velocity,
                 duration: fast_note_duration,
                 ..Default::default()
@@ -240,16 +650,20 @@ fn play_winning_melody(
 
     let midi_audio = MidiAudio::Sequence(midi_notes);
     let audio_handle = asset_server.add::<MidiAudio>(midi_audio);
-    commands.spawn(AudioSourceBundle {
-        source: AudioPlayer(audio_handle),
-        settings: get_playback_settings(1.0)
-    });
+    commands.spawn((
+        AudioSourceBundle {
+            source: AudioPlayer(audio_handle),
+            settings: get_playback_settings(1.0, audio_settings)
+        },
+        SoundVolume(1.0),
+    ));
 }
 
-fn get_playback_settings(speed: f32) -> PlaybackSettings {
+fn get_playback_settings(speed: f32, audio_settings: &AudioSettings) -> PlaybackSettings {
     PlaybackSettings {
         mode: PlaybackMode::Despawn,
         speed,
+        volume: Volume::new(audio_settings.volume()),
         ..Default::default()
     }
 }
@@ -263,6 +677,7 @@ pub fn check_melody_solved(
     mut commands: Commands,
     maze_entities_query: Query<Entity, With<MazeMarker>>,
     mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+    game_settings: Res<GameSettings>,
 ) {
     let Ok(melody_tracker) = melody_tracker_query.get_single() else {
         return;
@@ -272,21 +687,25 @@ pub fn check_melody_solved(
         return;
     };
 
-    let notes = Notes(
-        melody_tracker
-            .room_ids
-            .iter()
-            .map(|room_id| {
-                let (_, note) = room_id_note_mapping.get(room_id).unwrap();
-                note.clone()
-            })
-            .collect_vec(),
-    );
+    // Rooms outside the current level's note mapping can only be stale, so they never decrypt.
+    let Some(notes) = melody_tracker
+        .room_ids
+        .iter()
+        .map(|room_id| room_id_note_mapping.get(room_id).map(|(_, note)| note.clone()))
+        .collect::<Option<Vec<Note>>>()
+    else {
+        return;
+    };
+    let notes = Notes(notes);
+
+    if let Some(melody_name) = revealed_melody_name(melody_tracker, &game_settings) {
+        message_popup_query.single_mut().0 = format!("~ {} ~", melody_name);
+    }
 
     let Some(melody) = try_decrypt_melody(&notes, &melody_tracker.encrypted_melody_bytes) else {
         return;
     };
-    
+
     message_popup_query.single_mut().0 = format!("~ {} ~", melody.name);
 
     let discovered_melody = DiscoveredMelody {
@@ -303,11 +722,106 @@ pub fn check_melody_solved(
     commands.run_system(system_handles.play_melody);
 }
 
+const MELODY_LEAD_IN: Duration = Duration::from_millis(800);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduledNote {
+    pub key: i32,
+    pub velocity: i32,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+// Pure timing for a melody, kept apart from audio playback so anything beat-aligned can share it
+// and it can be checked without a clock. Note values are in beats at the melody's bpm.
+pub fn melody_schedule(melody: &Melody, lead_in: Duration) -> Vec<ScheduledNote> {
+    let Notes(notes) = &melody.notes;
+    let seconds_per_beat = 60.0 / melody.bpm;
+
+    notes
+        .iter()
+        .scan(lead_in, |start, note| {
+            let duration = Duration::from_secs_f32(note.value.as_f32() * seconds_per_beat);
+            let scheduled_note = ScheduledNote {
+                key: note.key,
+                velocity: note.velocity,
+                start: *start,
+                duration,
+            };
+            *start += duration;
+            Some(scheduled_note)
+        })
+        .collect()
+}
+
+// Standard MIDI file resolution; a crotchet lasts this many ticks.
+const MIDI_TICKS_PER_BEAT: u16 = 480;
+const MIDI_NOTE_ON: u8 = 0x90;
+const MIDI_NOTE_OFF: u8 = 0x80;
+
+// A single track format 0 MIDI file. Note values are beats, so each maps straight to ticks and the
+// melody's bpm becomes the file's tempo.
+pub fn export_melody_midi(melody: &Melody) -> Vec<u8> {
+    let Notes(notes) = &melody.notes;
+    let microseconds_per_beat = (60_000_000.0 / melody.bpm.max(1.0)).round() as u32;
+
+    let mut track = vec![];
+
+    track.extend(midi_variable_length(0));
+    track.extend([0xFF, 0x51, 0x03]);
+    track.extend(&microseconds_per_beat.to_be_bytes()[1..]);
+
+    let name = melody.name.as_bytes();
+    track.extend(midi_variable_length(0));
+    track.extend([0xFF, 0x03]);
+    track.extend(midi_variable_length(name.len() as u32));
+    track.extend(name);
+
+    for note in notes {
+        let key = note.key.clamp(0, 127) as u8;
+        let velocity = note.velocity.clamp(1, 127) as u8;
+        let ticks = (note.value.as_f32() * MIDI_TICKS_PER_BEAT as f32).round() as u32;
+
+        track.extend(midi_variable_length(0));
+        track.extend([MIDI_NOTE_ON, key, velocity]);
+        track.extend(midi_variable_length(ticks));
+        track.extend([MIDI_NOTE_OFF, key, 0]);
+    }
+
+    track.extend(midi_variable_length(0));
+    track.extend([0xFF, 0x2F, 0x00]);
+
+    let mut bytes = vec![];
+    bytes.extend(b"MThd");
+    bytes.extend(6u32.to_be_bytes());
+    bytes.extend(0u16.to_be_bytes());
+    bytes.extend(1u16.to_be_bytes());
+    bytes.extend(MIDI_TICKS_PER_BEAT.to_be_bytes());
+    bytes.extend(b"MTrk");
+    bytes.extend((track.len() as u32).to_be_bytes());
+    bytes.extend(track);
+    bytes
+}
+
+// Seven bits per byte, most significant first, with the high bit set on all but the last.
+fn midi_variable_length(value: u32) -> Vec<u8> {
+    let mut bytes = vec![(value & 0x7F) as u8];
+    let mut value = value >> 7;
+
+    while value > 0 {
+        bytes.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+
+    bytes.reverse();
+    bytes
+}
+
 pub fn play_melody(
     current_level_index_query: Query<&CurrentPuzzle>,
     play_statistics: Res<PlayStatistics>,
-    asset_server: ResMut<AssetServer>,
     mut commands: Commands,
+    game_settings: Res<GameSettings>,
 ) {
     let CurrentPuzzle(puzzle_identifier) = current_level_index_query.single();
 
@@ -315,32 +829,88 @@ pub fn play_melody(
         return;
     };
 
-    let Notes(notes) = &discovered_melody.melody.notes;
+    spawn_melody_playback(&mut commands, &discovered_melody.melody, game_settings.transpose_semitones);
+}
 
-    let seconds_per_note = 60.0 / discovered_melody.melody.bpm;
-    let mut midi_notes = notes.iter().map(|note| MidiNote {
-            key: note.key,
-            velocity: note.velocity,
-            duration: Duration::from_secs_f32(note.value.as_f32() * seconds_per_note),
-            ..Default::default()
-        }).collect_vec();
+// Shifts a key for playback only. Melody hashes are always taken over the untransposed notes.
+pub fn transpose_key(key: i32, semitones: i32) -> i32 {
+    (key + semitones).clamp(0, 127)
+}
 
-    let pause_note = MidiNote {
-        velocity: 0,
-        duration: Duration::from_millis(800),
-        ..Default::default()
-    };
-    midi_notes.insert(0, pause_note);
-    let midi_audio = MidiAudio::Sequence(midi_notes);
-    let audio_handle = asset_server.add::<MidiAudio>(midi_audio);
-    commands.spawn(AudioSourceBundle {
-        source: AudioPlayer(audio_handle),
-        ..Default::default()
-    });
+pub fn spawn_melody_playback(commands: &mut Commands, melody: &Melody, transpose_semitones: i32) {
+    let schedule = melody_schedule(melody, MELODY_LEAD_IN);
+    commands.spawn(MelodyPlayback::new(schedule, transpose_semitones));
+}
+
+// While audio is paused the melody holds its place, as the sinks already sounding do.
+pub fn advance_melody_playback(
+    mut commands: Commands,
+    mut melody_playback_query: Query<(Entity, &mut MelodyPlayback)>,
+    sound_clock: Res<SoundClock>,
+    audio_paused: Res<AudioPaused>,
+    audio_settings: Res<AudioSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    if audio_paused.paused() {
+        return;
+    }
+
+    for (entity, mut melody_playback) in melody_playback_query.iter_mut() {
+        let transpose_semitones = melody_playback.transpose_semitones;
+
+        for scheduled_note in melody_playback.advance(sound_clock.delta()) {
+            let midi_note = MidiNote {
+                key: transpose_key(scheduled_note.key, transpose_semitones),
+                velocity: scheduled_note.velocity,
+                duration: scheduled_note.duration,
+                ..Default::default()
+            };
+            let audio_handle = asset_server.add::<MidiAudio>(MidiAudio::Sequence(vec![midi_note]));
+
+            commands.spawn((
+                AudioSourceBundle {
+                    source: AudioPlayer(audio_handle),
+                    settings: get_playback_settings(1.0, &audio_settings),
+                },
+                MelodyNote,
+                SoundVolume(1.0),
+            ));
+        }
+
+        if melody_playback.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+type NonceSize = <ChaCha20Poly1305 as AeadCore>::NonceSize;
+type TagSize = <ChaCha20Poly1305 as AeadCore>::TagSize;
+
+// Too short to hold a nonce and authentication tag means the melody can never decrypt.
+pub fn is_well_formed_encrypted_melody(encrypted_melody: &[u8]) -> bool {
+    encrypted_melody.len() > NonceSize::to_usize() + TagSize::to_usize()
+}
+
+// Debug builds can name the level's melody with `reveal_melodies` as its first note plays. Nothing
+// is recorded, so the melody is still only discovered by playing it in full.
+#[cfg(debug_assertions)]
+fn revealed_melody_name(melody_tracker: &MelodyPuzzleTracker, game_settings: &GameSettings) -> Option<String> {
+    if !game_settings.reveal_melodies || melody_tracker.room_ids.len() != 1 {
+        return None;
+    }
+
+    melody_tracker.plaintext_melody.as_ref().map(|melody| melody.name.clone())
+}
+
+#[cfg(not(debug_assertions))]
+fn revealed_melody_name(_melody_tracker: &MelodyPuzzleTracker, _game_settings: &GameSettings) -> Option<String> {
+    None
 }
 
 fn try_decrypt_melody(notes: &Notes, encrypted_melody: &Vec<u8>) -> Option<Melody> {
-    type NonceSize = <ChaCha20Poly1305 as AeadCore>::NonceSize;
+    if !is_well_formed_encrypted_melody(encrypted_melody) {
+        return None;
+    }
 
     let notes_hash_bytes = hash_melody(notes);
 
@@ -361,3 +931,645 @@ pub fn hash_melody(notes: &Notes) -> [u8; 32] {
 
     hash_result
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use bevy::{asset::AssetPlugin, ecs::system::RunSystemOnce};
+
+    use petgraph::graphmap::GraphMap;
+
+    use crate::game_save::PuzzleIdentifier;
+    use crate::play_statistics::PuzzleStatistics;
+    use crate::room::{
+        test_support::{room, room_at},
+        Edge,
+    };
+
+    use super::*;
+
+    const TICK: Duration = Duration::from_millis(50);
+
+    fn test_melody() -> Melody {
+        Melody {
+            name: "test".to_string(),
+            notes: Notes(vec![
+                Note { key: 60, velocity: 100, value: NoteValue::Crotchet },
+                Note { key: 62, velocity: 100, value: NoteValue::Quaver },
+                Note { key: 64, velocity: 100, value: NoteValue::Minim },
+            ]),
+            bpm: 120.0,
+        }
+    }
+
+    fn sound_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<MidiAudio>()
+            .insert_resource(SoundClock::manual())
+            .init_resource::<AudioPaused>()
+            .init_resource::<AudioSettings>();
+        app
+    }
+
+    // Assets added through the AssetServer only land in `Assets` once the app updates.
+    fn take_melody_notes(app: &mut App) -> Vec<MidiNote> {
+        app.update();
+        let world = app.world_mut();
+        let spawned = world
+            .query_filtered::<(Entity, &AudioPlayer<MidiAudio>), With<MelodyNote>>()
+            .iter(world)
+            .map(|(entity, AudioPlayer(handle))| (entity, handle.clone()))
+            .collect_vec();
+
+        spawned
+            .into_iter()
+            .flat_map(|(entity, handle)| {
+                world.despawn(entity);
+                match world.resource::<Assets<MidiAudio>>().get(&handle) {
+                    Some(MidiAudio::Sequence(midi_notes)) => midi_notes.clone(),
+                    _ => vec![],
+                }
+            })
+            .collect()
+    }
+
+    // Steps the manual clock until the melody finishes, noting when each note was spawned.
+    fn play_to_end(app: &mut App, melody: &Melody, transpose_semitones: i32) -> Vec<(Duration, MidiNote)> {
+        let melody = melody.clone();
+        app.world_mut().run_system_once(move |mut commands: Commands| {
+            spawn_melody_playback(&mut commands, &melody, transpose_semitones)
+        }).unwrap();
+
+        let mut played = vec![];
+        for _ in 0..100 {
+            app.world_mut().resource_mut::<SoundClock>().advance(TICK);
+            app.world_mut().run_system_once(advance_melody_playback).unwrap();
+
+            let now = app.world().resource::<SoundClock>().elapsed();
+            played.extend(take_melody_notes(app).into_iter().map(|midi_note| (now, midi_note)));
+
+            let world = app.world_mut();
+            if world.query::<&MelodyPlayback>().iter(world).next().is_none() {
+                break;
+            }
+        }
+
+        played
+    }
+
+    #[test]
+    fn melody_notes_start_at_their_scheduled_offsets() {
+        let mut app = sound_app();
+        let played = play_to_end(&mut app, &test_melody(), 0);
+
+        let starts = played.iter().map(|(start, midi_note)| (*start, midi_note.key)).collect_vec();
+        assert_eq!(
+            starts,
+            vec![
+                (Duration::from_millis(800), 60),
+                (Duration::from_millis(1300), 62),
+                (Duration::from_millis(1550), 64),
+            ]
+        );
+
+        let durations = played.iter().map(|(_, midi_note)| midi_note.duration).collect_vec();
+        assert_eq!(durations, vec![Duration::from_millis(500), Duration::from_millis(250), Duration::from_secs(1)]);
+    }
+
+    #[test]
+    fn melody_playback_ends_with_its_last_note() {
+        let mut app = sound_app();
+        play_to_end(&mut app, &test_melody(), 0);
+
+        assert_eq!(app.world().resource::<SoundClock>().elapsed(), Duration::from_millis(2550));
+    }
+
+    #[test]
+    fn paused_audio_holds_the_melody_in_place() {
+        let mut app = sound_app();
+        app.world_mut().run_system_once(|mut commands: Commands| {
+            spawn_melody_playback(&mut commands, &test_melody(), 0)
+        }).unwrap();
+
+        app.world_mut().resource_mut::<AudioPaused>().menu = true;
+        for _ in 0..40 {
+            app.world_mut().resource_mut::<SoundClock>().advance(TICK);
+            app.world_mut().run_system_once(advance_melody_playback).unwrap();
+        }
+        assert!(take_melody_notes(&mut app).is_empty());
+
+        app.world_mut().resource_mut::<AudioPaused>().menu = false;
+        app.world_mut().resource_mut::<SoundClock>().advance(Duration::from_millis(800));
+        app.world_mut().run_system_once(advance_melody_playback).unwrap();
+        assert_eq!(take_melody_notes(&mut app).len(), 1);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn reveal_melodies_names_the_melody_without_discovering_it() {
+        let puzzle_identifier = PuzzleIdentifier::Level(0);
+        let mut world = World::new();
+        world.init_resource::<SystemHandles>();
+        world.insert_resource(GameSettings { reveal_melodies: true, ..default() });
+        world.insert_resource(PlayStatistics(HashMap::from([(puzzle_identifier.clone(), PuzzleStatistics::default())])));
+        world.spawn((MessagePopup::default(), MessagePopupUpperMarker));
+        let tracker = world
+            .spawn((
+                CurrentPuzzle(puzzle_identifier.clone()),
+                NoteMapping(HashMap::from([(0, (Handle::default(), test_melody().notes.0[0].clone()))])),
+                MelodyPuzzleTracker {
+                    room_ids: VecDeque::from([0]),
+                    encrypted_melody_bytes: vec![],
+                    plaintext_melody: Some(test_melody()),
+                },
+            ))
+            .id();
+
+        world.run_system_once(check_melody_solved).unwrap();
+
+        assert_eq!(world.query::<&MessagePopup>().single(&world).0, "~ test ~");
+        assert!(world.resource::<PlayStatistics>().0[&puzzle_identifier].discovered_melody.is_none());
+
+        // Later notes leave the message alone rather than announcing the melody on every move.
+        world.query::<&mut MessagePopup>().single_mut(&mut world).0.clear();
+        world.get_mut::<MelodyPuzzleTracker>(tracker).unwrap().room_ids.push_back(0);
+        world.run_system_once(check_melody_solved).unwrap();
+
+        assert!(world.query::<&MessagePopup>().single(&world).0.is_empty());
+    }
+
+    fn encrypt_melody(notes: &Notes, melody: &Melody) -> Vec<u8> {
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(&hash_melody(notes)));
+        let nonce = [0; 12];
+        let ciphertext = cipher
+            .encrypt(chacha20poly1305::Nonce::from_slice(&nonce), serde_json::to_vec(melody).unwrap().as_slice())
+            .unwrap();
+
+        [nonce.to_vec(), ciphertext].concat()
+    }
+
+    #[test]
+    fn a_second_melody_level_is_not_discovered_from_moves_made_in_the_first() {
+        let melody = test_melody();
+        let encrypted_melody_bytes = encrypt_melody(&melody.notes, &melody);
+
+        // Room ids restart at zero in every level, so the first level's trail names rooms of the
+        // second, and here those rooms happen to play its melody.
+        let note_mapping = NoteMapping(
+            melody
+                .notes
+                .0
+                .iter()
+                .enumerate()
+                .map(|(room_id, note)| (room_id as u64, (Handle::default(), note.clone())))
+                .collect(),
+        );
+        let stale_room_ids = VecDeque::from([0, 1, 2]);
+        assert!(try_decrypt_melody(&melody.notes, &encrypted_melody_bytes).is_some());
+
+        let puzzle_identifier = PuzzleIdentifier::Level(1);
+        let mut world = World::new();
+        world.init_resource::<SystemHandles>();
+        world.init_resource::<GameSettings>();
+        world.insert_resource(PlayStatistics(HashMap::from([(puzzle_identifier.clone(), PuzzleStatistics::default())])));
+        world.insert_resource(PreviousNoteRoom(Some(room(2))));
+        world.spawn((MessagePopup::default(), MessagePopupUpperMarker));
+        world.spawn((
+            CurrentPuzzle(puzzle_identifier.clone()),
+            note_mapping,
+            MelodyPuzzleTracker {
+                room_ids: stale_room_ids,
+                encrypted_melody_bytes,
+                #[cfg(debug_assertions)]
+                plaintext_melody: None,
+            },
+        ));
+
+        world.run_system_once(reset_melody_tracking).unwrap();
+        world.run_system_once(check_melody_solved).unwrap();
+
+        assert!(world.resource::<PreviousNoteRoom>().0.is_none());
+        assert!(world.query::<&MelodyPuzzleTracker>().single(&world).room_ids.is_empty());
+        assert!(world.resource::<PlayStatistics>().0[&puzzle_identifier].discovered_melody.is_none());
+        assert!(world.query::<&MessagePopup>().single(&world).0.is_empty());
+    }
+
+    // A discovered melody is still playing as the player steps onto the goal, whose room also
+    // carries the melody's last note.
+    fn reach_goal_during_melody(melody_victory_priority: MelodyVictoryPriority) -> App {
+        let mut app = sound_app();
+        app.insert_resource(GameSettings { melody_victory_priority, ..default() })
+            .init_resource::<PreviousNoteRoom>();
+
+        let melody = test_melody();
+        let (start, goal) = (room(0), room(1));
+        let mut graph = GraphMap::new();
+        graph.add_edge(start, goal, Edge);
+        graph.add_edge(goal, start, Edge);
+
+        let note_mapping = NoteMapping(HashMap::from([
+            (start.id, (Handle::default(), melody.notes.0[1].clone())),
+            (goal.id, (Handle::default(), melody.notes.0[2].clone())),
+        ]));
+
+        let world = app.world_mut();
+        world.spawn((
+            GraphComponent(graph),
+            SolutionComponent(vec![start, goal]),
+            note_mapping,
+            MelodyPuzzleTracker {
+                room_ids: VecDeque::with_capacity(melody.notes.0.len()),
+                encrypted_melody_bytes: vec![],
+                #[cfg(debug_assertions)]
+                plaintext_melody: None,
+            },
+        ));
+        world.spawn(PlayerMazeState::Node(goal));
+        world.run_system_once(move |mut commands: Commands| spawn_melody_playback(&mut commands, &melody, 0)).unwrap();
+
+        world.run_system_once(play_note).unwrap();
+        app
+    }
+
+    fn count<F: bevy::ecs::query::QueryFilter>(app: &mut App) -> usize {
+        let world = app.world_mut();
+        world.query_filtered::<Entity, F>().iter(world).count()
+    }
+
+    #[test]
+    fn melody_first_lets_the_discovered_melody_finish_alone() {
+        let mut app = reach_goal_during_melody(MelodyVictoryPriority::MelodyFirst);
+
+        assert_eq!(count::<With<MelodyPlayback>>(&mut app), 1);
+        assert_eq!(count::<With<AudioPlayer<MidiAudio>>>(&mut app), 0);
+
+        // The goal's note is not added to the tracker, so it can't be heard or counted twice.
+        let world = app.world_mut();
+        assert!(world.query::<&MelodyPuzzleTracker>().single(world).room_ids.is_empty());
+    }
+
+    #[test]
+    fn victory_first_replaces_the_melody_with_the_winning_melody() {
+        let mut app = reach_goal_during_melody(MelodyVictoryPriority::VictoryFirst);
+
+        assert_eq!(count::<With<MelodyPlayback>>(&mut app), 0);
+        assert_eq!(count::<With<AudioPlayer<MidiAudio>>>(&mut app), 1);
+    }
+
+    #[test]
+    fn transposition_shifts_played_keys_but_not_the_tracked_melody() {
+        let melody = test_melody();
+        let mut app = sound_app();
+        app.insert_resource(GameSettings { transpose_semitones: 5, ..default() })
+            .init_resource::<PreviousNoteRoom>();
+
+        // A zigzag so every room is a junction, ending at a goal that plays no note.
+        let rooms = [
+            room(0),
+            room(1),
+            room_at(2, Vec3::new(1.0, 1.0, 0.0)),
+            room_at(3, Vec3::new(2.0, 1.0, 0.0)),
+        ];
+        let mut graph = GraphMap::new();
+        for (from, to) in rooms.iter().tuple_windows() {
+            graph.add_edge(*from, *to, Edge);
+            graph.add_edge(*to, *from, Edge);
+        }
+
+        let notes_by_room: HashMap<u64, Note> =
+            rooms.iter().zip(&melody.notes.0).map(|(room, note)| (room.id, note.clone())).collect();
+        let note_mapping = NoteMapping(
+            notes_by_room
+                .iter()
+                .map(|(room_id, note)| (*room_id, (Handle::default(), note.clone())))
+                .collect(),
+        );
+
+        let world = app.world_mut();
+        world.spawn((
+            GraphComponent(graph),
+            SolutionComponent(rooms.to_vec()),
+            note_mapping,
+            MelodyPuzzleTracker {
+                room_ids: VecDeque::with_capacity(melody.notes.0.len()),
+                encrypted_melody_bytes: vec![],
+                #[cfg(debug_assertions)]
+                plaintext_melody: None,
+            },
+        ));
+        let player = world.spawn(PlayerMazeState::Node(rooms[0])).id();
+
+        for room in &rooms[..3] {
+            *app.world_mut().get_mut::<PlayerMazeState>(player).unwrap() = PlayerMazeState::Node(*room);
+            app.world_mut().run_system_once(play_note).unwrap();
+        }
+
+        app.update();
+        let world = app.world_mut();
+        let handles = world
+            .query_filtered::<&AudioPlayer<MidiAudio>, With<NoteVoice>>()
+            .iter(world)
+            .map(|AudioPlayer(handle)| handle.clone())
+            .collect_vec();
+        let mut keys = handles
+            .iter()
+            .flat_map(|handle| match world.resource::<Assets<MidiAudio>>().get(handle) {
+                Some(MidiAudio::Sequence(midi_notes)) => midi_notes.iter().map(|midi_note| midi_note.key).collect(),
+                _ => vec![],
+            })
+            .collect_vec();
+        keys.sort();
+        assert_eq!(keys, vec![65, 67, 69]);
+
+        // check_melody_solved hashes the notes of the tracked rooms, which must still be the level's.
+        let tracked_notes = Notes(
+            world
+                .query::<&MelodyPuzzleTracker>()
+                .single(world)
+                .room_ids
+                .iter()
+                .map(|room_id| notes_by_room[room_id].clone())
+                .collect(),
+        );
+        assert_eq!(hash_melody(&tracked_notes), hash_melody(&melody.notes));
+    }
+
+    #[test]
+    fn transposition_stays_in_the_midi_range() {
+        assert_eq!(transpose_key(60, -12), 48);
+        assert_eq!(transpose_key(120, 12), 127);
+        assert_eq!(transpose_key(5, -12), 0);
+    }
+
+    // Reads back the note and tempo events of a single-track file written by export_melody_midi.
+    struct ParsedMidi {
+        ticks_per_beat: u16,
+        microseconds_per_beat: u32,
+        note_ons: Vec<(u32, u8)>,
+        note_offs: Vec<(u32, u8)>,
+        end_tick: u32,
+    }
+
+    fn read_variable_length(bytes: &[u8], position: &mut usize) -> u32 {
+        let mut value = 0;
+        loop {
+            let byte = bytes[*position];
+            *position += 1;
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                return value;
+            }
+        }
+    }
+
+    fn parse_midi(bytes: &[u8]) -> ParsedMidi {
+        assert_eq!(&bytes[0..4], b"MThd");
+        assert_eq!(&bytes[14..18], b"MTrk");
+        let ticks_per_beat = u16::from_be_bytes([bytes[12], bytes[13]]);
+        let track_length = u32::from_be_bytes(bytes[18..22].try_into().unwrap()) as usize;
+        let track = &bytes[22..];
+        assert_eq!(track.len(), track_length);
+
+        let mut parsed = ParsedMidi {
+            ticks_per_beat,
+            microseconds_per_beat: 0,
+            note_ons: vec![],
+            note_offs: vec![],
+            end_tick: 0,
+        };
+        let mut position = 0;
+        let mut tick = 0;
+
+        while position < track.len() {
+            tick += read_variable_length(track, &mut position);
+            let status = track[position];
+            position += 1;
+
+            match status {
+                0xFF => {
+                    let meta_type = track[position];
+                    position += 1;
+                    let length = read_variable_length(track, &mut position) as usize;
+                    let data = &track[position..position + length];
+                    position += length;
+
+                    match meta_type {
+                        0x51 => parsed.microseconds_per_beat = u32::from_be_bytes([0, data[0], data[1], data[2]]),
+                        0x2F => parsed.end_tick = tick,
+                        _ => {}
+                    }
+                }
+                MIDI_NOTE_ON => {
+                    parsed.note_ons.push((tick, track[position]));
+                    position += 2;
+                }
+                MIDI_NOTE_OFF => {
+                    parsed.note_offs.push((tick, track[position]));
+                    position += 2;
+                }
+                _ => panic!("unexpected status byte {status:#x}"),
+            }
+        }
+
+        parsed
+    }
+
+    #[test]
+    fn exported_midi_round_trips_note_count_and_duration() {
+        let parsed = parse_midi(&export_melody_midi(&test_melody()));
+
+        assert_eq!(parsed.ticks_per_beat, 480);
+        assert_eq!(parsed.microseconds_per_beat, 500_000);
+        assert_eq!(parsed.note_ons, vec![(0, 60), (480, 62), (720, 64)]);
+        assert_eq!(parsed.note_offs, vec![(480, 60), (720, 62), (1680, 64)]);
+
+        // A crotchet, a quaver and a minim are three and a half beats, or 1.75s at 120 bpm.
+        let duration = Duration::from_micros(
+            parsed.end_tick as u64 * parsed.microseconds_per_beat as u64 / parsed.ticks_per_beat as u64,
+        );
+        assert_eq!(duration, Duration::from_millis(1750));
+    }
+
+    #[test]
+    fn note_name_puts_middle_c_in_the_fourth_octave() {
+        assert_eq!(note_name(60), "C4");
+        assert_eq!(note_name(69), "A4");
+        assert_eq!(note_name(72), "C5");
+    }
+
+    #[test]
+    fn note_name_spells_black_keys_as_sharps() {
+        assert_eq!(note_name(61), "C#4");
+        assert_eq!(note_name(66), "F#4");
+        assert_eq!(note_name(70), "A#4");
+    }
+
+    #[test]
+    fn note_name_covers_the_midi_range() {
+        assert_eq!(note_name(0), "C-1");
+        assert_eq!(note_name(127), "G9");
+    }
+
+    #[test]
+    fn voices_to_cut_evicts_the_oldest_voices_first() {
+        let mut world = World::new();
+        let voices: Vec<Entity> = [0.4, 0.1, 0.5, 0.2, 0.3]
+            .into_iter()
+            .map(|started| world.spawn(NoteVoice { started }).id())
+            .collect();
+
+        let cut = world
+            .run_system_once(|note_voice_query: Query<(Entity, &NoteVoice)>| voices_to_cut(&note_voice_query, 3))
+            .unwrap();
+
+        // Five voices plus the new one need three cut to fit a cap of three.
+        assert_eq!(cut, vec![voices[1], voices[3], voices[4]]);
+    }
+
+    #[test]
+    fn voices_to_cut_keeps_every_voice_under_the_cap() {
+        let mut world = World::new();
+        world.spawn(NoteVoice { started: 0.1 });
+        world.spawn(NoteVoice { started: 0.2 });
+
+        let cut = world
+            .run_system_once(|note_voice_query: Query<(Entity, &NoteVoice)>| voices_to_cut(&note_voice_query, 3))
+            .unwrap();
+
+        assert!(cut.is_empty());
+    }
+
+    #[derive(Component, Default)]
+    struct TestSink {
+        paused: AtomicBool,
+    }
+
+    impl AudioSinkPlayback for TestSink {
+        fn volume(&self) -> f32 {
+            1.0
+        }
+
+        fn set_volume(&self, _volume: f32) {}
+
+        fn speed(&self) -> f32 {
+            1.0
+        }
+
+        fn set_speed(&self, _speed: f32) {}
+
+        fn play(&self) {
+            self.paused.store(false, Ordering::Relaxed);
+        }
+
+        fn pause(&self) {
+            self.paused.store(true, Ordering::Relaxed);
+        }
+
+        fn is_paused(&self) -> bool {
+            self.paused.load(Ordering::Relaxed)
+        }
+
+        fn stop(&self) {}
+
+        fn empty(&self) -> bool {
+            false
+        }
+    }
+
+    fn focus_app() -> App {
+        let mut app = App::new();
+        app.add_event::<WindowFocused>()
+            .init_resource::<AudioPaused>()
+            .add_systems(
+                Update,
+                (pause_on_focus_change, apply_audio_pause::<TestSink>.after(pause_on_focus_change)),
+            );
+        app
+    }
+
+    fn set_focus(app: &mut App, focused: bool) {
+        app.world_mut().send_event(WindowFocused { window: Entity::PLACEHOLDER, focused });
+        app.update();
+    }
+
+    fn sink_paused(app: &App, sink: Entity) -> bool {
+        app.world().get::<TestSink>(sink).unwrap().is_paused()
+    }
+
+    #[test]
+    fn losing_focus_pauses_sinks_and_regaining_it_resumes_them() {
+        let mut app = focus_app();
+        let sink = app.world_mut().spawn(TestSink::default()).id();
+        app.update();
+        assert!(!sink_paused(&app, sink));
+
+        set_focus(&mut app, false);
+        assert!(app.world().resource::<AudioPaused>().focus);
+        assert!(sink_paused(&app, sink));
+
+        set_focus(&mut app, true);
+        assert!(!app.world().resource::<AudioPaused>().focus);
+        assert!(!sink_paused(&app, sink));
+    }
+
+    #[test]
+    fn sinks_started_while_unfocused_are_paused() {
+        let mut app = focus_app();
+        set_focus(&mut app, false);
+
+        let sink = app.world_mut().spawn(TestSink::default()).id();
+        app.update();
+
+        assert!(sink_paused(&app, sink));
+    }
+
+    #[test]
+    fn sinks_are_left_alone_while_focus_is_unchanged() {
+        let mut app = focus_app();
+        let sink = app.world_mut().spawn(TestSink::default()).id();
+        app.update();
+
+        // Something else paused this sink, and no focus change should resume it.
+        app.world().get::<TestSink>(sink).unwrap().pause();
+        app.update();
+
+        assert!(sink_paused(&app, sink));
+    }
+
+    #[test]
+    fn regaining_focus_keeps_audio_paused_behind_the_pause_menu() {
+        let mut app = focus_app();
+        app.init_resource::<PreviousNoteRoom>();
+        let sink = app.world_mut().spawn(TestSink::default()).id();
+        app.update();
+
+        app.world_mut().run_system_once(crate::pause::on_pause).unwrap();
+        set_focus(&mut app, false);
+        set_focus(&mut app, true);
+        assert!(app.world().resource::<AudioPaused>().paused());
+        assert!(sink_paused(&app, sink));
+
+        app.world_mut().run_system_once(crate::pause::on_resume).unwrap();
+        app.update();
+        assert!(!sink_paused(&app, sink));
+    }
+
+    #[test]
+    fn resuming_from_the_pause_menu_while_unfocused_keeps_audio_paused() {
+        let mut app = focus_app();
+        app.init_resource::<PreviousNoteRoom>();
+        let sink = app.world_mut().spawn(TestSink::default()).id();
+        app.update();
+
+        app.world_mut().run_system_once(crate::pause::on_pause).unwrap();
+        set_focus(&mut app, false);
+        app.world_mut().run_system_once(crate::pause::on_resume).unwrap();
+        app.update();
+        assert!(sink_paused(&app, sink));
+
+        set_focus(&mut app, true);
+        assert!(!sink_paused(&app, sink));
+    }
+}
@@ -0,0 +1,63 @@
+use std::{fs, path::PathBuf};
+
+use bevy::prelude::*;
+
+const EMBEDDED_SOUNDFONT: &[u8] = include_bytes!("../../desktop/assets/marimba_chiapaneca.sf2");
+
+// Where the synth's instrument is loaded from. Entry points insert this before calling
+// `add_common_plugins`; without it the embedded marimba is used.
+#[derive(Resource, Debug, Clone, Default)]
+pub enum SoundfontSource {
+    #[default]
+    Embedded,
+    File(PathBuf),
+}
+
+// What `SoundfontSource::load` did. Loading runs before the log plugin is added, so the outcome
+// is kept and logged from a startup system instead.
+#[derive(Resource, Debug, Clone)]
+pub enum SoundfontOutcome {
+    Embedded,
+    Loaded(PathBuf),
+    NotASoundfont(PathBuf),
+    Unreadable(PathBuf, String),
+}
+
+impl SoundfontSource {
+    // A missing or malformed file falls back to the embedded soundfont, so a bad swap never leaves
+    // the game silent.
+    pub fn load(&self) -> (&'static [u8], SoundfontOutcome) {
+        let SoundfontSource::File(path) = self else {
+            return (EMBEDDED_SOUNDFONT, SoundfontOutcome::Embedded);
+        };
+
+        match fs::read(path) {
+            // The synth keeps the soundfont for the life of the app.
+            Ok(bytes) if is_soundfont(&bytes) => {
+                (Box::leak(bytes.into_boxed_slice()), SoundfontOutcome::Loaded(path.clone()))
+            }
+            Ok(_) => (EMBEDDED_SOUNDFONT, SoundfontOutcome::NotASoundfont(path.clone())),
+            Err(err) => (EMBEDDED_SOUNDFONT, SoundfontOutcome::Unreadable(path.clone(), err.to_string())),
+        }
+    }
+}
+
+pub fn log_outcome(outcome: Res<SoundfontOutcome>) {
+    match outcome.as_ref() {
+        SoundfontOutcome::Embedded => {}
+        SoundfontOutcome::Loaded(path) => {
+            info!(target: "mazonic::soundfont", "Loaded soundfont {}", path.display());
+        }
+        SoundfontOutcome::NotASoundfont(path) => {
+            warn!(target: "mazonic::soundfont", "{} is not a soundfont; using the embedded one", path.display());
+        }
+        SoundfontOutcome::Unreadable(path, err) => {
+            info!(target: "mazonic::soundfont", "Could not read {} ({err}); using the embedded soundfont", path.display());
+        }
+    }
+}
+
+// SoundFont 2 files are RIFF containers of form type "sfbk".
+fn is_soundfont(bytes: &[u8]) -> bool {
+    bytes.len() > 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"sfbk"
+}
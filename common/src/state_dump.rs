@@ -0,0 +1,181 @@
+use std::fs;
+
+use bevy::prelude::*;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    camera::MainCamera,
+    controller::ControllerState,
+    game_save::{save_directory, CurrentPuzzle, PuzzleIdentifier, SaveLocation},
+    game_settings::GameSettings,
+    game_state::{GameState, PuzzleState},
+    player::PlayerMazeState,
+    ui::message::{MessagePopup, MessagePopupUpperMarker},
+};
+
+const DUMP_KEY: KeyCode = KeyCode::KeyD;
+const DUMP_DIRECTORY: &str = "state_dumps";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameStateSnapshot {
+    pub version: String,
+    pub timestamp: String,
+    pub game_state: String,
+    pub puzzle_state: Option<String>,
+    pub controller_state: Option<String>,
+    pub current_puzzle: Option<PuzzleIdentifier>,
+    pub player_maze_state: Option<PlayerMazeState>,
+    pub camera_translation: Option<Vec3>,
+    pub camera_rotation: Option<Quat>,
+    pub settings: String,
+}
+
+pub fn dump_requested(keys: Res<ButtonInput<KeyCode>>) -> bool {
+    let control = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    control && shift && keys.just_pressed(DUMP_KEY)
+}
+
+pub fn dump(
+    game_state: Res<State<GameState>>,
+    puzzle_state: Option<Res<State<PuzzleState>>>,
+    controller_state: Option<Res<State<ControllerState>>>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    player_query: Query<&PlayerMazeState>,
+    camera_query: Query<&Transform, With<MainCamera>>,
+    game_settings: Res<GameSettings>,
+    save_location: Option<Res<SaveLocation>>,
+    mut message_popup_query: Query<&mut MessagePopup, With<MessagePopupUpperMarker>>,
+) {
+    let now = Utc::now();
+    let camera_transform = camera_query.get_single().ok();
+
+    let snapshot = GameStateSnapshot {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        timestamp: now.to_rfc3339(),
+        game_state: format!("{:?}", game_state.get()),
+        puzzle_state: puzzle_state.map(|state| format!("{:?}", state.get())),
+        controller_state: controller_state.map(|state| format!("{:?}", state.get())),
+        current_puzzle: current_puzzle_query
+            .get_single()
+            .ok()
+            .map(|CurrentPuzzle(puzzle_identifier)| puzzle_identifier.clone()),
+        player_maze_state: player_query.get_single().ok().cloned(),
+        camera_translation: camera_transform.map(|transform| transform.translation),
+        camera_rotation: camera_transform.map(|transform| transform.rotation),
+        settings: format!("{:#?}", *game_settings),
+    };
+
+    let directory = save_directory(save_location.as_deref()).join(DUMP_DIRECTORY);
+    let path = directory.join(format!("{}.json", now.format("%Y%m%d-%H%M%S")));
+
+    let result = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| e.to_string())
+        .and_then(|json| {
+            fs::create_dir_all(&directory)
+                .and_then(|_| fs::write(&path, json))
+                .map_err(|e| e.to_string())
+        });
+
+    let message = match result {
+        Ok(()) => {
+            info!(target: "mazonic::state_dump", "Wrote state dump to {:?}", path);
+            "state saved"
+        }
+        Err(err) => {
+            warn!(target: "mazonic::state_dump", "Failed to write state dump to {:?}: {}", path, err);
+            "could not save state"
+        }
+    };
+
+    if let Ok(mut message_popup) = message_popup_query.get_single_mut() {
+        message_popup.0 = message.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    fn temp_directory(test_name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("mazonic-{}-{}", test_name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn dump_world(save_location: PathBuf) -> World {
+        let mut world = World::new();
+        world.insert_resource(State::new(GameState::Puzzle));
+        world.insert_resource(State::new(PuzzleState::Playing));
+        world.init_resource::<GameSettings>();
+        world.insert_resource(SaveLocation(save_location));
+        world.spawn(CurrentPuzzle(PuzzleIdentifier::Level(4)));
+        world.spawn((MainCamera, Transform::from_xyz(0.0, 0.0, 5.0)));
+        world.spawn((MessagePopupUpperMarker, MessagePopup(String::new())));
+        world
+    }
+
+    fn popup_message(world: &mut World) -> String {
+        world.query::<&MessagePopup>().single(world).0.clone()
+    }
+
+    #[test]
+    fn dump_needs_control_shift_and_the_dump_key() {
+        let requested = |held: &[KeyCode]| {
+            let mut keys = ButtonInput::<KeyCode>::default();
+            for key in held {
+                keys.press(*key);
+            }
+            let mut world = World::new();
+            world.insert_resource(keys);
+            world.run_system_once(dump_requested).unwrap()
+        };
+
+        assert!(requested(&[KeyCode::ControlLeft, KeyCode::ShiftRight, DUMP_KEY]));
+        assert!(!requested(&[KeyCode::ControlLeft, DUMP_KEY]));
+        assert!(!requested(&[KeyCode::ShiftLeft, DUMP_KEY]));
+        assert!(!requested(&[KeyCode::ControlLeft, KeyCode::ShiftLeft]));
+    }
+
+    #[test]
+    fn dump_writes_a_readable_snapshot() {
+        let directory = temp_directory("state-dump");
+        let mut world = dump_world(directory.clone());
+
+        world.run_system_once(dump).unwrap();
+
+        let dump_path = fs::read_dir(directory.join(DUMP_DIRECTORY))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let snapshot = serde_json::from_str::<GameStateSnapshot>(&fs::read_to_string(dump_path).unwrap()).unwrap();
+
+        assert_eq!(snapshot.game_state, "Puzzle");
+        assert_eq!(snapshot.puzzle_state.as_deref(), Some("Playing"));
+        assert_eq!(snapshot.controller_state, None);
+        assert_eq!(snapshot.current_puzzle, Some(PuzzleIdentifier::Level(4)));
+        assert_eq!(snapshot.camera_translation, Some(Vec3::new(0.0, 0.0, 5.0)));
+        assert_eq!(popup_message(&mut world), "state saved");
+    }
+
+    #[test]
+    fn dump_reports_when_it_cannot_write() {
+        // A file where the save directory should be means the dump directory can't be created.
+        let directory = temp_directory("state-dump-blocked");
+        fs::write(&directory, "").unwrap();
+        let mut world = dump_world(directory.clone());
+
+        world.run_system_once(dump).unwrap();
+
+        assert_eq!(popup_message(&mut world), "could not save state");
+        fs::remove_file(directory).unwrap();
+    }
+}
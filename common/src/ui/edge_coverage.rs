@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+
+use crate::{
+    constants::{FONT_PATH, TEXT_COLOR},
+    edge_coverage::EdgeCoverage,
+};
+
+const FONT_SIZE: f32 = 20.0;
+
+#[derive(Component)]
+pub struct EdgeCoverageUI;
+
+#[derive(Component)]
+pub struct EdgeCoverageText;
+
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.),
+            right: Val::Px(10.),
+            ..default()
+        })
+        .insert(EdgeCoverageUI)
+        .insert(PickingBehavior::IGNORE)
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font: asset_server.load(FONT_PATH),
+                font_size: FONT_SIZE,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+            EdgeCoverageText,
+        ));
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<EdgeCoverageUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update(edge_coverage: Res<EdgeCoverage>, mut text_query: Query<&mut Text, With<EdgeCoverageText>>) {
+    if !edge_coverage.is_changed() {
+        return;
+    }
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    text.0 = format!("edges: {}/{}", edge_coverage.crossed.len(), edge_coverage.total);
+}
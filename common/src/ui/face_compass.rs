@@ -0,0 +1,96 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    camera::MainCamera,
+    constants::{FONT_PATH, TEXT_COLOR},
+    game_settings::GameSettings,
+    geometry::closest_facing,
+    room::Face,
+    shape::loader::GraphComponent,
+};
+
+const FONT_SIZE: f32 = 20.0;
+const RECOMPUTE_ANGLE: f32 = 0.02;
+
+#[derive(Component)]
+pub struct FaceCompassUI;
+
+#[derive(Component)]
+pub struct FaceCompassText;
+
+pub fn face_compass_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.face_compass
+}
+
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.),
+            left: Val::Px(10.),
+            ..default()
+        })
+        .insert(FaceCompassUI)
+        .insert(PickingBehavior::IGNORE)
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font: asset_server.load(FONT_PATH),
+                font_size: FONT_SIZE,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+            FaceCompassText,
+        ));
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<FaceCompassUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update(
+    camera_query: Query<&Transform, With<MainCamera>>,
+    graph_query: Query<&GraphComponent>,
+    mut text_query: Query<&mut Text, With<FaceCompassText>>,
+    mut last_camera_forward: Local<Option<Vec3>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    let Ok(GraphComponent(graph)) = graph_query.get_single() else {
+        return;
+    };
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    let camera_forward = camera_transform.forward().as_vec3();
+
+    // Skip small camera movements, but always fill in a freshly spawned label.
+    let camera_moved = last_camera_forward
+        .map_or(true, |last_forward| last_forward.angle_between(camera_forward) > RECOMPUTE_ANGLE);
+
+    if !camera_moved && !text.is_added() {
+        return;
+    }
+
+    *last_camera_forward = Some(camera_forward);
+
+    let faces = graph
+        .nodes()
+        .map(|room| (room.face().id(), room.face()))
+        .collect::<HashMap<usize, Face>>();
+
+    let Some(front_face) = closest_facing(camera_forward, faces.into_values(), Face::normal) else {
+        return;
+    };
+
+    let label = format!("face {}", front_face.id() + 1);
+    if text.0 != label {
+        text.0 = label;
+    }
+}
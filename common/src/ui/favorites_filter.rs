@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use crate::{
+    constants::{FONT_PATH, TRANSPARENCY},
+    favorites::FavoritesFilter,
+    ui::BUTTON_COLOR,
+};
+
+const FONT_SIZE: f32 = 40.0;
+const BUTTON_SIZE: f32 = 72.0;
+const ACTIVE_TEXT_COLOR: Color = Color::srgba(0.9, 0.3, 0.4, TRANSPARENCY);
+const INACTIVE_TEXT_COLOR: Color = Color::srgba(0.5, 0.5, 0.5, TRANSPARENCY);
+
+#[derive(Component)]
+pub struct FavoritesFilterUI;
+
+#[derive(Component)]
+pub struct FavoritesFilterButton;
+
+#[derive(Component)]
+pub struct FavoritesFilterButtonText;
+
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>, filter: Res<FavoritesFilter>) {
+    let font = asset_server.load(FONT_PATH);
+
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.),
+            right: Val::Px(10.),
+            ..default()
+        })
+        .insert(FavoritesFilterUI)
+        .insert(PickingBehavior::IGNORE)
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        width: Val::Px(BUTTON_SIZE),
+                        height: Val::Px(BUTTON_SIZE),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BorderRadius::MAX,
+                    BackgroundColor(BUTTON_COLOR),
+                    FavoritesFilterButton,
+                ))
+                .with_child((
+                    Text::new("♥"),
+                    TextFont {
+                        font,
+                        font_size: FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(get_text_color(&filter)),
+                    FavoritesFilterButtonText,
+                ));
+        });
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<FavoritesFilterUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn toggle(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<FavoritesFilterButton>)>,
+    mut filter: ResMut<FavoritesFilter>,
+) {
+    let Ok(interaction) = interaction_query.get_single() else {
+        return;
+    };
+
+    if *interaction == Interaction::Pressed {
+        filter.0 = !filter.0;
+    }
+}
+
+pub fn update(
+    filter: Res<FavoritesFilter>,
+    mut text_query: Query<&mut TextColor, With<FavoritesFilterButtonText>>,
+) {
+    if !filter.is_changed() {
+        return;
+    }
+
+    let Ok(mut text_color) = text_query.get_single_mut() else {
+        return;
+    };
+
+    text_color.0 = get_text_color(&filter);
+}
+
+fn get_text_color(filter: &FavoritesFilter) -> Color {
+    if filter.0 {
+        ACTIVE_TEXT_COLOR
+    } else {
+        INACTIVE_TEXT_COLOR
+    }
+}
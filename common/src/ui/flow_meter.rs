@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::{flow_meter::FlowMeter, game_settings::GameSettings};
+
+const BAR_HEIGHT: f32 = 6.0;
+
+#[derive(Component)]
+pub struct FlowMeterUI;
+
+#[derive(Component)]
+pub struct FlowMeterBar;
+
+pub fn spawn(mut commands: Commands, game_settings: Res<GameSettings>) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(0.),
+            left: Val::Px(0.),
+            width: Val::Percent(100.0),
+            height: Val::Px(BAR_HEIGHT),
+            ..default()
+        })
+        .insert(FlowMeterUI)
+        .insert(PickingBehavior::IGNORE)
+        .with_child((
+            Node {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(game_settings.palette.player_color),
+            FlowMeterBar,
+        ));
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<FlowMeterUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update(flow_meter: Res<FlowMeter>, mut bar_query: Query<&mut Node, With<FlowMeterBar>>) {
+    if !flow_meter.is_changed() {
+        return;
+    }
+
+    let Ok(mut bar_node) = bar_query.get_single_mut() else {
+        return;
+    };
+
+    bar_node.width = Val::Percent(flow_meter.value * 100.0);
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+
+use crate::{
+    constants::{FONT_PATH, TEXT_COLOR},
+    game_save::CurrentPuzzle,
+    game_settings::GameSettings,
+    play_statistics::PlayStatistics,
+};
+
+const FONT_SIZE: f32 = 16.0;
+
+#[derive(Component)]
+pub struct MelodyNotationUI;
+
+#[derive(Component)]
+pub struct MelodyNotationText;
+
+pub fn melody_notation_enabled(game_settings: Res<GameSettings>) -> bool {
+    game_settings.show_melody_notation
+}
+
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn(Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(50.),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        })
+        .insert(MelodyNotationUI)
+        .insert(PickingBehavior::IGNORE)
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font: asset_server.load(FONT_PATH),
+                font_size: FONT_SIZE,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+            TextLayout::new_with_justify(JustifyText::Center),
+            MelodyNotationText,
+        ));
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<MelodyNotationUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update(
+    play_statistics: Res<PlayStatistics>,
+    current_puzzle_query: Query<Ref<CurrentPuzzle>>,
+    mut text_query: Query<&mut Text, With<MelodyNotationText>>,
+) {
+    let Ok(current_puzzle) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    if !play_statistics.is_changed() && !current_puzzle.is_changed() && !text.is_added() {
+        return;
+    }
+
+    text.0 = play_statistics
+        .0
+        .get(&current_puzzle.0)
+        .and_then(|puzzle_statistics| puzzle_statistics.discovered_melody.as_ref())
+        .map(|discovered_melody| discovered_melody.melody.notation())
+        .unwrap_or_default();
+}
@@ -2,7 +2,7 @@ use std::ops::AddAssign;
 
 use bevy::{ecs::query::QueryData, prelude::*, time::Stopwatch};
 
-use crate::{constants::{FONT_PATH, TEXT_COLOR, TRANSPARENCY}, game_save::CurrentPuzzle, play_statistics::{PlayStatistics, PuzzleStatistics, SolveTime}};
+use crate::{constants::{FONT_PATH, TEXT_COLOR, TRANSPARENCY}, daily_streak::{self, DailyStreak}, game_save::CurrentPuzzle, play_statistics::{PlayStatistics, PuzzleStatistics, SolveTime, MAX_STARS}};
 
 
 const FADE_START_TIME_SECONDS: f32 = 3.0;
@@ -104,6 +104,62 @@ pub fn update_lower_during_puzzle_state(
 
 }
 
+// Once the puzzle is complete the solve time is final, so the best score joins it.
+pub fn update_lower_during_victory(
+    solve_time: Res<SolveTime>,
+    play_statistics: Res<PlayStatistics>,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    mut popup_ui_query: Query<&mut Text, With<MessagePopupLowerMarker>>,
+) {
+    if !solve_time.is_changed() && !play_statistics.is_changed() {
+        return;
+    }
+
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let Ok(mut text) = popup_ui_query.get_single_mut() else {
+        return;
+    };
+
+    let elapsed = solve_time.stopwatch.elapsed().as_secs_f32();
+    let puzzle_statistics = play_statistics.0.get(puzzle_identifier);
+    let mut summary = match puzzle_statistics.and_then(|statistics| statistics.best_score) {
+        Some(best_score) => format!("{:.1}s · Best: {}", elapsed, best_score),
+        None => format!("{:.1}s", elapsed),
+    };
+
+    if let Some(stars) = puzzle_statistics.and_then(|statistics| statistics.best_stars) {
+        summary.push_str(&format!(" · {}/{} stars", stars, MAX_STARS));
+    }
+
+    text.0 = summary;
+}
+
+pub fn show_daily_streak(
+    daily_streak: Res<DailyStreak>,
+    mut popup_ui_query: Query<&mut Text, With<MessagePopupLowerMarker>>,
+) {
+    let Ok(mut text) = popup_ui_query.get_single_mut() else {
+        return;
+    };
+
+    text.0 = match daily_streak.current(daily_streak::today()) {
+        0 => "".to_string(),
+        1 => "daily streak: 1 day".to_string(),
+        days => format!("daily streak: {} days", days),
+    };
+}
+
+pub fn clear_lower(mut popup_ui_query: Query<&mut Text, With<MessagePopupLowerMarker>>) {
+    let Ok(mut text) = popup_ui_query.get_single_mut() else {
+        return;
+    };
+
+    text.0 = "".to_string();
+}
+
 pub fn exit_puzzle_state(mut popup_ui_query: Query<&mut Text, With<MessagePopupLowerMarker>>) {
     let mut text = popup_ui_query.single_mut();
     text.0 = "".to_string();
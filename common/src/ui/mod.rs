@@ -1,2 +1,156 @@
+use bevy::prelude::*;
+
+use crate::constants::{FONT_PATH, TEXT_COLOR, TRANSPARENCY};
+
+pub mod edge_coverage;
+pub mod face_compass;
+pub mod favorites_filter;
+pub mod flow_meter;
+pub mod melody_notation;
 pub mod navigation;
 pub mod message;
+pub mod mute;
+pub mod pause;
+pub mod planning;
+pub mod resume;
+pub mod save_import;
+pub mod snap_assist;
+pub mod undo;
+pub mod whats_new;
+
+pub const PROMPT_FONT_SIZE: f32 = 24.0;
+pub const BUTTON_COLOR: Color = Color::srgba(0.15, 0.15, 0.15, TRANSPARENCY);
+
+// The raised spot is one prompt above the usual one, for prompts that can show at the same time
+// as another centred prompt.
+const PROMPT_BOTTOM: f32 = 60.0;
+const RAISED_PROMPT_BOTTOM: f32 = 110.0;
+const PROMPT_SIDE_MARGIN: f32 = 20.0;
+const PROMPT_TOP_MARGIN: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptPlacement {
+    BottomCentre,
+    RaisedBottomCentre,
+    BottomLeft,
+    BottomRight,
+    TopLeft,
+}
+
+impl PromptPlacement {
+    fn node(self) -> Node {
+        let centred = |bottom: f32| Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(bottom),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        };
+
+        match self {
+            PromptPlacement::BottomCentre => centred(PROMPT_BOTTOM),
+            PromptPlacement::RaisedBottomCentre => centred(RAISED_PROMPT_BOTTOM),
+            PromptPlacement::BottomLeft => Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(PROMPT_BOTTOM),
+                left: Val::Px(PROMPT_SIDE_MARGIN),
+                ..default()
+            },
+            PromptPlacement::BottomRight => Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(PROMPT_BOTTOM),
+                right: Val::Px(PROMPT_SIDE_MARGIN),
+                ..default()
+            },
+            PromptPlacement::TopLeft => Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(PROMPT_TOP_MARGIN),
+                left: Val::Px(PROMPT_TOP_MARGIN),
+                ..default()
+            },
+        }
+    }
+}
+
+pub fn prompt_text(font: Handle<Font>, text: impl Into<String>, font_size: f32) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font,
+            font_size,
+            ..default()
+        },
+        TextColor(TEXT_COLOR),
+    )
+}
+
+pub fn prompt_button(marker: impl Bundle) -> impl Bundle {
+    (
+        Button,
+        Node {
+            padding: UiRect::axes(Val::Px(20.), Val::Px(10.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderRadius::all(Val::Px(10.)),
+        BackgroundColor(BUTTON_COLOR),
+        marker,
+    )
+}
+
+// A single labelled button on its own overlay. The overlay ignores picking so only the button
+// itself blocks the maze underneath.
+pub fn spawn_prompt_button(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    placement: PromptPlacement,
+    label: &str,
+    overlay_marker: impl Bundle,
+    button_marker: impl Bundle,
+    text_marker: impl Bundle,
+) {
+    commands
+        .spawn((placement.node(), PickingBehavior::IGNORE, overlay_marker))
+        .with_children(|parent| {
+            parent
+                .spawn(prompt_button(button_marker))
+                .with_child((
+                    prompt_text(asset_server.load(FONT_PATH), label, PROMPT_FONT_SIZE),
+                    text_marker,
+                ));
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use bevy::prelude::*;
+
+    use super::{navigation::{self, NavigationUI}, pause::{self, PauseUI}};
+
+    fn spawn_overlay(world: &mut World, marker: impl Component) -> (Entity, Entity) {
+        let child = world.spawn(Node::default()).id();
+        let root = world.spawn((Node::default(), marker)).add_child(child).id();
+        (root, child)
+    }
+
+    #[test]
+    fn despawn_only_removes_its_own_overlay() {
+        let mut world = World::new();
+        let (pause_root, pause_child) = spawn_overlay(&mut world, PauseUI);
+        let (navigation_root, navigation_child) = spawn_overlay(&mut world, NavigationUI);
+
+        world.run_system_once(pause::despawn).unwrap();
+
+        assert!(world.get_entity(pause_root).is_err());
+        assert!(world.get_entity(pause_child).is_err());
+        assert!(world.get_entity(navigation_root).is_ok());
+        assert!(world.get_entity(navigation_child).is_ok());
+
+        world.run_system_once(navigation::despawn_level_navigation_ui).unwrap();
+
+        assert!(world.get_entity(navigation_root).is_err());
+        assert!(world.get_entity(navigation_child).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::{
+    sound::AudioSettings,
+    ui::{spawn_prompt_button, PromptPlacement},
+};
+
+#[derive(Component)]
+pub struct MuteUI;
+
+#[derive(Component)]
+pub struct MuteButton;
+
+#[derive(Component)]
+pub struct MuteButtonText;
+
+fn button_text(audio_settings: &AudioSettings) -> &'static str {
+    if audio_settings.muted {
+        "unmute"
+    } else {
+        "mute"
+    }
+}
+
+pub fn spawn(mut commands: Commands, audio_settings: Res<AudioSettings>, asset_server: Res<AssetServer>) {
+    spawn_prompt_button(
+        &mut commands,
+        &asset_server,
+        PromptPlacement::TopLeft,
+        button_text(&audio_settings),
+        MuteUI,
+        MuteButton,
+        MuteButtonText,
+    );
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<MuteUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn press(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<MuteButton>)>,
+    mut audio_settings: ResMut<AudioSettings>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        audio_settings.muted = !audio_settings.muted;
+    }
+}
+
+pub fn update_text(
+    audio_settings: Res<AudioSettings>,
+    mut text_query: Query<&mut Text, With<MuteButtonText>>,
+) {
+    if !audio_settings.is_changed() {
+        return;
+    }
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    text.0 = button_text(&audio_settings).to_string();
+}
@@ -4,7 +4,7 @@ use bevy::{
 };
 
 use crate::{
-    constants::{FONT_PATH, TEXT_COLOR, TRANSPARENCY}, game_save::{CurrentPuzzle, GameSave, PuzzleIdentifier, WorkingLevelIndex}, game_state::{GameState, PuzzleState}, levels::LEVELS, play_statistics::PlayStatistics, player_path::PlayerPath, shape::loader::{GraphComponent, SolutionComponent}
+    assets::symbol_sheet::SymbolSheet, constants::{FONT_PATH, TEXT_COLOR, TRANSPARENCY}, favorites::Favorites, game_save::{CurrentPuzzle, GameSave, PuzzleIdentifier, WorkingLevelIndex}, game_settings::GameSettings, game_state::{GameState, PuzzleState}, hint::HintRequest, levels::LEVELS, play_statistics::PlayStatistics, player_path::PlayerPath, shape::loader::{GraphComponent, SolutionComponent}
 };
 
 #[derive(Component)]
@@ -22,15 +22,26 @@ pub struct NextLevelButton;
 #[derive(Component)]
 pub struct LevelSelectorButton;
 
+#[derive(Component)]
+pub struct FavoriteButton;
+
+#[derive(Component)]
+pub struct HintButton;
+
+#[derive(Component)]
+pub struct FavoriteButtonText;
+
 const NORMAL_BUTTON: Color = Color::srgba(0.15, 0.15, 0.15, TRANSPARENCY);
 const HOVERED_BUTTON: Color = Color::srgba(0.25, 0.25, 0.25, TRANSPARENCY);
 const PRESSED_BUTTON: Color = Color::srgba(0.65, 0.65, 0.65, TRANSPARENCY);
 const BUTTON_BACKGROUND_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, TRANSPARENCY);
 const PRESSED_BUTTON_BORDER_COLOR: Color = Color::srgba(0.9, 0.9, 0.9, TRANSPARENCY);
+const FAVORITE_TEXT_COLOR: Color = Color::srgba(0.9, 0.3, 0.4, TRANSPARENCY);
+const NOT_FAVORITE_TEXT_COLOR: Color = Color::srgba(0.5, 0.5, 0.5, TRANSPARENCY);
 const FONT_SIZE: f32 = 50.0;
 
 
-pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>, symbol_sheet: Res<SymbolSheet>) {
     let font = asset_server.load(FONT_PATH);
 
     let get_text_node = |text: &str| {
@@ -71,15 +82,9 @@ pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
         ..default()
     };
 
-    let selector_symbol_pixel_width = 512.;
-    let level_selector_rect = Rect::new(
-        0.,
-        selector_symbol_pixel_width,
-        selector_symbol_pixel_width,
-        2.0 * selector_symbol_pixel_width,
-    );
+    let level_selector_rect = symbol_sheet.pixel_rect("level_selector");
     let level_selector_node = (ImageNode {
-        image: asset_server.load("sprites/symbols_sprite_sheet.png"),
+        image: asset_server.load(&symbol_sheet.path),
         color: PRESSED_BUTTON_BORDER_COLOR,
         ..Default::default()
     }
@@ -102,6 +107,11 @@ pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                     .insert(ReplayLevelButton)
                     .with_child(get_text_node("↻"));
 
+                parent
+                    .spawn(button.clone())
+                    .insert(FavoriteButton)
+                    .with_child((get_text_node("♥"), FavoriteButtonText));
+
                 parent
                     .spawn(button.clone())
                     .insert(PreviousLevelButton)
@@ -121,6 +131,11 @@ pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
                         level_selector_node,
                     ));
 
+                parent
+                    .spawn(button.clone())
+                    .insert(HintButton)
+                    .with_child(get_text_node("?"));
+
                 parent
                     .spawn(button)
                     .insert(NextLevelButton)
@@ -162,6 +177,7 @@ pub fn update_level_complete_ui(
 pub fn update_previous_level_button_visibility(
     mut previous_level_button_query: Query<&mut Visibility, With<PreviousLevelButton>>,
     current_level_index_query: Query<&CurrentPuzzle>,
+    game_settings: Res<GameSettings>,
 ) {
     let Ok(CurrentPuzzle(puzzle_identifier)) = current_level_index_query.get_single() else {
         return;
@@ -173,7 +189,7 @@ pub fn update_previous_level_button_visibility(
     };
 
     *previous_level_button_visibility = match puzzle_identifier {
-        PuzzleIdentifier::Level(level_index) if *level_index > 0 => Visibility::Visible,
+        PuzzleIdentifier::Level(level_index) if game_settings.campaign_order.campaign_position(*level_index).is_some_and(|campaign_position| campaign_position > 0) => Visibility::Visible,
         _ => Visibility::Hidden,
         
     }
@@ -183,6 +199,7 @@ pub fn update_next_level_button_visibility(
     mut next_level_button_query: Query<&mut Visibility, With<NextLevelButton>>,
     current_level_index_query: Query<&CurrentPuzzle>,
     play_statistics: Res<PlayStatistics>,
+    game_settings: Res<GameSettings>,
 ) {
     let Ok(CurrentPuzzle(puzzle_identifier)) = current_level_index_query.get_single() else {
         return;
@@ -192,12 +209,16 @@ pub fn update_next_level_button_visibility(
         return;
     };
 
-    let working_level_index = play_statistics.get_working_level();
+    let campaign_order = &game_settings.campaign_order;
+    let working_level_index = play_statistics.get_working_level(campaign_order);
 
     let max_level_index = LEVELS.len() - 1;
 
     *next_level_button_visibility = match puzzle_identifier {
-        PuzzleIdentifier::Level(level_index) if *level_index < working_level_index && *level_index < max_level_index => Visibility::Visible,
+        PuzzleIdentifier::Level(level_index) => match campaign_order.campaign_position(*level_index) {
+            Some(campaign_position) if campaign_position < working_level_index && campaign_position < max_level_index => Visibility::Visible,
+            _ => Visibility::Hidden,
+        },
         _ => Visibility::Hidden,
         
     };
@@ -239,6 +260,7 @@ pub fn previous_level(
     >,
     mut current_level_index_query: Query<&mut CurrentPuzzle>,
     mut play_state: ResMut<NextState<PuzzleState>>,
+    game_settings: Res<GameSettings>,
 ) {
     let Ok(mut current_puzzle) = current_level_index_query.get_single_mut() else {
         return;
@@ -248,13 +270,18 @@ pub fn previous_level(
         return;
     };
     
-    let CurrentPuzzle(PuzzleIdentifier::Level(mut current_level_index)) = *current_puzzle else {
+    let CurrentPuzzle(PuzzleIdentifier::Level(current_level_index)) = *current_puzzle else {
         return;
     };
 
+    let campaign_order = &game_settings.campaign_order;
+    let Some(campaign_position) = campaign_order.campaign_position(current_level_index) else {
+        return;
+    };
 
-    if *interaction == Interaction::Pressed && current_level_index > 0 {
-        *current_puzzle = CurrentPuzzle(PuzzleIdentifier::Level(current_level_index - 1));
+    if *interaction == Interaction::Pressed && campaign_position > 0 {
+        let previous_level_index = campaign_order.level_index(campaign_position - 1);
+        *current_puzzle = CurrentPuzzle(PuzzleIdentifier::Level(previous_level_index));
 
         play_state.set(PuzzleState::Loading);
     }
@@ -283,6 +310,7 @@ pub fn next_level(
     >,
     mut current_puzzle_query: Query<&mut CurrentPuzzle>,
     mut play_state: ResMut<NextState<PuzzleState>>,
+    game_settings: Res<GameSettings>,
 ) {
     let Ok(mut current_puzzle) = current_puzzle_query.get_single_mut() else {
         return;
@@ -296,8 +324,14 @@ pub fn next_level(
         return;
     };
 
-    if *interaction == Interaction::Pressed && current_level_index < LEVELS.len() - 1 {
-        *current_puzzle  = CurrentPuzzle(PuzzleIdentifier::Level(current_level_index + 1));
+    let campaign_order = &game_settings.campaign_order;
+    let Some(campaign_position) = campaign_order.campaign_position(current_level_index) else {
+        return;
+    };
+
+    if *interaction == Interaction::Pressed && campaign_position < LEVELS.len() - 1 {
+        let next_level_index = campaign_order.level_index(campaign_position + 1);
+        *current_puzzle  = CurrentPuzzle(PuzzleIdentifier::Level(next_level_index));
         play_state.set(PuzzleState::Loading);
     }
 }
@@ -321,3 +355,66 @@ pub fn level_selector(
         game_state.set(GameState::Selector);
     }
 }
+
+pub fn toggle_favorite(
+    interaction_query: Query<
+        &Interaction,
+        (Changed<Interaction>, With<Button>, With<FavoriteButton>),
+    >,
+    current_puzzle_query: Query<&CurrentPuzzle>,
+    mut favorites: ResMut<Favorites>,
+) {
+    let Ok(interaction) = interaction_query.get_single() else {
+        return;
+    };
+
+    let Ok(CurrentPuzzle(puzzle_identifier)) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    if *interaction == Interaction::Pressed {
+        favorites.toggle(puzzle_identifier);
+    }
+}
+
+pub fn request_hint(
+    interaction_query: Query<
+        &Interaction,
+        (Changed<Interaction>, With<Button>, With<HintButton>),
+    >,
+    mut hint_writer: EventWriter<HintRequest>,
+) {
+    let Ok(interaction) = interaction_query.get_single() else {
+        return;
+    };
+
+    if *interaction == Interaction::Pressed {
+        hint_writer.send(HintRequest);
+    }
+}
+
+pub fn update_favorite_button_text(
+    mut favorite_text_query: Query<&mut TextColor, With<FavoriteButtonText>>,
+    current_puzzle_query: Query<Ref<CurrentPuzzle>>,
+    favorites: Res<Favorites>,
+) {
+    let Ok(current_puzzle) = current_puzzle_query.get_single() else {
+        return;
+    };
+
+    let Ok(mut text_color) = favorite_text_query.get_single_mut() else {
+        return;
+    };
+
+    let CurrentPuzzle(puzzle_identifier) = current_puzzle.as_ref();
+
+    let color = if favorites.contains(puzzle_identifier) {
+        FAVORITE_TEXT_COLOR
+    } else {
+        NOT_FAVORITE_TEXT_COLOR
+    };
+
+    if text_color.0 != color {
+        text_color.0 = color;
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::{
+    pause::{PauseState, TogglePause},
+    ui::{spawn_prompt_button, PromptPlacement},
+};
+
+#[derive(Component)]
+pub struct PauseUI;
+
+#[derive(Component)]
+pub struct PauseButton;
+
+#[derive(Component)]
+pub struct PauseButtonText;
+
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_prompt_button(
+        &mut commands,
+        &asset_server,
+        PromptPlacement::BottomRight,
+        "pause",
+        PauseUI,
+        PauseButton,
+        PauseButtonText,
+    );
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<PauseUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn press(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PauseButton>)>,
+    mut toggle_pause_writer: EventWriter<TogglePause>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        toggle_pause_writer.send(TogglePause);
+    }
+}
+
+pub fn update_text(
+    pause_state: Res<State<PauseState>>,
+    mut text_query: Query<&mut Text, With<PauseButtonText>>,
+) {
+    if !pause_state.is_changed() {
+        return;
+    }
+
+    let Ok(mut text) = text_query.get_single_mut() else {
+        return;
+    };
+
+    text.0 = match pause_state.get() {
+        PauseState::Running => "pause",
+        PauseState::Paused => "resume",
+    }
+    .to_string();
+}
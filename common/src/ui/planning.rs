@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_settings::GameSettings,
+    play_statistics::{DailyTimerStart, SolveTime},
+    ui::{spawn_prompt_button, PromptPlacement},
+};
+
+#[derive(Component)]
+pub struct PlanningUI;
+
+#[derive(Component)]
+pub struct PlanningButton;
+
+// Only a small button is shown, so the maze stays in view and can be orbited while planning.
+pub fn spawn(
+    mut commands: Commands,
+    solve_time: Res<SolveTime>,
+    game_settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    if !solve_time.awaiting_start || game_settings.daily_timer_start != DailyTimerStart::AfterPlanning {
+        return;
+    }
+
+    spawn_prompt_button(
+        &mut commands,
+        &asset_server,
+        PromptPlacement::BottomCentre,
+        "planning · tap to start the clock",
+        PlanningUI,
+        PlanningButton,
+        (),
+    );
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<PlanningUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn dismiss(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PlanningButton>)>,
+    ui_entities: Query<Entity, With<PlanningUI>>,
+    mut solve_time: ResMut<SolveTime>,
+) {
+    let Ok(ui_entity) = ui_entities.get_single() else {
+        return;
+    };
+
+    let pressed = interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+
+    // A first move also starts the clock, which makes the prompt redundant.
+    if pressed || !solve_time.awaiting_start {
+        solve_time.start();
+        commands.entity(ui_entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_save::{CurrentPuzzle, PendingResume, PuzzleIdentifier},
+    game_state::GameState,
+    menu::ResumeOffer,
+    ui::{spawn_prompt_button, PromptPlacement},
+};
+
+#[derive(Component)]
+pub struct ResumeUI;
+
+#[derive(Component)]
+pub struct ResumeButton;
+
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_prompt_button(
+        &mut commands,
+        &asset_server,
+        PromptPlacement::BottomCentre,
+        "resume",
+        ResumeUI,
+        ResumeButton,
+        (),
+    );
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<ResumeUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn press(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<ResumeButton>)>,
+    mut resume_offer: ResMut<ResumeOffer>,
+    mut pending_resume: ResMut<PendingResume>,
+    mut current_puzzle_query: Query<&mut CurrentPuzzle>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if !interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+
+    let Some(progress) = resume_offer.0.take() else {
+        return;
+    };
+
+    let Ok(mut current_puzzle) = current_puzzle_query.get_single_mut() else {
+        return;
+    };
+
+    let next_game_state = match progress.puzzle {
+        PuzzleIdentifier::Level(_) => GameState::Puzzle,
+        PuzzleIdentifier::EasyDaily(_) | PuzzleIdentifier::HardDaily(_) => GameState::LoadingRemoteLevel,
+    };
+
+    current_puzzle.0 = progress.puzzle.clone();
+    pending_resume.0 = Some(progress);
+    game_state.set(next_game_state);
+}
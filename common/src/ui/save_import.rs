@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{
+    constants::{FONT_PATH, TRANSPARENCY},
+    game_save::{PendingSaveImport, ResolveSaveImport},
+    ui::{prompt_button, prompt_text, PROMPT_FONT_SIZE},
+};
+
+const TITLE_FONT_SIZE: f32 = 40.0;
+const PANEL_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, TRANSPARENCY);
+
+#[derive(Component)]
+pub struct SaveImportUI;
+
+#[derive(Component)]
+pub struct SaveImportButton(pub ResolveSaveImport);
+
+pub fn spawn(
+    mut commands: Commands,
+    pending_save_import: Res<PendingSaveImport>,
+    existing_ui_query: Query<(), With<SaveImportUI>>,
+    asset_server: Res<AssetServer>,
+) {
+    if !pending_save_import.is_changed()
+        || pending_save_import.0.is_none()
+        || !existing_ui_query.is_empty()
+    {
+        return;
+    }
+
+    let font = asset_server.load(FONT_PATH);
+    let text_node = |text: &str, font_size: f32| prompt_text(font.clone(), text, font_size);
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            GlobalZIndex(10),
+            SaveImportUI,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        max_width: Val::Percent(80.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.),
+                        padding: UiRect::all(Val::Px(20.)),
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_COLOR),
+                    BorderRadius::all(Val::Px(20.)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(text_node("import save", TITLE_FONT_SIZE));
+                    parent.spawn(text_node("merge keeps the best record of each level", PROMPT_FONT_SIZE));
+
+                    for (label, resolution) in [
+                        ("merge", ResolveSaveImport::Merge),
+                        ("overwrite", ResolveSaveImport::Overwrite),
+                        ("cancel", ResolveSaveImport::Cancel),
+                    ] {
+                        parent
+                            .spawn(prompt_button(SaveImportButton(resolution)))
+                            .with_child(text_node(label, PROMPT_FONT_SIZE));
+                    }
+                });
+        });
+}
+
+pub fn choose(
+    mut commands: Commands,
+    interaction_query: Query<(&Interaction, &SaveImportButton), Changed<Interaction>>,
+    ui_entities: Query<Entity, With<SaveImportUI>>,
+    mut resolve_save_import_events: EventWriter<ResolveSaveImport>,
+) {
+    let Some(SaveImportButton(resolution)) = interaction_query
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| button)
+    else {
+        return;
+    };
+
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    resolve_save_import_events.send(*resolution);
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_settings::GameSettings,
+    player::PlayerMazeState,
+    shape::loader::SolutionComponent,
+    snap_assist::{available_remaining_solution, SnapAssist},
+    ui::{spawn_prompt_button, PromptPlacement},
+};
+
+#[derive(Component)]
+pub struct SnapAssistUI;
+
+#[derive(Component)]
+pub struct SnapAssistButton;
+
+// Shows the button only while the player stands close enough to the goal on the solution.
+pub fn update(
+    mut commands: Commands,
+    player_query: Query<&PlayerMazeState, Changed<PlayerMazeState>>,
+    solution_query: Query<&SolutionComponent>,
+    ui_entities: Query<Entity, With<SnapAssistUI>>,
+    snap_assist: Res<SnapAssist>,
+    game_settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(player_maze_state) = player_query.get_single() else {
+        return;
+    };
+
+    let Ok(SolutionComponent(solution)) = solution_query.get_single() else {
+        return;
+    };
+
+    let available = !snap_assist.is_running()
+        && available_remaining_solution(player_maze_state, solution, &game_settings).is_some();
+
+    match (available, ui_entities.get_single()) {
+        (true, Err(_)) => spawn(&mut commands, &asset_server),
+        (false, Ok(ui_entity)) => commands.entity(ui_entity).despawn_recursive(),
+        _ => {}
+    }
+}
+
+// Raised above the planning prompt, which stays up until the first move even when the goal
+// starts within reach.
+fn spawn(commands: &mut Commands, asset_server: &AssetServer) {
+    spawn_prompt_button(
+        commands,
+        asset_server,
+        PromptPlacement::RaisedBottomCentre,
+        "assist · finish the path",
+        SnapAssistUI,
+        SnapAssistButton,
+        (),
+    );
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<SnapAssistUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn press(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<SnapAssistButton>)>,
+    ui_entities: Query<Entity, With<SnapAssistUI>>,
+    player_query: Query<&PlayerMazeState>,
+    solution_query: Query<&SolutionComponent>,
+    mut snap_assist: ResMut<SnapAssist>,
+    game_settings: Res<GameSettings>,
+) {
+    let pressed = interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+
+    if !pressed {
+        return;
+    }
+
+    let (Ok(player_maze_state), Ok(SolutionComponent(solution))) =
+        (player_query.get_single(), solution_query.get_single())
+    else {
+        return;
+    };
+
+    if let Some(remaining) = available_remaining_solution(player_maze_state, solution, &game_settings) {
+        info!(target: "mazonic::assist", "Finishing the last {} rooms with the snap assist", remaining.len());
+        snap_assist.start(remaining);
+    }
+
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::{
+    ui::{spawn_prompt_button, PromptPlacement},
+    undo::UndoMove,
+};
+
+#[derive(Component)]
+pub struct UndoUI;
+
+#[derive(Component)]
+pub struct UndoButton;
+
+// Touch screens have no undo key, so the button is always offered while playing.
+pub fn spawn(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_prompt_button(
+        &mut commands,
+        &asset_server,
+        PromptPlacement::BottomLeft,
+        "undo",
+        UndoUI,
+        UndoButton,
+        (),
+    );
+}
+
+pub fn despawn(mut commands: Commands, ui_entities: Query<Entity, With<UndoUI>>) {
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn press(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<UndoButton>)>,
+    mut undo_writer: EventWriter<UndoMove>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        undo_writer.send(UndoMove);
+    }
+}
@@ -0,0 +1,114 @@
+use bevy::prelude::*;
+
+use crate::{
+    constants::{FONT_PATH, TRANSPARENCY},
+    whats_new::{self, LastSeenVersion, OpenWhatsNew},
+    ui::{prompt_text, BUTTON_COLOR, PROMPT_FONT_SIZE},
+};
+
+const TITLE_FONT_SIZE: f32 = 40.0;
+const PANEL_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, TRANSPARENCY);
+
+#[derive(Component)]
+pub struct WhatsNewUI;
+
+#[derive(Component)]
+pub struct DismissWhatsNewButton;
+
+pub fn spawn(
+    mut commands: Commands,
+    mut open_whats_new_events: EventReader<OpenWhatsNew>,
+    last_seen_version: Res<LastSeenVersion>,
+    existing_ui_query: Query<(), With<WhatsNewUI>>,
+    asset_server: Res<AssetServer>,
+) {
+    if open_whats_new_events.read().last().is_none() || !existing_ui_query.is_empty() {
+        return;
+    }
+
+    // When reopened with nothing unseen, show the full changelog instead of an empty panel.
+    let mut entries = whats_new::unseen_entries(last_seen_version.0.as_deref());
+    if entries.is_empty() {
+        entries = whats_new::changelog();
+    }
+
+    let font = asset_server.load(FONT_PATH);
+    let text_node = |text: String, font_size: f32| prompt_text(font.clone(), text, font_size);
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            GlobalZIndex(10),
+            WhatsNewUI,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        max_width: Val::Percent(80.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(10.),
+                        padding: UiRect::all(Val::Px(20.)),
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_COLOR),
+                    BorderRadius::all(Val::Px(20.)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(text_node("what's new".to_string(), TITLE_FONT_SIZE));
+
+                    for entry in entries {
+                        parent.spawn(text_node(entry.version, PROMPT_FONT_SIZE));
+
+                        for change in entry.changes {
+                            parent.spawn(text_node(change, PROMPT_FONT_SIZE));
+                        }
+                    }
+
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(72.),
+                                height: Val::Px(72.),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BorderRadius::MAX,
+                            BackgroundColor(BUTTON_COLOR),
+                            DismissWhatsNewButton,
+                        ))
+                        .with_child(text_node("✓".to_string(), TITLE_FONT_SIZE));
+                });
+        });
+}
+
+pub fn dismiss(
+    mut commands: Commands,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<DismissWhatsNewButton>)>,
+    ui_entities: Query<Entity, With<WhatsNewUI>>,
+    mut last_seen_version: ResMut<LastSeenVersion>,
+) {
+    let Ok(interaction) = interaction_query.get_single() else {
+        return;
+    };
+
+    if *interaction != Interaction::Pressed {
+        return;
+    }
+
+    for entity in ui_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    last_seen_version.mark_seen();
+}
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use crate::{
+    controller::ControllerState,
+    is_room_junction::is_junction,
+    player::PlayerMazeState,
+    player_path::PlayerPath,
+    room::Room,
+    shape::loader::{GraphComponent, SolutionComponent},
+    sound::PreviousNoteRoom,
+};
+
+#[derive(Event)]
+pub struct UndoMove;
+
+// Every node the player has arrived at this attempt, starting with the start room.
+#[derive(Resource, Default)]
+pub struct PlayerMoveHistory(pub Vec<Room>);
+
+impl PlayerMoveHistory {
+    pub fn record(&mut self, room: Room) {
+        if self.0.last() != Some(&room) {
+            self.0.push(room);
+        }
+    }
+}
+
+pub fn reset(mut history: ResMut<PlayerMoveHistory>, solution_query: Query<&SolutionComponent>) {
+    let Ok(SolutionComponent(solution)) = solution_query.get_single() else {
+        return;
+    };
+
+    history.0 = solution.first().copied().into_iter().collect();
+}
+
+pub fn read_keys(keys: Res<ButtonInput<KeyCode>>, mut undo_writer: EventWriter<UndoMove>) {
+    let control = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    if keys.just_pressed(KeyCode::Backspace) || (control && keys.just_pressed(KeyCode::KeyZ)) {
+        undo_writer.send(UndoMove);
+    }
+}
+
+// Steps back over corridor rooms to the previous junction, where a different turn can be taken.
+pub fn undo(
+    mut undo_reader: EventReader<UndoMove>,
+    mut history: ResMut<PlayerMoveHistory>,
+    mut player_query: Query<(&mut PlayerMazeState, &mut PlayerPath)>,
+    graph_query: Query<&GraphComponent>,
+    controller_state: Res<State<ControllerState>>,
+    mut previous_note_room: ResMut<PreviousNoteRoom>,
+) {
+    if undo_reader.read().count() == 0 || *controller_state.get() == ControllerState::Solving {
+        return;
+    }
+
+    let Ok(GraphComponent(graph)) = graph_query.get_single() else {
+        return;
+    };
+
+    let Ok((mut player_maze_state, mut player_path)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    if !matches!(*player_maze_state, PlayerMazeState::Node(_)) || history.0.len() < 2 {
+        return;
+    }
+
+    history.0.pop();
+    while history.0.len() > 1 && !history.0.last().is_some_and(|room| is_junction(room, graph)) {
+        history.0.pop();
+    }
+
+    let Some(&room) = history.0.last() else {
+        return;
+    };
+
+    // Statistics only ever see the path as if the undone moves were never made.
+    if let Some(index) = player_path.0.iter().rposition(|path_room| *path_room == room) {
+        player_path.0.truncate(index + 1);
+    }
+
+    // The room was already sounded on the way out, so arriving back should stay silent.
+    previous_note_room.0 = Some(room);
+
+    debug!(target: "mazonic::undo", "Undoing back to room {}", room.id);
+    *player_maze_state = PlayerMazeState::Node(room);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use petgraph::graphmap::GraphMap;
+
+    use crate::room::{
+        test_support::{room, room_at},
+        Edge,
+    };
+
+    use super::*;
+
+    // A straight corridor 0-1-2 with a turn at 2 into room 3, so 0 and 2 are the junctions.
+    fn rooms() -> [Room; 4] {
+        [
+            room(0),
+            room(1),
+            room(2),
+            room_at(3, Vec3::new(2.0, 1.0, 0.0)),
+        ]
+    }
+
+    fn undo_world(at: Room, walked: &[Room]) -> World {
+        let rooms = rooms();
+        let mut graph = GraphMap::new();
+        for (from, to) in [(0, 1), (1, 2), (2, 3)] {
+            graph.add_edge(rooms[from], rooms[to], Edge);
+            graph.add_edge(rooms[to], rooms[from], Edge);
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Events<UndoMove>>();
+        world.init_resource::<PreviousNoteRoom>();
+        world.insert_resource(State::new(ControllerState::IdlePostView));
+        world.insert_resource(PlayerMoveHistory(walked.to_vec()));
+        world.spawn(GraphComponent(graph));
+        world.spawn((PlayerMazeState::Node(at), PlayerPath(walked.to_vec())));
+        world
+    }
+
+    fn player(world: &mut World) -> (Room, Vec<u64>) {
+        let (state, path) = world
+            .query::<(&PlayerMazeState, &PlayerPath)>()
+            .single(world);
+        let PlayerMazeState::Node(room) = state else {
+            panic!("player should be at a node");
+        };
+        (*room, path.0.iter().map(|room| room.id).collect())
+    }
+
+    #[test]
+    fn undo_at_the_start_room_does_nothing() {
+        let [start, ..] = rooms();
+        let mut world = undo_world(start, &[start]);
+
+        world.send_event(UndoMove);
+        world.run_system_once(undo).unwrap();
+
+        let (room, path) = player(&mut world);
+        assert_eq!(room.id, 0);
+        assert_eq!(path, vec![0]);
+        assert_eq!(world.resource::<PlayerMoveHistory>().0.len(), 1);
+        assert!(world.resource::<PreviousNoteRoom>().0.is_none());
+    }
+
+    #[test]
+    fn undo_steps_back_over_the_corridor_and_trims_the_path() {
+        let [start, corridor, turn, _] = rooms();
+        let mut world = undo_world(turn, &[start, corridor, turn]);
+
+        world.send_event(UndoMove);
+        world.run_system_once(undo).unwrap();
+
+        let (room, path) = player(&mut world);
+        assert_eq!(room.id, 0);
+        assert_eq!(path, vec![0]);
+        // The start room already sounded on the way out, so arriving back stays silent.
+        assert_eq!(world.resource::<PreviousNoteRoom>().0.map(|room| room.id), Some(0));
+    }
+
+    #[test]
+    fn undo_stops_at_the_last_junction() {
+        let [start, corridor, turn, end] = rooms();
+        let mut world = undo_world(end, &[start, corridor, turn, end]);
+
+        world.send_event(UndoMove);
+        world.run_system_once(undo).unwrap();
+
+        let (room, path) = player(&mut world);
+        assert_eq!(room.id, 2);
+        assert_eq!(path, vec![0, 1, 2]);
+        assert_eq!(world.resource::<PreviousNoteRoom>().0.map(|room| room.id), Some(2));
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const CHANGELOG_JSON: &str = include_str!("../../desktop/assets/changelog.json");
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ChangelogEntry {
+    pub version: String,
+    pub changes: Vec<String>,
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct LastSeenVersion(pub Option<String>);
+
+impl LastSeenVersion {
+    pub fn mark_seen(&mut self) {
+        if self.0.as_deref() != Some(CURRENT_VERSION) {
+            self.0 = Some(CURRENT_VERSION.to_string());
+        }
+    }
+}
+
+#[derive(Event, Debug, Clone, Default)]
+pub struct OpenWhatsNew;
+
+pub fn changelog() -> Vec<ChangelogEntry> {
+    serde_json::from_str(CHANGELOG_JSON).unwrap_or_else(|err| {
+        warn!(target: "mazonic::whats_new", "Failed to parse changelog: {}", err);
+        vec![]
+    })
+}
+
+// Entries are listed newest first, so everything before the last seen version is unseen.
+pub fn unseen_entries(last_seen_version: Option<&str>) -> Vec<ChangelogEntry> {
+    changelog()
+        .into_iter()
+        .take_while(|entry| Some(entry.version.as_str()) != last_seen_version)
+        .collect()
+}
+
+pub fn check_version(
+    last_seen_version: Res<LastSeenVersion>,
+    mut open_whats_new_events: EventWriter<OpenWhatsNew>,
+) {
+    let last_seen_version = last_seen_version.0.as_deref();
+
+    if last_seen_version != Some(CURRENT_VERSION) && !unseen_entries(last_seen_version).is_empty() {
+        open_whats_new_events.send(OpenWhatsNew);
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use mazonic::{
+    autoplay::{play_through, Playthrough},
+    game_save::PuzzleIdentifier,
+    shape::loader::MazeLevelData,
+};
+
+// The first campaign level of each shape, in the order they are introduced.
+const LEVELS: [(&str, usize); 5] = [
+    ("tetrahedron", 0),
+    ("cube", 1),
+    ("octahedron", 2),
+    ("dodecahedron", 3),
+    ("icosahedron", 4),
+];
+
+fn load_level(index: usize) -> MazeLevelData {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../desktop/assets/levels")
+        .join(format!("{index}.json"));
+    let bytes = std::fs::read(&path).unwrap_or_else(|error| panic!("reading {}: {error}", path.display()));
+    serde_json::from_slice(&bytes).unwrap_or_else(|error| panic!("parsing {}: {error}", path.display()))
+}
+
+#[test]
+fn following_the_solution_wins_every_shape() {
+    for (shape, index) in LEVELS {
+        let Playthrough {
+            solution_length,
+            victory_after_moves,
+            completed_before_victory,
+            completed,
+        } = play_through(load_level(index), PuzzleIdentifier::Level(index)).unwrap();
+
+        assert!(solution_length > 1, "{shape}: solution is too short to play");
+        assert_eq!(
+            victory_after_moves,
+            Some(solution_length - 1),
+            "{shape}: victory should fire on reaching the goal and not before"
+        );
+        assert!(!completed_before_victory, "{shape}: marked completed before it was played");
+        assert!(completed, "{shape}: victory did not mark the level completed");
+    }
+}
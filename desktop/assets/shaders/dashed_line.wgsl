@@ -5,6 +5,13 @@
     pbr_functions::{apply_pbr_lighting, alpha_discard, main_pass_post_lighting_processing},
 }
 
+struct DashedArrowSettings {
+    scroll_speed: f32,
+    _webgl2_padding: vec3<f32>,
+}
+
+@group(2) @binding(100) var<uniform> dashed_arrow: DashedArrowSettings;
+
 @fragment
 fn fragment(
     in: VertexOutput,
@@ -23,7 +30,8 @@ fn fragment(
     // note this does not include fullscreen postprocessing effects like bloom.
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
     
-    let sine_sample = sin(40.0 * in.uv.y - globals.time);
+    // Dashes travel towards the arrow head, showing which way the edge can be crossed.
+    let sine_sample = sin(40.0 * in.uv.y - dashed_arrow.scroll_speed * globals.time);
     let arrow_chunk = floor(1.5 * (sine_sample + 1.0));
     let in_arrow_head = floor(in.uv.y + 0.5);
     out.color = out.color * max(min(arrow_chunk, 1.0), in_arrow_head);
@@ -1,9 +1,16 @@
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::{asset::io::file::FileAssetReader, prelude::*, window::PrimaryWindow};
 
-use mazonic::{self, camera::CameraTarget, controller_screen_position::ControllerScreenPosition};
+use mazonic::{
+    self, camera::CameraTarget, controller_screen_position::ControllerScreenPosition,
+    soundfont::SoundfontSource,
+};
+
+// Resolved against the same root Bevy loads assets from, so it does not depend on the working directory.
+const SOUNDFONT_PATH: &str = "assets/marimba_chiapaneca.sf2";
 
 fn main() {
     let mut app = App::new();
+    app.insert_resource(SoundfontSource::File(FileAssetReader::get_base_path().join(SOUNDFONT_PATH)));
     mazonic::add_common_plugins(&mut app);
 
     app.add_systems(Update, update_controller_position);